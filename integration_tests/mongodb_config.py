@@ -1,8 +1,8 @@
-ORGANIZATION_DATABASE = "local"
+ORGANIZATION_DATABASE = "cooplan_organization"
 ORGANIZATION_COLLECTION = "organization"
 
-USER_DATABASE = "local"
+USER_DATABASE = "cooplan_organization"
 USER_COLLECTION = "user"
 
-INVITATION_DATABASE = "local"
+INVITATION_DATABASE = "cooplan_organization"
 INVITATION_COLLECTION = "invitation"
\ No newline at end of file
@@ -33,7 +33,7 @@ async def join_organization_and_expect_it_as_response():
         "organization_id": result.inserted_id,
         "permissions": [],
         "created_at": int(time.time()),
-        "expires_after": int(time.time()) + 3600,
+        "expires_after": 3600,
     })
 
     REQUEST = {
@@ -31,7 +31,7 @@ async def read_organization_as_expected():
         "organization_id": organization_insert.inserted_id,
         "permissions": [],
         "created_at": int(time.time()),
-        "expires_after": int(time.time()) + 3600,
+        "expires_after": 3600,
     }
 
     client[INVITATION_DATABASE][INVITATION_COLLECTION].insert_one(EXAMPLE_INVITATION)
@@ -0,0 +1,61 @@
+use cooplan_amqp_api::api::input::request::Request;
+use mongodb::bson::oid::ObjectId;
+use serde_json::Value;
+use tracing::Span;
+
+const HEADER_KEY: &str = "header";
+const CORRELATION_ID_KEY: &str = "correlation_id";
+const ACTION_KEY: &str = "action";
+
+/// Span covering the whole request, from the api down to the storage.
+pub fn request_span(request: &Request, element: &str) -> Span {
+    tracing::info_span!(
+        "request",
+        correlation_id = %correlation_id(request),
+        element,
+        action = action(request)
+    )
+}
+
+/// Action of the request's header, empty if missing.
+pub fn action(request: &Request) -> &str {
+    request
+        .data
+        .get(HEADER_KEY)
+        .and_then(|header| header.get(ACTION_KEY))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+}
+
+/// Taken from the request's header if present, otherwise a new one is generated,
+/// so the logs of every request can still be told apart.
+fn correlation_id(request: &Request) -> String {
+    match request
+        .data
+        .get(HEADER_KEY)
+        .and_then(|header| header.get(CORRELATION_ID_KEY))
+        .and_then(Value::as_str)
+    {
+        Some(correlation_id) => correlation_id.to_string(),
+        None => ObjectId::new().to_hex(),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn correlation_id_is_taken_from_header() {
+    let mut data = serde_json::Map::new();
+    data.insert(
+        HEADER_KEY.to_string(),
+        serde_json::json!({ "action": "read", "correlation_id": "CORRELATION_ID" }),
+    );
+
+    assert_eq!("CORRELATION_ID", correlation_id(&Request::new(data)));
+}
+
+#[test]
+fn correlation_id_is_generated_if_missing() {
+    let request = Request::new(serde_json::Map::new());
+
+    assert_ne!(correlation_id(&request), correlation_id(&request));
+}
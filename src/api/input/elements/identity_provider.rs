@@ -0,0 +1,168 @@
+use crate::api::input::rate_limit::RateLimiter;
+use crate::api::input::token::extract_user_id_from_token;
+use crate::api::input::{correlation, error_response, rate_limit, shutdown, timeout};
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::deadline::RequestTimeouts;
+use crate::logic::actions::identity_provider_logic_action::IdentityProviderLogicAction;
+use crate::logic::logic_request::{LogicRequest, LogicRequestSender};
+use crate::telemetry::traced_channel::Traced;
+use cooplan_amqp_api::api::input::input_element;
+use cooplan_amqp_api::api::input::input_element::InputElement;
+use cooplan_amqp_api::api::input::request::{extract_parameter_from_request_data, Request};
+use cooplan_amqp_api::error::Error;
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use cooplan_amqp_api_shared::api::input::request_result_error::{
+    RequestResultError, RequestResultErrorKind,
+};
+use cooplan_lapin_wrapper::config::api::Api;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+use tracing::Instrument;
+
+/// Notifications of the identity provider, which are only sent by its own client,
+/// since no user holds the permissions for these actions.
+const ACTIONS: &[&str] = &["user_deleted", "user_blocked", "user_unblocked"];
+
+pub fn get(
+    api: &Api,
+    request_timeouts: RequestTimeouts,
+    rate_limiter: RateLimiter,
+    claim_mapping: ClaimMappingConfig,
+) -> Result<InputElement<Traced<LogicRequest>>, Error> {
+    const ELEMENT_ID: &str = "identity_provider";
+
+    let input_api = input_element::extract_input(
+        api,
+        ELEMENT_ID,
+        Arc::new(move |request, logic_request_sender| {
+            let span = correlation::request_span(&request, ELEMENT_ID);
+            let deadline = request_timeouts.deadline(ELEMENT_ID, correlation::action(&request));
+            let verdict = rate_limiter.check(ELEMENT_ID, &request);
+            let shutting_down = logic_request_sender.is_closed();
+
+            Box::pin(
+                shutdown::hold_while_shutting_down(
+                    shutting_down,
+                    timeout::bounded(
+                        deadline,
+                        rate_limit::admit(
+                            verdict,
+                            request_handler(
+                                request,
+                                logic_request_sender.into(),
+                                claim_mapping.clone(),
+                            ),
+                        ),
+                    ),
+                )
+                .instrument(span),
+            )
+        }),
+        ACTIONS,
+    )?;
+
+    Ok(input_api)
+}
+
+async fn request_handler(
+    request: Request,
+    logic_request_sender: LogicRequestSender,
+    claim_mapping: ClaimMappingConfig,
+) -> RequestResult {
+    let action = match request.try_get_header() {
+        Ok(header) => {
+            if !ACTIONS.contains(&header.action()) {
+                return RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::MalformedRequest,
+                    format!("unknown action '{}'", header.action()),
+                ));
+            }
+
+            header.action().to_string()
+        }
+        Err(error) => return RequestResult::Err(error.into()),
+    };
+
+    let authorized_token = match request.authorized_token {
+        Some(authorized_token) => authorized_token,
+        None => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                "missing authorized token after authorization",
+            ))
+        }
+    };
+
+    let actor = match extract_user_id_from_token(&authorized_token, &claim_mapping) {
+        Ok(actor) => actor,
+        Err(request_result) => return request_result,
+    };
+
+    lifecycle_change(action, actor, request.data, logic_request_sender).await
+}
+
+/// Expected parameters:
+/// - **user_id**: String
+async fn lifecycle_change(
+    action: String,
+    actor: String,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    let user_id = match extract_parameter_from_request_data::<String>(&data, "user_id") {
+        Ok(user_id) => user_id,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = match action.as_str() {
+        "user_deleted" => IdentityProviderLogicAction::Deleted {
+            actor,
+            user_id,
+            replier,
+        },
+        "user_blocked" => IdentityProviderLogicAction::Blocked {
+            actor,
+            user_id,
+            replier,
+        },
+        "user_unblocked" => IdentityProviderLogicAction::Unblocked {
+            actor,
+            user_id,
+            replier,
+        },
+        _ => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::MalformedRequest,
+                format!("unknown action '{}'", action),
+            ))
+        }
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::IdentityProvider(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!("failed to send logic request: {}", error),
+            ))
+        }
+    }
+
+    match receiver.await {
+        Ok(result) => match result {
+            Ok(()) => RequestResult::Ok(Value::Null),
+            Err(error) => {
+                error_response::from_error("failed to apply user lifecycle change", &error)
+            }
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    }
+}
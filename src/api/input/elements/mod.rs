@@ -1,2 +1,3 @@
+pub mod identity_provider;
 pub mod organization;
 pub mod user;
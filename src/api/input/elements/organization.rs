@@ -1,7 +1,17 @@
+use crate::api::input::parameter::extract_optional_parameter_from_request_data;
+use crate::api::input::rate_limit::RateLimiter;
+use crate::api::input::token::{extract_caller_from_token, extract_user_id_from_token};
+use crate::api::input::{correlation, error_response, rate_limit, shutdown, timeout};
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::deadline::RequestTimeouts;
 use crate::logic::actions::organization_logic_action::OrganizationLogicAction;
 use crate::logic::actions::organization_root_logic_action::OrganizationRootLogicAction;
-use crate::logic::logic_request::LogicRequest;
-use async_channel::Sender;
+use crate::logic::elements::organization_listing::{OrganizationFilter, OrganizationSort};
+use crate::logic::elements::organization_root::{
+    OrganizationRootField, OrganizationRootQuery, RootPage, DEFAULT_PAGE_SIZE,
+};
+use crate::logic::logic_request::{LogicRequest, LogicRequestSender};
+use crate::telemetry::traced_channel::Traced;
 use cooplan_amqp_api::api::input::input_element;
 use cooplan_amqp_api::api::input::input_element::InputElement;
 use cooplan_amqp_api::api::input::request::{extract_parameter_from_request_data, Request};
@@ -13,25 +23,60 @@ use cooplan_amqp_api_shared::api::input::request_result_error::{
 };
 use cooplan_lapin_wrapper::config::api::Api;
 use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::Instrument;
 
 const ACTIONS: &[&str] = &[
     "create",
     "join",
     "read",
+    "read_many",
     "update",
     "delete",
+    "restore",
+    "audit",
+    "list",
     "request_permission",
 ];
 
-pub fn get(api: &Api) -> Result<InputElement<LogicRequest>, Error> {
+pub fn get(
+    api: &Api,
+    request_timeouts: RequestTimeouts,
+    rate_limiter: RateLimiter,
+    claim_mapping: ClaimMappingConfig,
+    service_scopes: Arc<HashMap<String, Vec<String>>>,
+) -> Result<InputElement<Traced<LogicRequest>>, Error> {
     const ELEMENT_ID: &str = "organization";
 
     let input_api = input_element::extract_input(
         api,
         ELEMENT_ID,
         Arc::new(move |request, logic_request_sender| {
-            Box::pin(request_handler(request, logic_request_sender))
+            let span = correlation::request_span(&request, ELEMENT_ID);
+            let deadline = request_timeouts.deadline(ELEMENT_ID, correlation::action(&request));
+            let verdict = rate_limiter.check(ELEMENT_ID, &request);
+            let shutting_down = logic_request_sender.is_closed();
+
+            Box::pin(
+                shutdown::hold_while_shutting_down(
+                    shutting_down,
+                    timeout::bounded(
+                        deadline,
+                        rate_limit::admit(
+                            verdict,
+                            request_handler(
+                                request,
+                                logic_request_sender.into(),
+                                rate_limiter.clone(),
+                                claim_mapping.clone(),
+                                service_scopes.clone(),
+                            ),
+                        ),
+                    ),
+                )
+                .instrument(span),
+            )
         }),
         ACTIONS,
     )?;
@@ -41,7 +86,10 @@ pub fn get(api: &Api) -> Result<InputElement<LogicRequest>, Error> {
 
 async fn request_handler(
     request: Request,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
+    rate_limiter: RateLimiter,
+    claim_mapping: ClaimMappingConfig,
+    service_scopes: Arc<HashMap<String, Vec<String>>>,
 ) -> RequestResult {
     let action = match request.try_get_header() {
         Ok(header) => {
@@ -70,9 +118,60 @@ async fn request_handler(
     let data = request.data;
 
     match action.as_str() {
-        "create" => create(authorized_token, data, logic_request_sender).await,
-        "join" => join(authorized_token, data, logic_request_sender).await,
-        "read" => read(authorized_token, data, logic_request_sender).await,
+        "create" => create(authorized_token, &claim_mapping, data, logic_request_sender).await,
+        "join" => {
+            join(
+                authorized_token,
+                &claim_mapping,
+                data,
+                logic_request_sender,
+                rate_limiter,
+            )
+            .await
+        }
+        "read" => {
+            read(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "read_many" => {
+            read_many(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "update" => update(authorized_token, &claim_mapping, data, logic_request_sender).await,
+        "delete" => delete(authorized_token, &claim_mapping, data, logic_request_sender).await,
+        "restore" => restore(authorized_token, &claim_mapping, data, logic_request_sender).await,
+        "list" => {
+            list(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "audit" => {
+            audit(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
         _ => {
             return RequestResult::Err(RequestResultError::new(
                 RequestResultErrorKind::MalformedRequest,
@@ -89,10 +188,11 @@ async fn request_handler(
 /// - **telephone**: String
 async fn create(
     authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
     data: Map<String, Value>,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
 ) -> RequestResult {
-    let user_id = match extract_user_id_from_token(&authorized_token) {
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
         Ok(user_id) => user_id,
         Err(request_result) => return request_result,
     };
@@ -138,10 +238,7 @@ async fn create(
                     format!("failed to serialize organization: {}", error),
                 )),
             },
-            Err(error) => RequestResult::Err(RequestResultError::new(
-                RequestResultErrorKind::MalformedRequest,
-                format!("failed to create organization: {}", error),
-            )),
+            Err(error) => error_response::from_error("failed to create organization", &error),
         },
         Err(error) => RequestResult::Err(RequestResultError::new(
             RequestResultErrorKind::InternalFailure,
@@ -152,28 +249,6 @@ async fn create(
     result
 }
 
-fn extract_user_id_from_token(authorized_token: &Token) -> Result<String, RequestResult> {
-    let user_id = match authorized_token.get("sub") {
-        Some(user_id) => match user_id.as_str() {
-            Some(user_id) => user_id.to_string(),
-            None => {
-                return Err(RequestResult::Err(RequestResultError::new(
-                    RequestResultErrorKind::MalformedRequest,
-                    "failed to read user id from token",
-                )))
-            }
-        },
-        None => {
-            return Err(RequestResult::Err(RequestResultError::new(
-                RequestResultErrorKind::MalformedRequest,
-                "failed to read user id from token",
-            )))
-        }
-    };
-
-    Ok(user_id)
-}
-
 fn extract_create_expected_parameters(
     data: Map<String, Value>,
 ) -> Result<(String, String, String, String), RequestResult> {
@@ -208,18 +283,445 @@ fn extract_create_expected_parameters(
     Ok((name, country, address, telephone))
 }
 
+/// Expected parameters:
+/// - **organization_id**: String
+/// - **version**: u64
+/// - **name**: String
+/// - **country**: String
+/// - **address**: String
+/// - **telephone**: String
+async fn update(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const ORGANIZATION_ID_KEY: &str = "organization_id";
+    const ORGANIZATION_VERSION_KEY: &str = "version";
+
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
+        Ok(user_id) => user_id,
+        Err(request_result) => return request_result,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, ORGANIZATION_ID_KEY) {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let version = match extract_parameter_from_request_data::<u64>(&data, ORGANIZATION_VERSION_KEY)
+    {
+        Ok(version) => version,
+        Err(error) => return error,
+    };
+
+    let (name, country, address, telephone) = match extract_create_expected_parameters(data) {
+        Ok((name, country, address, telephone)) => (name, country, address, telephone),
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::Update {
+        user_id,
+        organization_id,
+        version,
+        name,
+        country,
+        address,
+        telephone,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization update request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(organization) => match serde_json::to_value(organization) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize organization: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to update organization", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **organization_id**: String
+/// - **version**: u64
+async fn delete(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const ORGANIZATION_ID_KEY: &str = "organization_id";
+    const ORGANIZATION_VERSION_KEY: &str = "version";
+
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
+        Ok(user_id) => user_id,
+        Err(request_result) => return request_result,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, ORGANIZATION_ID_KEY) {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let version = match extract_parameter_from_request_data::<u64>(&data, ORGANIZATION_VERSION_KEY)
+    {
+        Ok(version) => version,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::Delete {
+        user_id,
+        organization_id,
+        version,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization delete request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(_) => RequestResult::Ok(Value::Null),
+            Err(error) => error_response::from_error("failed to delete organization", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **organization_id**: String
+async fn restore(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const ORGANIZATION_ID_KEY: &str = "organization_id";
+
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
+        Ok(user_id) => user_id,
+        Err(request_result) => return request_result,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, ORGANIZATION_ID_KEY) {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::Restore {
+        user_id,
+        organization_id,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization restore request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(organization) => match serde_json::to_value(organization) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize organization: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to restore organization", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **organization_id**: String
+/// - **page**: u64, starting from 0
+/// - **page_size**: u64
+async fn audit(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const ORGANIZATION_ID_KEY: &str = "organization_id";
+    const PAGE_KEY: &str = "page";
+    const PAGE_SIZE_KEY: &str = "page_size";
+
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "organization.audit",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, ORGANIZATION_ID_KEY) {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let page = match extract_parameter_from_request_data::<u64>(&data, PAGE_KEY) {
+        Ok(page) => page,
+        Err(error) => return error,
+    };
+
+    let page_size = match extract_parameter_from_request_data::<u64>(&data, PAGE_SIZE_KEY) {
+        Ok(page_size) => page_size,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::Audit {
+        caller,
+        organization_id,
+        page,
+        page_size,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization audit request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(records) => match serde_json::to_value(records) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize audit records: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to read organization audit", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **page_size**: u32
+///
+/// Optional parameters:
+/// - **country**: String
+/// - **name_prefix**: String
+/// - **created_after**: u64, unix timestamp in seconds
+/// - **created_before**: u64, unix timestamp in seconds
+/// - **sort**: `name_ascending` (default), `name_descending`, `created_ascending` or `created_descending`
+/// - **cursor**: String, the `next_cursor` of the previous page
+async fn list(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const PAGE_SIZE_KEY: &str = "page_size";
+    const SORT_KEY: &str = "sort";
+    const CURSOR_KEY: &str = "cursor";
+
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "organization.list",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
+    let page_size = match extract_parameter_from_request_data::<u32>(&data, PAGE_SIZE_KEY) {
+        Ok(page_size) => page_size,
+        Err(error) => return error,
+    };
+
+    let filter = match extract_list_filter(&data) {
+        Ok(filter) => filter,
+        Err(error) => return error,
+    };
+
+    let sort =
+        match extract_optional_parameter_from_request_data::<OrganizationSort>(&data, SORT_KEY) {
+            Ok(sort) => sort.unwrap_or_default(),
+            Err(error) => return error,
+        };
+
+    let cursor = match extract_optional_parameter_from_request_data::<String>(&data, CURSOR_KEY) {
+        Ok(cursor) => cursor,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::List {
+        caller,
+        filter,
+        sort,
+        cursor,
+        page_size,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization list request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(page) => match serde_json::to_value(page) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize organizations: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to list organizations", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+fn extract_list_filter(data: &Map<String, Value>) -> Result<OrganizationFilter, RequestResult> {
+    const COUNTRY_KEY: &str = "country";
+    const NAME_PREFIX_KEY: &str = "name_prefix";
+    const CREATED_AFTER_KEY: &str = "created_after";
+    const CREATED_BEFORE_KEY: &str = "created_before";
+
+    Ok(OrganizationFilter {
+        country: extract_optional_parameter_from_request_data(data, COUNTRY_KEY)?,
+        name_prefix: extract_optional_parameter_from_request_data(data, NAME_PREFIX_KEY)?,
+        created_after: extract_optional_parameter_from_request_data(data, CREATED_AFTER_KEY)?,
+        created_before: extract_optional_parameter_from_request_data(data, CREATED_BEFORE_KEY)?,
+        ids: None,
+    })
+}
+
 /// Expected parameters:
 /// - **invitation_code**: String
 async fn join(
     authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
     data: Map<String, Value>,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
+    rate_limiter: RateLimiter,
 ) -> RequestResult {
-    let user_id = match extract_user_id_from_token(&authorized_token) {
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
         Ok(user_id) => user_id,
         Err(request_result) => return request_result,
     };
 
+    if let Err(rejection) = rate_limiter.check_invitation_lockout(&user_id) {
+        return rejection;
+    }
+
     const ORGANIZATION_INVITATION_CODE_KEY: &str = "invitation_code";
 
     let invitation_code = match extract_parameter_from_request_data::<String>(
@@ -233,7 +735,7 @@ async fn join(
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
     let action = OrganizationLogicAction::Join {
-        user_id,
+        user_id: user_id.clone(),
         invitation_code,
         replier,
     };
@@ -263,10 +765,13 @@ async fn join(
                     format!("failed to serialize organization: {}", error),
                 )),
             },
-            Err(error) => RequestResult::Err(RequestResultError::new(
-                RequestResultErrorKind::MalformedRequest,
-                format!("failed to join organization: {}", error),
-            )),
+            Err(error) => {
+                if error.kind() == crate::error::ErrorKind::InvitationNotFound {
+                    rate_limiter.record_unknown_invitation(&user_id);
+                }
+
+                error_response::from_error("failed to join organization", &error)
+            }
         },
         Err(error) => RequestResult::Err(RequestResultError::new(
             RequestResultErrorKind::InternalFailure,
@@ -279,12 +784,36 @@ async fn join(
 
 /// Expected parameters:
 /// - **organization_id**: String
+///
+/// Optional parameters:
+/// - **fields**: among `organization`, `users`, `invitations` and `summary`, all of them by default
+/// - **users_page_size**: u32
+/// - **users_cursor**: String, the `next_users_cursor` of the previous read
+/// - **invitations_page_size**: u32
+/// - **invitations_cursor**: String, the `next_invitations_cursor` of the previous read
 async fn read(
     authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
     data: Map<String, Value>,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
 ) -> RequestResult {
     const READ_ORGANIZATION_ID_KEY: &str = "organization_id";
+    const FIELDS_KEY: &str = "fields";
+    const USERS_PAGE_SIZE_KEY: &str = "users_page_size";
+    const USERS_CURSOR_KEY: &str = "users_cursor";
+    const INVITATIONS_PAGE_SIZE_KEY: &str = "invitations_page_size";
+    const INVITATIONS_CURSOR_KEY: &str = "invitations_cursor";
+
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "organization.read",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
 
     let organization_id =
         match extract_parameter_from_request_data::<String>(&data, READ_ORGANIZATION_ID_KEY) {
@@ -292,10 +821,33 @@ async fn read(
             Err(error) => return error,
         };
 
+    let mut query = OrganizationRootQuery::default();
+
+    match extract_optional_parameter_from_request_data::<Vec<OrganizationRootField>>(
+        &data, FIELDS_KEY,
+    ) {
+        Ok(Some(fields)) => query.fields = fields,
+        Ok(None) => (),
+        Err(error) => return error,
+    }
+
+    query.users = match extract_root_page(&data, USERS_PAGE_SIZE_KEY, USERS_CURSOR_KEY) {
+        Ok(page) => page,
+        Err(error) => return error,
+    };
+
+    query.invitations =
+        match extract_root_page(&data, INVITATIONS_PAGE_SIZE_KEY, INVITATIONS_CURSOR_KEY) {
+            Ok(page) => page,
+            Err(error) => return error,
+        };
+
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
     let action = OrganizationRootLogicAction::Read {
+        caller,
         organization_id,
+        query,
         replier,
     };
 
@@ -324,10 +876,7 @@ async fn read(
                     format!("failed to serialize organization root: {}", error),
                 )),
             },
-            Err(error) => RequestResult::Err(RequestResultError::new(
-                RequestResultErrorKind::MalformedRequest,
-                format!("failed to read organization root: {}", error),
-            )),
+            Err(error) => error_response::from_error("failed to read organization root", &error),
         },
         Err(error) => {
             return RequestResult::Err(RequestResultError::new(
@@ -343,12 +892,99 @@ async fn read(
     result
 }
 
+/// Expected parameters:
+/// - **ids**: Vec<String>
+async fn read_many(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    const IDS_KEY: &str = "ids";
+
+    // Granted as single reads are.
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "organization.read",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
+    let ids = match extract_parameter_from_request_data::<Vec<String>>(&data, IDS_KEY) {
+        Ok(ids) => ids,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = OrganizationLogicAction::ReadMany {
+        caller,
+        ids,
+        replier,
+    };
+
+    match logic_request_sender
+        .send(LogicRequest::Organization(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!(
+                    "failed to send organization read many request to logic: {}",
+                    error
+                ),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(entries) => match serde_json::to_value(entries) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize organizations: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to read organizations", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive result from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+fn extract_root_page(
+    data: &Map<String, Value>,
+    page_size_key: &str,
+    cursor_key: &str,
+) -> Result<RootPage, RequestResult> {
+    Ok(RootPage {
+        after: extract_optional_parameter_from_request_data(data, cursor_key)?,
+        size: extract_optional_parameter_from_request_data(data, page_size_key)?
+            .unwrap_or(DEFAULT_PAGE_SIZE),
+    })
+}
+
 #[cfg(test)]
-async fn setup(logic_request_channel_boundary: usize) -> (Request, Sender<LogicRequest>) {
-    let request = Request::new(Map::new());
-    let (sender, receiver) = async_channel::bounded(logic_request_channel_boundary);
+fn unlimited_rate_limiter() -> RateLimiter {
+    let (abuse_sender, _) = tokio::sync::mpsc::channel(1);
 
-    (request, sender)
+    RateLimiter::new(
+        std::collections::HashMap::new(),
+        Default::default(),
+        Default::default(),
+        abuse_sender,
+    )
 }
 
 #[tokio::test]
@@ -363,9 +999,16 @@ async fn error_if_action_is_unknown() {
 
     let request = Request::new(map);
 
-    let (sender, receiver) = async_channel::bounded(1usize);
+    let (sender, _receiver) = crate::telemetry::traced_channel::bounded(1usize);
 
-    let result = request_handler(request, sender).await;
+    let result = request_handler(
+        request,
+        sender,
+        unlimited_rate_limiter(),
+        ClaimMappingConfig::default(),
+        Arc::new(HashMap::new()),
+    )
+    .await;
 
     match result {
         RequestResult::Ok(_) => panic!("expected RequestResult::Err"),
@@ -388,9 +1031,16 @@ async fn error_if_missing_authorized_token() {
 
     let request = Request::new(map);
 
-    let (sender, receiver) = async_channel::bounded(1usize);
+    let (sender, _receiver) = crate::telemetry::traced_channel::bounded(1usize);
 
-    let result = request_handler(request, sender).await;
+    let result = request_handler(
+        request,
+        sender,
+        unlimited_rate_limiter(),
+        ClaimMappingConfig::default(),
+        Arc::new(HashMap::new()),
+    )
+    .await;
 
     match result {
         RequestResult::Ok(_) => panic!("expected RequestResult::Err"),
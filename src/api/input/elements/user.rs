@@ -1,9 +1,15 @@
+use crate::api::input::rate_limit::RateLimiter;
+use crate::api::input::token::{extract_caller_from_token, extract_user_id_from_token};
+use crate::api::input::{correlation, error_response, rate_limit, shutdown, timeout};
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::deadline::RequestTimeouts;
 use crate::logic::actions::user_logic_action::UserLogicAction;
-use crate::logic::logic_request::LogicRequest;
-use async_channel::Sender;
+use crate::logic::logic_request::{LogicRequest, LogicRequestSender};
+use crate::telemetry::traced_channel::Traced;
 use cooplan_amqp_api::api::input::input_element;
 use cooplan_amqp_api::api::input::input_element::InputElement;
 use cooplan_amqp_api::api::input::request::{extract_parameter_from_request_data, Request};
+use cooplan_amqp_api::api::input::token::Token;
 use cooplan_amqp_api::error::Error;
 use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
 use cooplan_amqp_api_shared::api::input::request_result_error::{
@@ -11,18 +17,48 @@ use cooplan_amqp_api_shared::api::input::request_result_error::{
 };
 use cooplan_lapin_wrapper::config::api::Api;
 use serde_json::{Map, Value};
+use std::collections::HashMap;
 use std::sync::Arc;
+use tracing::Instrument;
 
-const ACTIONS: &[&str] = &["read"];
+const ACTIONS: &[&str] = &["read", "read_many", "check_permission", "edit_permissions"];
 
-pub fn get(api: &Api) -> Result<InputElement<LogicRequest>, Error> {
+pub fn get(
+    api: &Api,
+    request_timeouts: RequestTimeouts,
+    rate_limiter: RateLimiter,
+    claim_mapping: ClaimMappingConfig,
+    service_scopes: Arc<HashMap<String, Vec<String>>>,
+) -> Result<InputElement<Traced<LogicRequest>>, Error> {
     const ELEMENT_ID: &str = "user";
 
     let input_api = input_element::extract_input(
         api,
         ELEMENT_ID,
         Arc::new(move |request, logic_request_sender| {
-            Box::pin(request_handler(request, logic_request_sender))
+            let span = correlation::request_span(&request, ELEMENT_ID);
+            let deadline = request_timeouts.deadline(ELEMENT_ID, correlation::action(&request));
+            let verdict = rate_limiter.check(ELEMENT_ID, &request);
+            let shutting_down = logic_request_sender.is_closed();
+
+            Box::pin(
+                shutdown::hold_while_shutting_down(
+                    shutting_down,
+                    timeout::bounded(
+                        deadline,
+                        rate_limit::admit(
+                            verdict,
+                            request_handler(
+                                request,
+                                logic_request_sender.into(),
+                                claim_mapping.clone(),
+                                service_scopes.clone(),
+                            ),
+                        ),
+                    ),
+                )
+                .instrument(span),
+            )
         }),
         ACTIONS,
     )?;
@@ -32,7 +68,9 @@ pub fn get(api: &Api) -> Result<InputElement<LogicRequest>, Error> {
 
 async fn request_handler(
     request: Request,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
+    claim_mapping: ClaimMappingConfig,
+    service_scopes: Arc<HashMap<String, Vec<String>>>,
 ) -> RequestResult {
     let action = match request.try_get_header() {
         Ok(header) => {
@@ -48,10 +86,52 @@ async fn request_handler(
         Err(error) => return RequestResult::Err(error.into()),
     };
 
+    let authorized_token = match request.authorized_token {
+        Some(authorized_token) => authorized_token,
+        None => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                "missing authorized token after authorization",
+            ))
+        }
+    };
+
     let data = request.data;
 
     match action.as_str() {
-        "read" => read(data, logic_request_sender).await,
+        "read" => {
+            read(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "read_many" => {
+            read_many(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "check_permission" => {
+            check_permission(
+                authorized_token,
+                &claim_mapping,
+                &service_scopes,
+                data,
+                logic_request_sender,
+            )
+            .await
+        }
+        "edit_permissions" => {
+            edit_permissions(authorized_token, &claim_mapping, data, logic_request_sender).await
+        }
         _ => {
             return RequestResult::Err(RequestResultError::new(
                 RequestResultErrorKind::MalformedRequest,
@@ -64,9 +144,22 @@ async fn request_handler(
 /// Expected parameters:
 /// - **user_id**: String
 async fn read(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
     data: Map<String, Value>,
-    logic_request_sender: Sender<LogicRequest>,
+    logic_request_sender: LogicRequestSender,
 ) -> RequestResult {
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "user.read",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
     let user_id = match extract_parameter_from_request_data::<String>(&data, "user_id") {
         Ok(user_id) => user_id,
         Err(error) => return error,
@@ -74,7 +167,11 @@ async fn read(
 
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
-    let action = UserLogicAction::Read { user_id, replier };
+    let action = UserLogicAction::Read {
+        caller,
+        user_id,
+        replier,
+    };
 
     match logic_request_sender.send(LogicRequest::User(action)).await {
         Ok(_) => (),
@@ -95,10 +192,227 @@ async fn read(
                     format!("failed to serialize user: {}", error),
                 )),
             },
-            Err(error) => RequestResult::Err(RequestResultError::new(
-                RequestResultErrorKind::MalformedRequest,
-                format!("failed to read user: {}", error),
-            )),
+            Err(error) => error_response::from_error("failed to read user", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive user from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **user_ids**: Vec<String>
+async fn read_many(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    // Granted as single reads are.
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "user.read",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
+    let user_ids = match extract_parameter_from_request_data::<Vec<String>>(&data, "user_ids") {
+        Ok(user_ids) => user_ids,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = UserLogicAction::ReadMany {
+        caller,
+        user_ids,
+        replier,
+    };
+
+    match logic_request_sender.send(LogicRequest::User(action)).await {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!("failed to send logic request: {}", error),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(entries) => match serde_json::to_value(entries) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize users: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to read users", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive users from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **user_id**: String
+/// - **organization_id**: String
+/// - **permissions**: Vec<String>
+async fn check_permission(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    let caller = match extract_caller_from_token(
+        &authorized_token,
+        claim_mapping,
+        service_scopes,
+        "user.check_permission",
+    ) {
+        Ok(caller) => caller,
+        Err(request_result) => return request_result,
+    };
+
+    let user_id = match extract_parameter_from_request_data::<String>(&data, "user_id") {
+        Ok(user_id) => user_id,
+        Err(error) => return error,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, "organization_id") {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let permissions = match extract_parameter_from_request_data::<Vec<String>>(&data, "permissions")
+    {
+        Ok(permissions) => permissions,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = UserLogicAction::CheckPermission {
+        caller,
+        user_id,
+        organization_id,
+        permissions,
+        replier,
+    };
+
+    match logic_request_sender.send(LogicRequest::User(action)).await {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!("failed to send logic request: {}", error),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(granted) => match serde_json::to_value(granted) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize permission check: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to check permissions", &error),
+        },
+        Err(error) => RequestResult::Err(RequestResultError::new(
+            RequestResultErrorKind::InternalFailure,
+            format!("failed to receive permission check from logic: {}", error),
+        )),
+    };
+
+    result
+}
+
+/// Expected parameters:
+/// - **target_user**: String
+/// - **organization_id**: String
+/// - **permissions**: Vec<String>
+/// - **version**: u64
+async fn edit_permissions(
+    authorized_token: Token,
+    claim_mapping: &ClaimMappingConfig,
+    data: Map<String, Value>,
+    logic_request_sender: LogicRequestSender,
+) -> RequestResult {
+    let user_id = match extract_user_id_from_token(&authorized_token, claim_mapping) {
+        Ok(user_id) => user_id,
+        Err(request_result) => return request_result,
+    };
+
+    let target_user_id = match extract_parameter_from_request_data::<String>(&data, "target_user") {
+        Ok(target_user_id) => target_user_id,
+        Err(error) => return error,
+    };
+
+    let organization_id =
+        match extract_parameter_from_request_data::<String>(&data, "organization_id") {
+            Ok(organization_id) => organization_id,
+            Err(error) => return error,
+        };
+
+    let permissions = match extract_parameter_from_request_data::<Vec<String>>(&data, "permissions")
+    {
+        Ok(permissions) => permissions,
+        Err(error) => return error,
+    };
+
+    let version = match extract_parameter_from_request_data::<u64>(&data, "version") {
+        Ok(version) => version,
+        Err(error) => return error,
+    };
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let action = UserLogicAction::EditPermissions {
+        user_id,
+        target_user_id,
+        organization_id,
+        permissions,
+        version,
+        replier,
+    };
+
+    match logic_request_sender.send(LogicRequest::User(action)).await {
+        Ok(_) => (),
+        Err(error) => {
+            return RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::InternalFailure,
+                format!("failed to send logic request: {}", error),
+            ))
+        }
+    }
+
+    let result = match receiver.await {
+        Ok(result) => match result {
+            Ok(user) => match serde_json::to_value(user) {
+                Ok(value) => RequestResult::Ok(value),
+                Err(error) => RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::InternalFailure,
+                    format!("failed to serialize user: {}", error),
+                )),
+            },
+            Err(error) => error_response::from_error("failed to edit user permissions", &error),
         },
         Err(error) => RequestResult::Err(RequestResultError::new(
             RequestResultErrorKind::InternalFailure,
@@ -0,0 +1,86 @@
+use crate::error::Error;
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use cooplan_amqp_api_shared::api::input::request_result_error::{
+    RequestResultError, RequestResultErrorKind,
+};
+use serde::Serialize;
+
+/// Carried as the message of the request's error, since its envelope is shared with other services.
+#[derive(Serialize)]
+struct ErrorPayload<'a> {
+    code: &'static str,
+    message: &'a str,
+    field: Option<&'static str>,
+    retryable: bool,
+}
+
+/// Answers with the error's code, the field which failed validation and whether the request may be
+/// retried. Failures within the service are reported as such, rather than as malformed requests.
+pub fn from_error(context: &str, error: &Error) -> RequestResult {
+    let kind = if error.kind().is_internal() {
+        RequestResultErrorKind::InternalFailure
+    } else {
+        RequestResultErrorKind::MalformedRequest
+    };
+
+    let message = format!("{}: {}", context, error);
+
+    let payload = ErrorPayload {
+        code: error.kind().code(),
+        message: &message,
+        field: error.field,
+        retryable: error.kind().is_retryable(),
+    };
+
+    match serde_json::to_string(&payload) {
+        Ok(payload) => RequestResult::Err(RequestResultError::new(kind, payload)),
+        Err(serialization_error) => {
+            log::error!("failed to serialize error payload: {}", serialization_error);
+
+            RequestResult::Err(RequestResultError::new(kind, message))
+        }
+    }
+}
+
+#[cfg(test)]
+fn payload(result: RequestResult) -> (RequestResultErrorKind, serde_json::Value) {
+    match result {
+        RequestResult::Err(error) => (error.kind(), serde_json::from_str(error.message()).unwrap()),
+        RequestResult::Ok(_) => panic!("expected RequestResult::Err"),
+    }
+}
+
+#[test]
+fn report_client_errors_with_their_code_and_field() {
+    use crate::error::ErrorKind;
+
+    let error =
+        Error::new(ErrorKind::NameAlreadyTaken, "name is already being used").with_field("name");
+
+    let (kind, payload) = payload(from_error("failed to create organization", &error));
+
+    assert_eq!(RequestResultErrorKind::MalformedRequest, kind);
+    assert_eq!(
+        serde_json::json!({
+            "code": "name_already_taken",
+            "message": "failed to create organization: name is already being used",
+            "field": "name",
+            "retryable": false
+        }),
+        payload
+    );
+}
+
+#[test]
+fn report_internal_failures_as_retryable() {
+    use crate::error::ErrorKind;
+
+    let error = Error::new(ErrorKind::StorageFailure, "connection refused");
+
+    let (kind, payload) = payload(from_error("failed to create organization", &error));
+
+    assert_eq!(RequestResultErrorKind::InternalFailure, kind);
+    assert_eq!("storage_failure", payload["code"]);
+    assert_eq!(serde_json::Value::Null, payload["field"]);
+    assert_eq!(true, payload["retryable"]);
+}
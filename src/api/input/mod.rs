@@ -1,2 +1,9 @@
+mod correlation;
 pub mod elements;
+pub mod error_response;
+pub mod parameter;
+pub mod rate_limit;
 pub mod registration;
+pub mod shutdown;
+pub mod timeout;
+pub mod token;
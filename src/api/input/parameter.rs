@@ -0,0 +1,37 @@
+use cooplan_amqp_api::api::input::request::extract_parameter_from_request_data;
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// Like `extract_parameter_from_request_data`, but missing or null parameters are `None`.
+pub fn extract_optional_parameter_from_request_data<ParameterType: DeserializeOwned>(
+    request_data: &Map<String, Value>,
+    key: &str,
+) -> Result<Option<ParameterType>, RequestResult> {
+    if !request_data.contains_key(key) {
+        return Ok(None);
+    }
+
+    extract_parameter_from_request_data::<Option<ParameterType>>(request_data, key)
+}
+
+#[cfg(test)]
+#[test]
+fn missing_and_null_parameters_are_none() {
+    let data = serde_json::json!({ "country": null, "page_size": 10, "cursor": 1 });
+    let data = data.as_object().unwrap();
+
+    assert_eq!(
+        Some(None),
+        extract_optional_parameter_from_request_data::<String>(data, "country").ok()
+    );
+    assert_eq!(
+        Some(None),
+        extract_optional_parameter_from_request_data::<String>(data, "name_prefix").ok()
+    );
+    assert_eq!(
+        Some(Some(10)),
+        extract_optional_parameter_from_request_data::<u32>(data, "page_size").ok()
+    );
+    assert!(extract_optional_parameter_from_request_data::<String>(data, "cursor").is_err());
+}
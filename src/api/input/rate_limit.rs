@@ -0,0 +1,342 @@
+use crate::api::input::{correlation, error_response};
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::config::rate_limit_config::{InvitationLockoutConfig, RateLimitConfig};
+use crate::error::{Error, ErrorKind};
+use crate::logic::elements::abuse_event::AbuseEvent;
+use crate::telemetry::metrics;
+use cooplan_amqp_api::api::input::request::Request;
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::mpsc::Sender;
+
+/// Tracked windows above which the expired ones are discarded.
+const PRUNE_THRESHOLD: usize = 10_000;
+
+struct Window {
+    started: Instant,
+    count: u32,
+}
+
+impl Window {
+    fn new() -> Window {
+        Window {
+            started: Instant::now(),
+            count: 0,
+        }
+    }
+
+    /// Counts a new hit within the window, starting a new one if expired.
+    /// Returns the time left until the window expires.
+    fn hit(&mut self, duration: Duration) -> (u32, Duration) {
+        if self.started.elapsed() >= duration {
+            *self = Window::new();
+        }
+
+        self.count += 1;
+
+        (self.count, duration.saturating_sub(self.started.elapsed()))
+    }
+}
+
+struct State {
+    /// By `element.action`.
+    limits: HashMap<String, RateLimitConfig>,
+    lockout: InvitationLockoutConfig,
+    /// By user and `element.action`.
+    windows: HashMap<(String, String), Window>,
+    invitation_failures: HashMap<String, Window>,
+    lockouts: HashMap<String, Instant>,
+}
+
+/// Limits the requests each user, identified by the claim mapping, may send to an action,
+/// and locks out from joining organizations those who try too many unknown invitation codes.
+#[derive(Clone)]
+pub struct RateLimiter {
+    claim_mapping: Arc<ClaimMappingConfig>,
+    state: Arc<Mutex<State>>,
+    abuse_sender: Sender<AbuseEvent>,
+}
+
+impl RateLimiter {
+    pub fn new(
+        limits: HashMap<String, RateLimitConfig>,
+        lockout: InvitationLockoutConfig,
+        claim_mapping: ClaimMappingConfig,
+        abuse_sender: Sender<AbuseEvent>,
+    ) -> RateLimiter {
+        RateLimiter {
+            claim_mapping: Arc::new(claim_mapping),
+            state: Arc::new(Mutex::new(State {
+                limits,
+                lockout,
+                windows: HashMap::new(),
+                invitation_failures: HashMap::new(),
+                lockouts: HashMap::new(),
+            })),
+            abuse_sender,
+        }
+    }
+
+    /// Applies new limits, keeping the windows and lockouts already being tracked.
+    pub fn update(
+        &self,
+        limits: HashMap<String, RateLimitConfig>,
+        lockout: InvitationLockoutConfig,
+    ) {
+        if let Ok(mut state) = self.state.lock() {
+            state.limits = limits;
+            state.lockout = lockout;
+        }
+    }
+
+    /// Rejects the request if its user has exceeded the rate limit of its action.
+    pub fn check(&self, element: &'static str, request: &Request) -> Result<(), RequestResult> {
+        let action = correlation::action(request);
+        let key = format!("{}.{}", element, action);
+
+        // Tokens without a subject are rejected by the actions themselves.
+        let user_id = match request
+            .authorized_token
+            .as_ref()
+            .and_then(|token| token.get(self.claim_mapping.user_id.as_str()))
+            .and_then(Value::as_str)
+        {
+            Some(user_id) => user_id.to_string(),
+            None => return Ok(()),
+        };
+
+        let (limit, count, retry_after) = match self.state.lock() {
+            Ok(mut state) => {
+                let limit = match state.limits.get(&key) {
+                    Some(limit) => *limit,
+                    None => return Ok(()),
+                };
+
+                if state.windows.len() >= PRUNE_THRESHOLD {
+                    let longest = state
+                        .limits
+                        .values()
+                        .map(|limit| limit.window_in_seconds)
+                        .max()
+                        .unwrap_or_default();
+
+                    state.windows.retain(|_, window| {
+                        window.started.elapsed() < Duration::from_secs(longest)
+                    });
+                }
+
+                let (count, retry_after) = state
+                    .windows
+                    .entry((user_id.clone(), key.clone()))
+                    .or_insert_with(Window::new)
+                    .hit(Duration::from_secs(limit.window_in_seconds));
+
+                (limit, count, retry_after)
+            }
+            Err(_) => return Ok(()),
+        };
+
+        if count <= limit.requests {
+            return Ok(());
+        }
+
+        log::warn!("rate limited user '{}' on '{}'", user_id, key);
+        metrics::record_rate_limited(element, action);
+
+        // Only the first rejection of each window is audited, so the audit log cannot be flooded.
+        if count == limit.requests + 1 {
+            self.report(AbuseEvent::RateLimited {
+                user_id,
+                action: key,
+            });
+        }
+
+        Err(rejection(format!(
+            "rate limit exceeded, retry in {} seconds",
+            retry_after.as_secs().max(1)
+        )))
+    }
+
+    /// Rejects the user if they are locked out from joining organizations.
+    pub fn check_invitation_lockout(&self, user_id: &str) -> Result<(), RequestResult> {
+        let locked_until = match self.state.lock() {
+            Ok(mut state) => match state.lockouts.get(user_id).copied() {
+                Some(locked_until) if locked_until > Instant::now() => locked_until,
+                Some(_) => {
+                    state.lockouts.remove(user_id);
+                    return Ok(());
+                }
+                None => return Ok(()),
+            },
+            Err(_) => return Ok(()),
+        };
+
+        Err(rejection(format!(
+            "too many unknown invitation codes, retry in {} seconds",
+            locked_until
+                .saturating_duration_since(Instant::now())
+                .as_secs()
+                .max(1)
+        )))
+    }
+
+    /// Locks the user out once they have tried too many unknown invitation codes within the window.
+    pub fn record_unknown_invitation(&self, user_id: &str) {
+        let lockout = match self.state.lock() {
+            Ok(mut state) => {
+                let config = state.lockout;
+                let lockout = Duration::from_secs(config.lockout_in_seconds);
+
+                let (failures, _) = state
+                    .invitation_failures
+                    .entry(user_id.to_string())
+                    .or_insert_with(Window::new)
+                    .hit(Duration::from_secs(config.window_in_seconds));
+
+                if failures < config.failures {
+                    return;
+                }
+
+                state.invitation_failures.remove(user_id);
+                state
+                    .lockouts
+                    .insert(user_id.to_string(), Instant::now() + lockout);
+
+                lockout
+            }
+            Err(_) => return,
+        };
+
+        log::warn!(
+            "locked user '{}' out of joining organizations for {} seconds",
+            user_id,
+            lockout.as_secs()
+        );
+        metrics::record_invitation_lockout();
+
+        let locked_until = (SystemTime::now() + lockout)
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        self.report(AbuseEvent::InvitationLockout {
+            user_id: user_id.to_string(),
+            locked_until,
+        });
+    }
+
+    fn report(&self, event: AbuseEvent) {
+        if let Err(error) = self.abuse_sender.try_send(event) {
+            log::warn!("failed to report abuse event: {}", error);
+        }
+    }
+}
+
+/// Runs the handler only if the request has not been rejected.
+pub async fn admit<F: Future<Output = RequestResult>>(
+    verdict: Result<(), RequestResult>,
+    handler: F,
+) -> RequestResult {
+    match verdict {
+        Ok(()) => handler.await,
+        Err(rejection) => rejection,
+    }
+}
+
+fn rejection(message: String) -> RequestResult {
+    let error = Error::new(ErrorKind::RateLimited, message);
+
+    error_response::from_error("request rejected", &error)
+}
+
+#[cfg(test)]
+fn rate_limiter(abuse_sender: Sender<AbuseEvent>) -> RateLimiter {
+    RateLimiter::new(
+        HashMap::from([(
+            "organization.create".to_string(),
+            RateLimitConfig {
+                requests: 2,
+                window_in_seconds: 60,
+            },
+        )]),
+        InvitationLockoutConfig {
+            failures: 3,
+            window_in_seconds: 60,
+            lockout_in_seconds: 60,
+        },
+        ClaimMappingConfig::default(),
+        abuse_sender,
+    )
+}
+
+#[cfg(test)]
+fn request(action: &str, user_id: &str) -> Request {
+    let token = cooplan_amqp_api::api::input::token::Token::try_new(jsonwebtoken::TokenData {
+        header: jsonwebtoken::Header::default(),
+        claims: HashMap::from([
+            ("sub".to_string(), Value::from(user_id)),
+            (
+                "permissions".to_string(),
+                serde_json::json!(["create:organization"]),
+            ),
+        ]),
+    })
+    .unwrap();
+
+    Request {
+        data: serde_json::json!({ "header": { "action": action } })
+            .as_object()
+            .unwrap()
+            .clone(),
+        authorized_token: Some(token),
+    }
+}
+
+#[tokio::test]
+async fn limit_requests_of_each_user_within_the_window() {
+    let (abuse_sender, mut abuse_receiver) = tokio::sync::mpsc::channel(4);
+    let rate_limiter = rate_limiter(abuse_sender);
+
+    let create = request("create", "USER");
+
+    assert!(rate_limiter.check("organization", &create).is_ok());
+    assert!(rate_limiter.check("organization", &create).is_ok());
+    assert!(rate_limiter.check("organization", &create).is_err());
+    assert!(rate_limiter.check("organization", &create).is_err());
+
+    assert!(rate_limiter
+        .check("organization", &request("create", "OTHER_USER"))
+        .is_ok());
+    assert!(rate_limiter
+        .check("organization", &request("update", "USER"))
+        .is_ok());
+
+    assert!(matches!(
+        abuse_receiver.try_recv(),
+        Ok(AbuseEvent::RateLimited { .. })
+    ));
+    assert!(abuse_receiver.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn lock_out_users_trying_too_many_unknown_invitations() {
+    let (abuse_sender, mut abuse_receiver) = tokio::sync::mpsc::channel(4);
+    let rate_limiter = rate_limiter(abuse_sender);
+
+    rate_limiter.record_unknown_invitation("USER");
+    rate_limiter.record_unknown_invitation("USER");
+    assert!(rate_limiter.check_invitation_lockout("USER").is_ok());
+
+    rate_limiter.record_unknown_invitation("USER");
+    assert!(rate_limiter.check_invitation_lockout("USER").is_err());
+    assert!(rate_limiter.check_invitation_lockout("OTHER_USER").is_ok());
+
+    assert!(matches!(
+        abuse_receiver.try_recv(),
+        Ok(AbuseEvent::InvitationLockout { .. })
+    ));
+}
@@ -1,12 +1,39 @@
 use crate::api::input::elements;
+use crate::api::input::rate_limit::RateLimiter;
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::deadline::RequestTimeouts;
 use crate::logic::logic_request::LogicRequest;
+use crate::telemetry::traced_channel::Traced;
 use cooplan_amqp_api::api::input::input_element::InputElement;
 use cooplan_amqp_api::error::Error;
 use cooplan_lapin_wrapper::config::api::Api;
+use std::collections::HashMap;
+use std::sync::Arc;
 
-pub fn register(api: &Api) -> Result<Vec<InputElement<LogicRequest>>, Error> {
-    let elements: Vec<InputElement<LogicRequest>> =
-        vec![elements::organization::get(api)?, elements::user::get(api)?];
+pub fn register(
+    api: &Api,
+    request_timeouts: RequestTimeouts,
+    rate_limiter: RateLimiter,
+    claim_mapping: ClaimMappingConfig,
+    service_scopes: Arc<HashMap<String, Vec<String>>>,
+) -> Result<Vec<InputElement<Traced<LogicRequest>>>, Error> {
+    let elements: Vec<InputElement<Traced<LogicRequest>>> = vec![
+        elements::organization::get(
+            api,
+            request_timeouts.clone(),
+            rate_limiter.clone(),
+            claim_mapping.clone(),
+            service_scopes.clone(),
+        )?,
+        elements::user::get(
+            api,
+            request_timeouts.clone(),
+            rate_limiter.clone(),
+            claim_mapping.clone(),
+            service_scopes,
+        )?,
+        elements::identity_provider::get(api, request_timeouts, rate_limiter, claim_mapping)?,
+    ];
 
     Ok(elements)
 }
@@ -0,0 +1,36 @@
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use std::future::Future;
+
+/// Holds the requests received once the service is shutting down, since the api cannot stop consuming,
+/// neither acknowledging nor rejecting them, so the broker requeues them once the connection closes.
+pub async fn hold_while_shutting_down<F: Future<Output = RequestResult>>(
+    shutting_down: bool,
+    handler: F,
+) -> RequestResult {
+    if shutting_down {
+        log::info!("holding request received while shutting down");
+
+        return std::future::pending().await;
+    }
+
+    handler.await
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn hold_requests_received_while_shutting_down() {
+    use std::time::Duration;
+
+    let held = tokio::time::timeout(
+        Duration::from_millis(10),
+        hold_while_shutting_down(true, async { RequestResult::Ok(serde_json::Value::Null) }),
+    )
+    .await;
+
+    assert!(held.is_err());
+
+    let handled =
+        hold_while_shutting_down(false, async { RequestResult::Ok(serde_json::Value::Null) }).await;
+
+    assert!(matches!(handled, RequestResult::Ok(_)));
+}
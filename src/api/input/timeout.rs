@@ -0,0 +1,55 @@
+use crate::api::input::error_response;
+use crate::deadline;
+use crate::error::{Error, ErrorKind};
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use std::future::Future;
+use tokio::time::Instant;
+
+/// Answers with a `Timeout` error if the handler has not answered by the deadline,
+/// dropping it along with the logic and storage work still pending for it.
+pub async fn bounded<F: Future<Output = RequestResult>>(
+    deadline: Instant,
+    handler: F,
+) -> RequestResult {
+    let started = Instant::now();
+
+    tokio::select! {
+        // Checked first, so a request whose logic has been dropped because of the same
+        // deadline is reported as timed out, rather than as a failure to receive its response.
+        biased;
+        _ = tokio::time::sleep_until(deadline) => {
+            let error = Error::new(
+                ErrorKind::Timeout,
+                format!(
+                    "request timed out after {} ms",
+                    started.elapsed().as_millis()
+                ),
+            );
+
+            log::warn!("{}", error);
+
+            error_response::from_error("failed to answer request", &error)
+        }
+        result = deadline::scope(deadline, handler) => result,
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn answer_with_timeout_once_the_deadline_is_reached() {
+    use std::time::Duration;
+
+    let deadline = Instant::now() + Duration::from_millis(10);
+
+    let result = bounded(deadline, async {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+
+        RequestResult::Ok(serde_json::Value::Null)
+    })
+    .await;
+
+    match result {
+        RequestResult::Err(error) => assert!(error.message().contains("timed out")),
+        RequestResult::Ok(_) => panic!("request should have timed out"),
+    }
+}
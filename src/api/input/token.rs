@@ -0,0 +1,190 @@
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::logic::caller::Caller;
+use cooplan_amqp_api::api::input::token::Token;
+use cooplan_amqp_api_shared::api::input::request_result::RequestResult;
+use cooplan_amqp_api_shared::api::input::request_result_error::{
+    RequestResultError, RequestResultErrorKind,
+};
+use serde_json::Value;
+use std::collections::HashMap;
+
+pub fn extract_user_id_from_token(
+    authorized_token: &Token,
+    claim_mapping: &ClaimMappingConfig,
+) -> Result<String, RequestResult> {
+    let user_id = match authorized_token.get(claim_mapping.user_id.as_str()) {
+        Some(user_id) => match user_id.as_str() {
+            Some(user_id) => user_id.to_string(),
+            None => {
+                return Err(RequestResult::Err(RequestResultError::new(
+                    RequestResultErrorKind::MalformedRequest,
+                    "failed to read user id from token",
+                )))
+            }
+        },
+        None => {
+            return Err(RequestResult::Err(RequestResultError::new(
+                RequestResultErrorKind::MalformedRequest,
+                "failed to read user id from token",
+            )))
+        }
+    };
+
+    Ok(user_id)
+}
+
+/// Identifies the caller as a service if its token holds a scope granting the action, by `element.action`,
+/// or as a user otherwise. Client credentials tokens hold the service's client id where users hold theirs.
+pub fn extract_caller_from_token(
+    authorized_token: &Token,
+    claim_mapping: &ClaimMappingConfig,
+    service_scopes: &HashMap<String, Vec<String>>,
+    action: &str,
+) -> Result<Caller, RequestResult> {
+    let id = extract_user_id_from_token(authorized_token, claim_mapping)?;
+
+    let scope = extract_scopes_from_token(authorized_token, claim_mapping)
+        .into_iter()
+        .find(|scope| match service_scopes.get(scope) {
+            Some(actions) => actions.iter().any(|granted| granted == action),
+            None => false,
+        });
+
+    let caller = match scope {
+        Some(scope) => Caller::Service {
+            client_id: id,
+            scope,
+        },
+        None => Caller::User(id),
+    };
+
+    Ok(caller)
+}
+
+/// Email of the user, only if the identity provider has verified it.
+pub fn extract_verified_email_from_token(
+    authorized_token: &Token,
+    claim_mapping: &ClaimMappingConfig,
+) -> Option<String> {
+    // Some identity providers send booleans as strings.
+    let verified = match authorized_token.get(claim_mapping.email_verified.as_str()) {
+        Some(Value::Bool(verified)) => *verified,
+        Some(Value::String(verified)) => verified == "true",
+        _ => false,
+    };
+
+    if !verified {
+        return None;
+    }
+
+    authorized_token
+        .get(claim_mapping.email.as_str())
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+/// Scopes granted to the token, either space separated or as an array.
+pub fn extract_scopes_from_token(
+    authorized_token: &Token,
+    claim_mapping: &ClaimMappingConfig,
+) -> Vec<String> {
+    match authorized_token.get(claim_mapping.scopes.as_str()) {
+        Some(Value::String(scopes)) => scopes.split_whitespace().map(str::to_string).collect(),
+        Some(Value::Array(scopes)) => scopes
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn read_identity_through_the_claim_mapping() {
+    let token = Token::try_new(jsonwebtoken::TokenData {
+        header: jsonwebtoken::Header::default(),
+        claims: HashMap::from([
+            ("sub".to_string(), Value::from("PAIRWISE_ID")),
+            ("oid".to_string(), Value::from("USER")),
+            ("mail".to_string(), Value::from("user@example.com")),
+            ("mail_verified".to_string(), Value::from("true")),
+            (
+                "scp".to_string(),
+                Value::from("organization:read:any membership:check"),
+            ),
+            (
+                "permissions".to_string(),
+                serde_json::json!(["create:organization"]),
+            ),
+        ]),
+    })
+    .unwrap();
+
+    let claim_mapping = ClaimMappingConfig {
+        user_id: "oid".to_string(),
+        email: "mail".to_string(),
+        email_verified: "mail_verified".to_string(),
+        scopes: "scp".to_string(),
+    };
+
+    assert_eq!(
+        Some("USER".to_string()),
+        extract_user_id_from_token(&token, &claim_mapping).ok()
+    );
+    assert_eq!(
+        Some("user@example.com".to_string()),
+        extract_verified_email_from_token(&token, &claim_mapping)
+    );
+    assert_eq!(
+        vec!["organization:read:any", "membership:check"],
+        extract_scopes_from_token(&token, &claim_mapping)
+    );
+
+    let default_mapping = ClaimMappingConfig::default();
+
+    assert_eq!(
+        Some("PAIRWISE_ID".to_string()),
+        extract_user_id_from_token(&token, &default_mapping).ok()
+    );
+    assert_eq!(
+        None,
+        extract_verified_email_from_token(&token, &default_mapping)
+    );
+    assert!(extract_scopes_from_token(&token, &default_mapping).is_empty());
+}
+
+#[test]
+fn identify_services_by_the_scopes_granting_the_action() {
+    let token = Token::try_new(jsonwebtoken::TokenData {
+        header: jsonwebtoken::Header::default(),
+        claims: HashMap::from([
+            ("sub".to_string(), Value::from("BILLING@clients")),
+            ("scope".to_string(), Value::from("organization:read:any")),
+            (
+                "permissions".to_string(),
+                serde_json::json!(["read:organization"]),
+            ),
+        ]),
+    })
+    .unwrap();
+
+    let claim_mapping = ClaimMappingConfig::default();
+    let service_scopes = HashMap::from([(
+        "organization:read:any".to_string(),
+        vec!["organization.read".to_string()],
+    )]);
+
+    assert_eq!(
+        Some(Caller::Service {
+            client_id: "BILLING@clients".to_string(),
+            scope: "organization:read:any".to_string()
+        }),
+        extract_caller_from_token(&token, &claim_mapping, &service_scopes, "organization.read")
+            .ok()
+    );
+    assert_eq!(
+        Some(Caller::User("BILLING@clients".to_string())),
+        extract_caller_from_token(&token, &claim_mapping, &service_scopes, "user.read").ok()
+    );
+}
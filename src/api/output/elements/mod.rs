@@ -1 +1 @@
-
+pub mod organization_event;
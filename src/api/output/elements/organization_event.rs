@@ -0,0 +1,28 @@
+use crate::api::output::publisher::ConfirmedPublisher;
+use crate::error::{Error, ErrorKind};
+use crate::logic::elements::domain_event::OUTPUT_ELEMENT_ID;
+use cooplan_lapin_wrapper::config::amqp_connect_config::AmqpConnectConfig;
+use cooplan_lapin_wrapper::config::api::Api;
+
+/// Publishes the domain events of organizations, users and invitations.
+/// Events are confirmed by the broker, so the outbox relay only removes those which are published.
+pub async fn publisher(
+    api: &Api,
+    connect_config: AmqpConnectConfig,
+) -> Result<ConfirmedPublisher, Error> {
+    let output_api = match api
+        .output()
+        .iter()
+        .find(|output_api| output_api.id() == OUTPUT_ELEMENT_ID)
+    {
+        Some(output_api) => output_api,
+        None => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("failed to find output api with id '{}'", OUTPUT_ELEMENT_ID),
+            ))
+        }
+    };
+
+    ConfirmedPublisher::connect(connect_config, output_api.clone()).await
+}
@@ -0,0 +1,235 @@
+use crate::error::{Error, ErrorKind};
+use crate::health::heartbeat::Heartbeat;
+use cooplan_lapin_wrapper::config::amqp_connect_config::AmqpConnectConfig;
+use cooplan_lapin_wrapper::config::amqp_output_api::AmqpOutputApi;
+use lapin::options::ConfirmSelectOptions;
+use lapin::{Channel, Connection};
+use serde_json::Value;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex};
+
+const MIN_RECONNECT_DELAY: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// Publishes to an output queue, unlike the output elements of the api,
+/// only answering once the broker has confirmed the publication.
+/// Lost connections are opened again, backing off exponentially while they keep failing.
+#[derive(Clone)]
+pub struct ConfirmedPublisher {
+    connect_config: Arc<AmqpConnectConfig>,
+    output_api: AmqpOutputApi,
+    link: Arc<Mutex<Link>>,
+}
+
+/// Connection in use, if any, along with when it may be opened again after a failure.
+struct Link {
+    connection: Option<(Connection, Channel)>,
+    failed_attempts: u32,
+    reconnect_at: Instant,
+}
+
+impl ConfirmedPublisher {
+    /// Opens a channel in confirm mode and declares the output queue.
+    pub async fn connect(
+        connect_config: AmqpConnectConfig,
+        output_api: AmqpOutputApi,
+    ) -> Result<ConfirmedPublisher, Error> {
+        let connection = open(&connect_config, &output_api).await?;
+
+        Ok(ConfirmedPublisher {
+            connect_config: Arc::new(connect_config),
+            output_api,
+            link: Arc::new(Mutex::new(Link {
+                connection: Some(connection),
+                failed_attempts: 0,
+                reconnect_at: Instant::now(),
+            })),
+        })
+    }
+
+    /// Fails unless the broker acknowledges the publication.
+    pub async fn publish(&self, data: &Value) -> Result<(), Error> {
+        let payload = match serde_json::to_vec(data) {
+            Ok(payload) => payload,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::InternalFailure,
+                    format!("failed to serialize output data as bytes: {}", error),
+                ))
+            }
+        };
+
+        let channel = self.channel().await?;
+        let publish = self.output_api.publish();
+
+        let confirmation = match channel
+            .basic_publish(
+                publish.exchange(),
+                self.output_api.queue().name(),
+                *publish.options(),
+                payload.as_slice(),
+                publish.properties().clone(),
+            )
+            .await
+        {
+            Ok(confirm) => confirm.await,
+            Err(error) => Err(error),
+        };
+
+        match confirmation {
+            Ok(confirmation) if confirmation.is_ack() => Ok(()),
+            Ok(_) => Err(Error::new(
+                ErrorKind::ApiFailure,
+                "publication was rejected by the broker",
+            )),
+            Err(error) => {
+                // The connection is opened again by the next publication.
+                self.link.lock().await.connection = None;
+
+                Err(Error::new(
+                    ErrorKind::ApiFailure,
+                    format!("failed to publish to queue: {}", error),
+                ))
+            }
+        }
+    }
+
+    /// Beats while the publisher is connected, reconnecting in between publications,
+    /// so an outage is reported even while there is nothing to publish.
+    pub async fn monitor(self, heartbeat: Heartbeat, mut stop: watch::Receiver<bool>) {
+        loop {
+            match self.channel().await {
+                Ok(_) => heartbeat.beat(),
+                Err(error) => log::warn!("amqp publisher is unavailable: {}", error),
+            }
+
+            if *stop.borrow() {
+                return;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(heartbeat.interval()) => (),
+                _ = stop.changed() => (),
+            }
+        }
+    }
+
+    /// The open channel, opening a new connection if it has been lost.
+    async fn channel(&self) -> Result<Channel, Error> {
+        let mut link = self.link.lock().await;
+
+        if let Some((connection, channel)) = &link.connection {
+            if connection.status().connected() && channel.status().connected() {
+                return Ok(channel.clone());
+            }
+        }
+
+        link.connection = None;
+
+        if Instant::now() < link.reconnect_at {
+            return Err(Error::new(
+                ErrorKind::ApiFailure,
+                "amqp connection has been lost, waiting to reconnect",
+            ));
+        }
+
+        match open(&self.connect_config, &self.output_api).await {
+            Ok((connection, channel)) => {
+                log::info!(
+                    "reconnected to amqp after {} failed attempts",
+                    link.failed_attempts
+                );
+
+                link.connection = Some((connection, channel.clone()));
+                link.failed_attempts = 0;
+
+                Ok(channel)
+            }
+            Err(error) => {
+                link.failed_attempts = link.failed_attempts.saturating_add(1);
+                link.reconnect_at = Instant::now() + reconnect_delay(link.failed_attempts);
+
+                Err(error)
+            }
+        }
+    }
+}
+
+async fn open(
+    connect_config: &AmqpConnectConfig,
+    output_api: &AmqpOutputApi,
+) -> Result<(Connection, Channel), Error> {
+    let connection = match Connection::connect_with_config(
+        connect_config.uri(),
+        connect_config.cloned_options(),
+        connect_config.cloned_owned_tls_config(),
+    )
+    .await
+    {
+        Ok(connection) => connection,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::ApiFailure,
+                format!("failed to connect: {}", error),
+            ))
+        }
+    };
+
+    let channel = match connection.create_channel().await {
+        Ok(channel) => channel,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::ApiFailure,
+                format!("failed to get channel: {}", error),
+            ))
+        }
+    };
+
+    if let Err(error) = channel
+        .confirm_select(ConfirmSelectOptions::default())
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::ApiFailure,
+            format!("failed to enable publisher confirms: {}", error),
+        ));
+    }
+
+    let queue = output_api.queue();
+
+    if let Err(error) = channel
+        .queue_declare(
+            queue.name(),
+            *queue.declare().options(),
+            queue.declare().arguments().clone(),
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::ApiFailure,
+            format!("failed to declare queue '{}': {}", queue.name(), error),
+        ));
+    }
+
+    Ok((connection, channel))
+}
+
+/// Doubles after every failed attempt, up to `MAX_RECONNECT_DELAY`.
+fn reconnect_delay(failed_attempts: u32) -> Duration {
+    let exponent = failed_attempts.saturating_sub(1).min(16);
+
+    MIN_RECONNECT_DELAY
+        .saturating_mul(1u32 << exponent)
+        .min(MAX_RECONNECT_DELAY)
+}
+
+#[cfg(test)]
+#[test]
+fn back_off_exponentially_between_reconnections() {
+    assert_eq!(Duration::from_secs(1), reconnect_delay(1));
+    assert_eq!(Duration::from_secs(2), reconnect_delay(2));
+    assert_eq!(Duration::from_secs(32), reconnect_delay(6));
+    assert_eq!(MAX_RECONNECT_DELAY, reconnect_delay(7));
+    assert_eq!(MAX_RECONNECT_DELAY, reconnect_delay(u32::MAX));
+}
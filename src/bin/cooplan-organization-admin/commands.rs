@@ -0,0 +1,385 @@
+use crate::fixtures;
+use cooplan_organization::error::{Error, ErrorKind};
+use cooplan_organization::logic::actions::invitation_code_storage_action::InvitationStorageAction;
+use cooplan_organization::logic::actions::organization_storage_action::OrganizationStorageAction;
+use cooplan_organization::logic::actions::user_storage_action::UserStorageAction;
+use cooplan_organization::logic::audit;
+use cooplan_organization::logic::elements::audit_record::{changes, AuditAction, AuditRecord};
+use cooplan_organization::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
+use cooplan_organization::logic::elements::invitation::Invitation;
+use cooplan_organization::logic::elements::organization::Organization;
+use cooplan_organization::logic::organization_permission::OrganizationPermission;
+use cooplan_organization::logic::storage_request::{StorageRequest, StorageRequestSender};
+use rand::distributions::Alphanumeric;
+use rand::Rng;
+use serde_json::Value;
+use std::str::FromStr;
+use std::time::SystemTime;
+use tokio::sync::oneshot::Sender;
+
+const DEFAULT_LIMIT: u32 = 50;
+const INVITATION_CODE_LENGTH: usize = 16;
+
+/// Runs the command, answering with its result as JSON.
+pub async fn run(
+    command: &[String],
+    storage_request_sender: &StorageRequestSender,
+) -> Result<String, Error> {
+    let arguments: Vec<&str> = command.iter().map(String::as_str).collect();
+
+    let output = match arguments.as_slice() {
+        ["organizations", "list"] => search(None, DEFAULT_LIMIT, storage_request_sender).await?,
+        ["organizations", "list", limit] => {
+            search(None, parse(limit, "limit")?, storage_request_sender).await?
+        }
+        ["organizations", "search", name] => {
+            search(Some(name), DEFAULT_LIMIT, storage_request_sender).await?
+        }
+        ["organizations", "search", name, limit] => {
+            search(Some(name), parse(limit, "limit")?, storage_request_sender).await?
+        }
+        ["user", user_id] => user(user_id, storage_request_sender).await?,
+        ["invitation", "create", organization_id, permissions, expires_after] => {
+            create_invitation(
+                organization_id,
+                permissions,
+                parse(expires_after, "expires_after_in_seconds")?,
+                storage_request_sender,
+            )
+            .await?
+        }
+        ["invitation", "revoke", code] => revoke_invitation(code, storage_request_sender).await?,
+        ["member", "remove", organization_id, user_id] => {
+            remove_member(organization_id, user_id, storage_request_sender).await?
+        }
+        ["seed", fixtures_file] => fixtures::seed(fixtures_file, storage_request_sender).await?,
+        _ => {
+            return Err(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("unknown command '{}'", arguments.join(" ")),
+            ))
+        }
+    };
+
+    match serde_json::to_string_pretty(&output) {
+        Ok(output) => Ok(output),
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!("failed to serialize output: {}", error),
+        )),
+    }
+}
+
+/// Identifies the operator within the audit log.
+pub fn actor() -> String {
+    format!(
+        "admin:{}",
+        std::env::var("USER").unwrap_or_else(|_| "unknown".to_string())
+    )
+}
+
+/// Sends the request built around the replier, waiting for its result.
+pub async fn request<T>(
+    request: impl FnOnce(Sender<Result<T, Error>>) -> StorageRequest,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<T, Error> {
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender.send(request(replier)).await {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    match receiver.await {
+        Ok(result) => result,
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
+}
+
+pub fn to_value<T: serde::Serialize>(value: T) -> Result<Value, Error> {
+    match serde_json::to_value(value) {
+        Ok(value) => Ok(value),
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!("failed to serialize output: {}", error),
+        )),
+    }
+}
+
+/// Rejects permissions which are unknown to organizations.
+pub fn parse_permissions(permissions: &str) -> Result<Vec<String>, Error> {
+    permissions
+        .split(',')
+        .filter(|permission| !permission.is_empty())
+        .map(
+            |permission| match OrganizationPermission::from_str(permission) {
+                Ok(permission) => Ok(permission.to_string()),
+                Err(error) => Err(Error::new(ErrorKind::InvalidArgument, error)),
+            },
+        )
+        .collect()
+}
+
+pub async fn create_invitation(
+    organization_id: &str,
+    permissions: &str,
+    expires_after: u64,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let permissions = parse_permissions(permissions)?;
+
+    find_organization(organization_id, storage_request_sender).await?;
+
+    let invitation = Invitation {
+        code: rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(INVITATION_CODE_LENGTH)
+            .map(char::from)
+            .collect(),
+        organization_id: organization_id.to_string(),
+        permissions,
+        created_at: SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        expires_after,
+    };
+
+    let output = to_value(&invitation)?;
+    let audit_changes = changes(None, Some(&invitation));
+
+    let event = DomainEvent::new(DomainEventPayload::InvitationCreated {
+        organization_id: invitation.organization_id.clone(),
+        permissions: invitation.permissions.clone(),
+        expires_at: invitation.created_at + invitation.expires_after,
+    });
+
+    request(
+        |replier| {
+            StorageRequest::Invitation(InvitationStorageAction::Create {
+                invitation,
+                events: vec![event],
+                audit_records: vec![AuditRecord::new(
+                    actor(),
+                    organization_id.to_string(),
+                    AuditAction::Invite,
+                    audit_changes,
+                )],
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    Ok(output)
+}
+
+async fn search(
+    name: Option<&str>,
+    limit: u32,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let organizations = request(
+        |replier| {
+            StorageRequest::Organization(OrganizationStorageAction::Search {
+                name: name.map(str::to_string),
+                limit,
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    to_value(organizations)
+}
+
+async fn user(
+    user_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let user = request(
+        |replier| {
+            StorageRequest::User(UserStorageAction::FindUserById {
+                user_id: user_id.to_string(),
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    match user {
+        Some(user) => to_value(user),
+        None => Err(Error::new(ErrorKind::UserNotFound, "user not found")),
+    }
+}
+
+async fn revoke_invitation(
+    code: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let invitation = request(
+        |replier| {
+            StorageRequest::Invitation(InvitationStorageAction::FindByCode {
+                code: code.to_string(),
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    let invitation = match invitation {
+        Some(invitation) => invitation,
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvitationNotFound,
+                "invitation code not found",
+            ))
+        }
+    };
+
+    request(
+        |replier| {
+            StorageRequest::Invitation(InvitationStorageAction::Delete {
+                code: code.to_string(),
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    // The deletion is not transactional, so the revocation fails rather than going unrecorded.
+    audit::try_record(
+        AuditRecord::new(
+            actor(),
+            invitation.organization_id.clone(),
+            AuditAction::RevokeInvitation,
+            changes(Some(&invitation), None),
+        ),
+        storage_request_sender,
+    )
+    .await?;
+
+    to_value(invitation)
+}
+
+/// Removes the membership even if the user is the organization's last administrator.
+/// Running services forget the cached permissions of the user through their change stream of users.
+async fn remove_member(
+    organization_id: &str,
+    user_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let user = request(
+        |replier| {
+            StorageRequest::User(UserStorageAction::FindUserById {
+                user_id: user_id.to_string(),
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    let membership = match user.as_ref().and_then(|user| {
+        user.organizations
+            .iter()
+            .find(|membership| membership.organization_id == organization_id)
+    }) {
+        Some(membership) => membership.clone(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::UserNotFound,
+                "user not found within the organization",
+            ))
+        }
+    };
+
+    request(
+        |replier| {
+            StorageRequest::User(UserStorageAction::RemoveMembership {
+                user_id: user_id.to_string(),
+                organization_id: organization_id.to_string(),
+                events: vec![DomainEvent::new(DomainEventPayload::UserRemoved {
+                    organization_id: organization_id.to_string(),
+                    user_id: user_id.to_string(),
+                })],
+                audit_records: vec![AuditRecord::new(
+                    actor(),
+                    organization_id.to_string(),
+                    AuditAction::RemoveMember,
+                    changes(Some(&membership), None),
+                )
+                .with_target_user_id(user_id.to_string())],
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    to_value(membership)
+}
+
+async fn find_organization(
+    organization_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Organization, Error> {
+    let organization = request(
+        |replier| {
+            StorageRequest::Organization(OrganizationStorageAction::FindById {
+                id: organization_id.to_string(),
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    match organization {
+        Some(organization) => Ok(organization),
+        None => Err(Error::new(
+            ErrorKind::OrganizationNotFound,
+            "organization not found",
+        )),
+    }
+}
+
+fn parse<T: FromStr>(value: &str, name: &'static str) -> Result<T, Error> {
+    match value.parse() {
+        Ok(value) => Ok(value),
+        Err(_) => Err(Error::new(
+            ErrorKind::InvalidArgument,
+            format!("invalid {} '{}'", name, value),
+        )
+        .with_field(name)),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn reject_unknown_permissions() {
+    assert_eq!(
+        vec!["read:org".to_string(), "invite:user".to_string()],
+        parse_permissions("read:org,invite:user").unwrap()
+    );
+    assert_eq!(
+        ErrorKind::InvalidArgument,
+        parse_permissions("read:org,fly:org").unwrap_err().kind()
+    );
+}
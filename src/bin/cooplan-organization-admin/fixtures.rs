@@ -0,0 +1,218 @@
+use crate::commands::{actor, create_invitation, parse_permissions, request, to_value};
+use cooplan_organization::error::{Error, ErrorKind};
+use cooplan_organization::logic::actions::organization_storage_action::OrganizationStorageAction;
+use cooplan_organization::logic::actions::user_storage_action::UserStorageAction;
+use cooplan_organization::logic::elements::audit_record::{changes, AuditAction, AuditRecord};
+use cooplan_organization::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
+use cooplan_organization::logic::elements::user_organization::UserOrganization;
+use cooplan_organization::logic::storage_request::{StorageRequest, StorageRequestSender};
+use cooplan_organization::logic::validation::country::is_country_code_valid;
+use cooplan_organization::logic::validation::telephone::is_telephone_valid;
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Deserialize)]
+struct Fixtures {
+    organizations: Vec<OrganizationFixture>,
+}
+
+#[derive(Deserialize)]
+struct OrganizationFixture {
+    name: String,
+    country: String,
+    address: String,
+    telephone: String,
+    /// The first member is recorded as the organization's creator.
+    members: Vec<MemberFixture>,
+    #[serde(default)]
+    invitations: Vec<InvitationFixture>,
+}
+
+#[derive(Deserialize)]
+struct MemberFixture {
+    user_id: String,
+    permissions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct InvitationFixture {
+    permissions: Vec<String>,
+    expires_after: u64,
+}
+
+/// Stores every organization of the fixtures file, along with its members and invitations,
+/// answering with whatever has been created.
+pub async fn seed(
+    fixtures_file: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let fixtures = read(fixtures_file).await?;
+
+    let mut seeded = Vec::new();
+
+    for organization in fixtures.organizations {
+        seeded.push(seed_organization(organization, storage_request_sender).await?);
+    }
+
+    Ok(Value::Array(seeded))
+}
+
+async fn read(fixtures_file: &str) -> Result<Fixtures, Error> {
+    let fixtures = match tokio::fs::read_to_string(fixtures_file).await {
+        Ok(fixtures) => match serde_json::from_str::<Fixtures>(fixtures.as_str()) {
+            Ok(fixtures) => fixtures,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::InvalidArgument,
+                    format!("failed to deserialize fixtures file's content: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("failed to read fixtures file: {}", error),
+            ));
+        }
+    };
+
+    for organization in &fixtures.organizations {
+        if !is_country_code_valid(&organization.country) {
+            return Err(Error::new(
+                ErrorKind::InvalidCountry,
+                format!(
+                    "organization '{}' has an invalid country code",
+                    organization.name
+                ),
+            )
+            .with_field("country"));
+        }
+
+        if !is_telephone_valid(&organization.telephone) {
+            return Err(Error::new(
+                ErrorKind::InvalidTelephone,
+                format!(
+                    "organization '{}' has an invalid telephone",
+                    organization.name
+                ),
+            )
+            .with_field("telephone"));
+        }
+
+        if organization.members.is_empty() {
+            return Err(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("organization '{}' has no members", organization.name),
+            )
+            .with_field("members"));
+        }
+
+        for member in &organization.members {
+            parse_permissions(&member.permissions.join(","))?;
+        }
+    }
+
+    Ok(fixtures)
+}
+
+async fn seed_organization(
+    fixture: OrganizationFixture,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Value, Error> {
+    let organization = request(
+        |replier| {
+            StorageRequest::Organization(OrganizationStorageAction::Create {
+                name: fixture.name,
+                country: fixture.country,
+                address: fixture.address,
+                telephone: fixture.telephone,
+                replier,
+            })
+        },
+        storage_request_sender,
+    )
+    .await?;
+
+    let mut members = Vec::new();
+
+    for (index, member) in fixture.members.into_iter().enumerate() {
+        let mut events = Vec::new();
+        let mut audit_records = Vec::new();
+
+        if index == 0 {
+            events.push(DomainEvent::new(DomainEventPayload::OrganizationCreated {
+                organization_id: organization.id.clone(),
+                name: organization.name.clone(),
+                country: organization.country.clone(),
+                address: organization.address.clone(),
+                telephone: organization.telephone.clone(),
+                actor: member.user_id.clone(),
+            }));
+
+            audit_records.push(AuditRecord::new(
+                actor(),
+                organization.id.clone(),
+                AuditAction::Create,
+                changes(None, Some(&organization)),
+            ));
+        }
+
+        events.push(DomainEvent::new(DomainEventPayload::UserJoined {
+            organization_id: organization.id.clone(),
+            user_id: member.user_id.clone(),
+            permissions: member.permissions.clone(),
+        }));
+
+        let user = request(
+            |replier| {
+                StorageRequest::User(UserStorageAction::Create {
+                    id: member.user_id,
+                    organization: UserOrganization {
+                        organization_id: organization.id.clone(),
+                        permissions: member.permissions,
+                    },
+                    events,
+                    audit_records,
+                    replier,
+                })
+            },
+            storage_request_sender,
+        )
+        .await?;
+
+        members.push(to_value(user)?);
+    }
+
+    let mut invitations = Vec::new();
+
+    for invitation in fixture.invitations {
+        invitations.push(
+            create_invitation(
+                &organization.id,
+                &invitation.permissions.join(","),
+                invitation.expires_after,
+                storage_request_sender,
+            )
+            .await?,
+        );
+    }
+
+    Ok(serde_json::json!({
+        "organization": to_value(organization)?,
+        "members": members,
+        "invitations": invitations,
+    }))
+}
+
+#[cfg(test)]
+#[test]
+fn deserialize_fixtures() {
+    let fixtures = serde_json::from_str::<Fixtures>(
+        r#"{ "organizations": [{ "name": "TEST", "country": "ES", "address": "TEST",
+        "telephone": "+34600000000", "members": [{ "user_id": "USER", "permissions": ["read:org"] }] }] }"#,
+    )
+    .unwrap();
+
+    assert_eq!(1, fixtures.organizations[0].members.len());
+    assert!(fixtures.organizations[0].invitations.is_empty());
+}
@@ -0,0 +1,85 @@
+use cooplan_organization::health::heartbeat::HeartbeatRegistry;
+use cooplan_organization::logic::storage_request::StorageRequest;
+use cooplan_organization::{config, storage, telemetry};
+use std::io::{Error, ErrorKind};
+use std::time::Duration;
+
+mod commands;
+mod fixtures;
+
+const USAGE: &str = "usage: cooplan-organization-admin <config_file> <command> [--path=value...]
+
+commands:
+  organizations list [limit]
+  organizations search <name> [limit]
+  user <user_id>
+  invitation create <organization_id> <permission,...> <expires_after_in_seconds>
+  invitation revoke <code>
+  member remove <organization_id> <user_id>
+  seed <fixtures_file>";
+
+/// Only a handful of requests are in flight at once.
+const STORAGE_REQUESTS_BOUNDARY: usize = 16;
+
+#[tokio::main]
+async fn main() -> Result<(), Error> {
+    let (arguments, flags) = config::source::split_arguments(std::env::args().skip(1));
+
+    let (config_file, command) = match arguments.split_first() {
+        Some((config_file, command)) if !command.is_empty() => (config_file, command),
+        _ => return Err(Error::new(ErrorKind::InvalidInput, USAGE)),
+    };
+
+    let config = match config::config::try_read_config(config_file, &flags).await {
+        Ok(config) => config,
+        Err(error) => {
+            return Err(Error::other(format!("failed to read config: {}", error)));
+        }
+    };
+
+    let (storage_request_sender, storage_request_receiver) =
+        telemetry::traced_channel::bounded::<StorageRequest>(STORAGE_REQUESTS_BOUNDARY);
+
+    let heartbeat_registry =
+        HeartbeatRegistry::new(Duration::from_secs(config.health_check_interval_in_seconds));
+
+    let storage_dispatcher = match storage::init::initialize_standalone(
+        storage_request_receiver,
+        config.mongodb_uri,
+        Duration::from_secs(config.organization_retention_in_seconds),
+        heartbeat_registry.register("storage_dispatch_0".to_string()),
+    )
+    .await
+    {
+        Ok(storage_dispatcher) => storage_dispatcher,
+        Err(error) => {
+            return Err(Error::other(format!(
+                "failed to initialize storage: {}",
+                error
+            )));
+        }
+    };
+
+    let result = commands::run(command, &storage_request_sender).await;
+
+    storage_request_sender.close();
+
+    if let Err(error) = storage_dispatcher.await {
+        eprintln!("storage dispatcher failed: {}", error);
+    }
+
+    match result {
+        Ok(output) => {
+            println!("{}", output);
+
+            Ok(())
+        }
+        Err(error) if error.kind() == cooplan_organization::error::ErrorKind::InvalidArgument => {
+            Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("{}\n\n{}", error, USAGE),
+            ))
+        }
+        Err(error) => Err(Error::other(error.to_string())),
+    }
+}
@@ -0,0 +1,45 @@
+use serde::Deserialize;
+
+/// Claims of the tokens holding the caller's identity, which differ between identity providers.
+#[derive(Deserialize, Clone)]
+pub struct ClaimMappingConfig {
+    /// Stable id of the user. Defaults to `sub`.
+    #[serde(default = "default_user_id")]
+    pub user_id: String,
+    /// Defaults to `email`.
+    #[serde(default = "default_email")]
+    pub email: String,
+    /// Whether the identity provider has verified the email. Defaults to `email_verified`.
+    #[serde(default = "default_email_verified")]
+    pub email_verified: String,
+    /// Scopes granted to services, either space separated or as an array. Defaults to `scope`.
+    #[serde(default = "default_scopes")]
+    pub scopes: String,
+}
+
+impl Default for ClaimMappingConfig {
+    fn default() -> Self {
+        ClaimMappingConfig {
+            user_id: default_user_id(),
+            email: default_email(),
+            email_verified: default_email_verified(),
+            scopes: default_scopes(),
+        }
+    }
+}
+
+fn default_user_id() -> String {
+    "sub".to_string()
+}
+
+fn default_email() -> String {
+    "email".to_string()
+}
+
+fn default_email_verified() -> String {
+    "email_verified".to_string()
+}
+
+fn default_scopes() -> String {
+    "scope".to_string()
+}
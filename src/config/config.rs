@@ -2,41 +2,355 @@ use cooplan_amqp_api::config::openid_connect_config::OpenIdConnectConfig;
 use cooplan_lapin_wrapper::config::amqp_connect_config::AmqpConnectConfig;
 use cooplan_state_tracker::state_tracking_config::StateTrackingConfig;
 use serde::Deserialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::net::SocketAddr;
 
+use crate::config::claim_mapping_config::ClaimMappingConfig;
+use crate::config::rate_limit_config::{InvitationLockoutConfig, RateLimitConfig};
+use crate::config::source;
 use crate::error::{Error, ErrorKind};
+use crate::logic::caller::SERVICE_ACTIONS;
 
+/// Settings of the service, read from the config file and overridden by the environment variables
+/// and flags described within `config::source`. Fields without a default are required.
 #[derive(Deserialize)]
 pub struct Config {
+    /// Issuers, audiences and keys of the tokens authorizing the requests.
     pub openid_connect: OpenIdConnectConfig,
+    /// Logic dispatchers executing requests concurrently, at least one.
     pub logic_request_dispatch_instances: u16,
+    /// Logic requests waiting to be dispatched before the api stops accepting more, at least one.
     pub logic_requests_boundary: usize,
+    /// Storage dispatchers executing requests concurrently, at least one.
     pub storage_request_dispatch_instances: u16,
+    /// Storage requests waiting to be dispatched before logic stops sending more, at least one.
     pub storage_requests_boundary: usize,
-    pub output_channel_boundary: usize,
+    /// Broker the requests are consumed from and the events published to.
     pub amqp_connect_config: AmqpConnectConfig,
     pub state_tracking_config: StateTrackingConfig,
+    /// States waiting to be sent to the state tracker, at least one.
     pub state_tracking_channel_boundary: usize,
-    pub mongodb_uri: String
+    /// Starting with `mongodb://` or `mongodb+srv://`.
+    pub mongodb_uri: String,
+    /// Report pending migrations and exit, without changing any stored data. Defaults to `false`.
+    #[serde(default)]
+    pub migration_dry_run: bool,
+    /// Seconds during which a deleted organization can still be restored. Defaults to 30 days.
+    #[serde(default = "default_organization_retention_in_seconds")]
+    pub organization_retention_in_seconds: u64,
+    /// Seconds between searches of deleted organizations whose retention period has expired.
+    /// Defaults to 3600.
+    #[serde(default = "default_organization_purge_interval_in_seconds")]
+    pub organization_purge_interval_in_seconds: u64,
+    /// Milliseconds between searches of outbox events waiting to be published. Defaults to 1000.
+    #[serde(default = "default_outbox_relay_interval_in_milliseconds")]
+    pub outbox_relay_interval_in_milliseconds: u64,
+    /// OpenTelemetry collector, such as `http://localhost:4317`, to which spans are exported
+    /// over OTLP/gRPC. Spans are only logged if missing.
+    #[serde(default)]
+    pub otlp_endpoint: Option<String>,
+    /// Address, such as `0.0.0.0:9464`, on which metrics are served in the Prometheus text format.
+    /// Metrics are not recorded if missing.
+    #[serde(default)]
+    pub metrics_address: Option<String>,
+    /// Seconds between health checks. Components which miss three of them are reported as unhealthy.
+    /// Defaults to 5.
+    #[serde(default = "default_health_check_interval_in_seconds")]
+    pub health_check_interval_in_seconds: u64,
+    /// Address, such as `0.0.0.0:9465`, answering `200` while every component is healthy
+    /// and `503` otherwise. Health is only reported through the state tracker if missing.
+    #[serde(default)]
+    pub readiness_address: Option<String>,
+    /// Seconds given to the dispatchers to execute their outstanding requests once
+    /// SIGTERM or SIGINT is received, before aborting them. Defaults to 30.
+    #[serde(default = "default_shutdown_timeout_in_seconds")]
+    pub shutdown_timeout_in_seconds: u64,
+    /// Milliseconds within which requests must be answered, unless overridden for their action.
+    /// Defaults to 10000.
+    #[serde(default = "default_request_timeout_in_milliseconds")]
+    pub default_request_timeout_in_milliseconds: u64,
+    /// Milliseconds within which requests must be answered, by `element.action`, such as
+    /// `organization.create`. Defaults to none.
+    #[serde(default)]
+    pub request_timeouts_in_milliseconds: HashMap<String, u64>,
+    /// Requests each user may send by `element.action`, such as `organization.create`.
+    /// Actions missing are not limited. Defaults to 5 creations per hour and 10 joins per minute.
+    #[serde(default = "default_rate_limits")]
+    pub rate_limits: HashMap<String, RateLimitConfig>,
+    /// Defaults to 5 unknown invitation codes within 600 seconds, locking out for 900 seconds.
+    #[serde(default)]
+    pub invitation_lockout: InvitationLockoutConfig,
+    /// Claims holding the user id, email, whether it is verified and the scopes of services.
+    /// Defaults to `sub`, `email`, `email_verified` and `scope`.
+    #[serde(default)]
+    pub claim_mapping: ClaimMappingConfig,
+    /// Actions granted to services regardless of memberships by each scope of their tokens,
+    /// such as `organization:read:any`, among `organization.read`, `organization.audit`, `organization.list`,
+    /// `user.read` and `user.check_permission`. Defaults to `organization:read:any` for the organization reads
+    /// and `membership:check` for the user ones.
+    #[serde(default = "default_service_scopes")]
+    pub service_scopes: HashMap<String, Vec<String>>,
+    /// Milliseconds during which the answers of `user.check_permission` are cached, zero disabling
+    /// the cache. Defaults to 5000.
+    #[serde(default = "default_permission_cache_ttl_in_milliseconds")]
+    pub permission_cache_ttl_in_milliseconds: u64,
 }
 
-pub async fn try_read_config(config_file: &str) -> Result<Config, Error> {
-    let config = match tokio::fs::read_to_string(config_file).await {
-        Ok(config) => match serde_json::from_str::<Config>(config.as_str()) {
-            Ok(config) => config,
-            Err(error) => {
-                return Err(Error::new(
-                    ErrorKind::AutoConfigFailure,
-                    format!("failed to deserialize config file's content: {}", error),
+impl Config {
+    /// Reports every invalid value at once, rather than failing on the first one.
+    pub fn validate(&self) -> Result<(), Error> {
+        let mut problems = Vec::new();
+
+        let at_least_one = [
+            (
+                "logic_request_dispatch_instances",
+                self.logic_request_dispatch_instances as u64,
+            ),
+            (
+                "logic_requests_boundary",
+                self.logic_requests_boundary as u64,
+            ),
+            (
+                "storage_request_dispatch_instances",
+                self.storage_request_dispatch_instances as u64,
+            ),
+            (
+                "storage_requests_boundary",
+                self.storage_requests_boundary as u64,
+            ),
+            (
+                "state_tracking_channel_boundary",
+                self.state_tracking_channel_boundary as u64,
+            ),
+            (
+                "organization_purge_interval_in_seconds",
+                self.organization_purge_interval_in_seconds,
+            ),
+            (
+                "outbox_relay_interval_in_milliseconds",
+                self.outbox_relay_interval_in_milliseconds,
+            ),
+            (
+                "health_check_interval_in_seconds",
+                self.health_check_interval_in_seconds,
+            ),
+            (
+                "default_request_timeout_in_milliseconds",
+                self.default_request_timeout_in_milliseconds,
+            ),
+            (
+                "invitation_lockout.failures",
+                self.invitation_lockout.failures as u64,
+            ),
+            (
+                "invitation_lockout.window_in_seconds",
+                self.invitation_lockout.window_in_seconds,
+            ),
+            (
+                "invitation_lockout.lockout_in_seconds",
+                self.invitation_lockout.lockout_in_seconds,
+            ),
+        ];
+
+        for (field, value) in at_least_one {
+            if value == 0 {
+                problems.push(format!("'{}' must be at least 1", field));
+            }
+        }
+
+        for (action, timeout) in &self.request_timeouts_in_milliseconds {
+            if *timeout == 0 {
+                problems.push(format!(
+                    "'request_timeouts_in_milliseconds.{}' must be at least 1",
+                    action
+                ));
+            }
+        }
+
+        for (action, limit) in &self.rate_limits {
+            if limit.requests == 0 || limit.window_in_seconds == 0 {
+                problems.push(format!(
+                    "'rate_limits.{}' must allow at least 1 request within at least 1 second",
+                    action
                 ));
             }
-        },
+        }
+
+        for (field, claim) in [
+            ("claim_mapping.user_id", &self.claim_mapping.user_id),
+            ("claim_mapping.email", &self.claim_mapping.email),
+            (
+                "claim_mapping.email_verified",
+                &self.claim_mapping.email_verified,
+            ),
+            ("claim_mapping.scopes", &self.claim_mapping.scopes),
+        ] {
+            if claim.is_empty() {
+                problems.push(format!("'{}' must name a claim", field));
+            }
+        }
+
+        for (scope, actions) in &self.service_scopes {
+            for action in actions {
+                if !SERVICE_ACTIONS.contains(&action.as_str()) {
+                    problems.push(format!(
+                        "'service_scopes.{}' cannot grant '{}', only {}",
+                        scope,
+                        action,
+                        SERVICE_ACTIONS.join(", ")
+                    ));
+                }
+            }
+        }
+
+        if !self.mongodb_uri.starts_with("mongodb://")
+            && !self.mongodb_uri.starts_with("mongodb+srv://")
+        {
+            problems
+                .push("'mongodb_uri' must start with 'mongodb://' or 'mongodb+srv://'".to_string());
+        }
+
+        for (field, address) in [
+            ("metrics_address", &self.metrics_address),
+            ("readiness_address", &self.readiness_address),
+        ] {
+            if let Some(address) = address {
+                if address.parse::<SocketAddr>().is_err() {
+                    problems.push(format!(
+                        "'{}' must be a socket address, such as '0.0.0.0:9464', not '{}'",
+                        field, address
+                    ));
+                }
+            }
+        }
+
+        if problems.is_empty() {
+            return Ok(());
+        }
+
+        Err(Error::new(
+            ErrorKind::AutoConfigFailure,
+            format!("invalid config: {}", problems.join(", ")),
+        ))
+    }
+}
+
+fn default_organization_retention_in_seconds() -> u64 {
+    // 30 days.
+    2_592_000
+}
+
+fn default_organization_purge_interval_in_seconds() -> u64 {
+    3600
+}
+
+fn default_outbox_relay_interval_in_milliseconds() -> u64 {
+    1000
+}
+
+fn default_health_check_interval_in_seconds() -> u64 {
+    5
+}
+
+fn default_shutdown_timeout_in_seconds() -> u64 {
+    30
+}
+
+fn default_request_timeout_in_milliseconds() -> u64 {
+    10_000
+}
+
+fn default_permission_cache_ttl_in_milliseconds() -> u64 {
+    5_000
+}
+
+fn default_rate_limits() -> HashMap<String, RateLimitConfig> {
+    HashMap::from([
+        (
+            "organization.create".to_string(),
+            RateLimitConfig {
+                requests: 5,
+                window_in_seconds: 3600,
+            },
+        ),
+        (
+            "organization.join".to_string(),
+            RateLimitConfig {
+                requests: 10,
+                window_in_seconds: 60,
+            },
+        ),
+    ])
+}
+
+fn default_service_scopes() -> HashMap<String, Vec<String>> {
+    HashMap::from([
+        (
+            "organization:read:any".to_string(),
+            vec![
+                "organization.read".to_string(),
+                "organization.audit".to_string(),
+                "organization.list".to_string(),
+            ],
+        ),
+        (
+            "membership:check".to_string(),
+            vec!["user.read".to_string(), "user.check_permission".to_string()],
+        ),
+    ])
+}
+
+/// Reads the config file, along with its overrides, validating the result.
+pub async fn try_read_config(config_file: &str, flags: &[String]) -> Result<Config, Error> {
+    try_from_value(source::read(config_file, flags).await?)
+}
+
+pub fn try_from_value(config: Value) -> Result<Config, Error> {
+    let config = match serde_json::from_value::<Config>(config) {
+        Ok(config) => config,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::AutoConfigFailure,
-                format!("failed to read config file: {}", error),
+                format!("failed to deserialize config: {}", error),
             ));
         }
     };
 
+    config.validate()?;
+
     Ok(config)
 }
+
+#[cfg(test)]
+fn example() -> Value {
+    serde_json::from_str(include_str!("../../config.json")).unwrap()
+}
+
+#[tokio::test]
+async fn accept_example_config() {
+    assert!(try_from_value(example()).is_ok());
+}
+
+#[tokio::test]
+async fn report_every_invalid_value() {
+    let mut config = example();
+    config["logic_request_dispatch_instances"] = Value::from(0);
+    config["mongodb_uri"] = Value::from("localhost:27017");
+    config["service_scopes"] =
+        serde_json::json!({ "organization:write:any": ["organization.delete"] });
+
+    let error = match try_from_value(config) {
+        Ok(_) => panic!("expected invalid config"),
+        Err(error) => error,
+    };
+
+    assert_eq!(ErrorKind::AutoConfigFailure, error.kind());
+    assert!(error
+        .message
+        .contains("'logic_request_dispatch_instances' must be at least 1"));
+    assert!(error.message.contains("'mongodb_uri' must start with"));
+    assert!(error
+        .message
+        .contains("'service_scopes.organization:write:any' cannot grant 'organization.delete'"));
+}
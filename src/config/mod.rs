@@ -1 +1,4 @@
+pub mod claim_mapping_config;
 pub mod config;
+pub mod rate_limit_config;
+pub mod source;
@@ -0,0 +1,27 @@
+use serde::Deserialize;
+
+/// Requests a user may send within each window.
+#[derive(Deserialize, Clone, Copy)]
+pub struct RateLimitConfig {
+    pub requests: u32,
+    pub window_in_seconds: u64,
+}
+
+/// Locks a user out of joining organizations after too many unknown invitation codes.
+#[derive(Deserialize, Clone, Copy)]
+pub struct InvitationLockoutConfig {
+    /// Unknown invitation codes a user may try within each window.
+    pub failures: u32,
+    pub window_in_seconds: u64,
+    pub lockout_in_seconds: u64,
+}
+
+impl Default for InvitationLockoutConfig {
+    fn default() -> Self {
+        InvitationLockoutConfig {
+            failures: 5,
+            window_in_seconds: 600,
+            lockout_in_seconds: 900,
+        }
+    }
+}
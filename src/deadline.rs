@@ -0,0 +1,109 @@
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, PoisonError, RwLock};
+use std::time::Duration;
+use tokio::time::Instant;
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+struct Timeouts {
+    default: Duration,
+    by_action: HashMap<String, Duration>,
+}
+
+/// Time given to each action to be answered, by `element.action`.
+/// Clones share the timeouts, so updating one updates them all.
+#[derive(Clone)]
+pub struct RequestTimeouts {
+    timeouts: Arc<RwLock<Timeouts>>,
+}
+
+impl RequestTimeouts {
+    pub fn new(default: Duration, by_action: HashMap<String, Duration>) -> RequestTimeouts {
+        RequestTimeouts {
+            timeouts: Arc::new(RwLock::new(Timeouts { default, by_action })),
+        }
+    }
+
+    pub fn update(&self, default: Duration, by_action: HashMap<String, Duration>) {
+        *self
+            .timeouts
+            .write()
+            .unwrap_or_else(PoisonError::into_inner) = Timeouts { default, by_action };
+    }
+
+    pub fn deadline(&self, element: &str, action: &str) -> Instant {
+        let timeouts = self.timeouts.read().unwrap_or_else(PoisonError::into_inner);
+
+        let timeout = timeouts
+            .by_action
+            .get(format!("{}.{}", element, action).as_str())
+            .copied()
+            .unwrap_or(timeouts.default);
+
+        Instant::now() + timeout
+    }
+}
+
+/// Runs the future with the deadline, which is attached to every request it sends.
+pub async fn scope<F: Future>(deadline: Instant, future: F) -> F::Output {
+    DEADLINE.scope(deadline, future).await
+}
+
+/// Deadline of the request being handled, if any.
+pub fn current() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+pub fn has_expired(deadline: Option<Instant>) -> bool {
+    match deadline {
+        Some(deadline) => deadline <= Instant::now(),
+        None => false,
+    }
+}
+
+/// Resolves with the future's output, or with `None` if the deadline is reached first,
+/// dropping the future along with its replier.
+pub async fn within<F: Future>(deadline: Option<Instant>, future: F) -> Option<F::Output> {
+    match deadline {
+        Some(deadline) => tokio::time::timeout_at(deadline, scope(deadline, future))
+            .await
+            .ok(),
+        None => Some(future.await),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn timeouts_are_taken_by_action() {
+    let timeouts = RequestTimeouts::new(
+        Duration::from_secs(10),
+        HashMap::from([("organization.create".to_string(), Duration::from_secs(1))]),
+    );
+
+    let now = Instant::now();
+
+    assert!(timeouts.deadline("organization", "create") <= now + Duration::from_secs(2));
+    assert!(timeouts.deadline("organization", "update") >= now + Duration::from_secs(9));
+
+    timeouts
+        .clone()
+        .update(Duration::from_secs(1), HashMap::new());
+    assert!(timeouts.deadline("organization", "update") <= now + Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn drop_work_exceeding_its_deadline() {
+    let deadline = Instant::now() + Duration::from_millis(10);
+
+    let result = within(Some(deadline), async {
+        assert_eq!(current(), Some(deadline));
+        tokio::time::sleep(Duration::from_secs(10)).await;
+    })
+    .await;
+
+    assert!(result.is_none());
+    assert!(has_expired(Some(deadline)));
+}
@@ -0,0 +1,139 @@
+use crate::health::heartbeat::{Heartbeat, HeartbeatRegistry};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio::task::JoinHandle;
+
+/// Tells a dispatcher to stop once it is done with its current request.
+#[derive(Clone, Default)]
+pub struct Retirement(Arc<AtomicBool>);
+
+impl Retirement {
+    pub fn retire(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_retired(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+struct Dispatcher {
+    component: String,
+    retirement: Retirement,
+    handle: JoinHandle<()>,
+}
+
+type Spawn = Box<dyn Fn(Heartbeat, Retirement) -> JoinHandle<()> + Send + Sync>;
+
+/// Dispatchers of a layer, which can be scaled up or down while running.
+pub struct DispatcherPool {
+    layer: &'static str,
+    heartbeat_registry: HeartbeatRegistry,
+    spawn: Spawn,
+    active: Vec<Dispatcher>,
+    /// Retired dispatchers which may still be executing their last request.
+    retiring: Vec<JoinHandle<()>>,
+    closed: bool,
+}
+
+impl DispatcherPool {
+    pub fn new(
+        layer: &'static str,
+        heartbeat_registry: HeartbeatRegistry,
+        spawn: impl Fn(Heartbeat, Retirement) -> JoinHandle<()> + Send + Sync + 'static,
+    ) -> DispatcherPool {
+        DispatcherPool {
+            layer,
+            heartbeat_registry,
+            spawn: Box::new(spawn),
+            active: Vec::new(),
+            retiring: Vec::new(),
+            closed: false,
+        }
+    }
+
+    pub fn instances(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Spawns or retires dispatchers until there are as many as `instances`.
+    /// The most recently spawned dispatchers are the first ones to be retired.
+    pub fn scale(&mut self, instances: usize) {
+        if self.closed {
+            return;
+        }
+
+        self.retiring.retain(|handle| !handle.is_finished());
+
+        while self.active.len() < instances {
+            let component = format!("{}_dispatch_{}", self.layer, self.active.len());
+            let retirement = Retirement::default();
+
+            let handle = (self.spawn)(
+                self.heartbeat_registry.register(component.clone()),
+                retirement.clone(),
+            );
+
+            self.active.push(Dispatcher {
+                component,
+                retirement,
+                handle,
+            });
+        }
+
+        while self.active.len() > instances {
+            if let Some(dispatcher) = self.active.pop() {
+                dispatcher.retirement.retire();
+                self.heartbeat_registry.unregister(&dispatcher.component);
+                self.retiring.push(dispatcher.handle);
+            }
+        }
+    }
+
+    /// Hands over every dispatcher, including the retiring ones, so they can be drained.
+    /// The pool can no longer be scaled afterwards.
+    pub fn close(&mut self) -> Vec<JoinHandle<()>> {
+        self.closed = true;
+
+        self.active
+            .drain(..)
+            .map(|dispatcher| dispatcher.handle)
+            .chain(self.retiring.drain(..))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn retire_the_most_recent_dispatchers_when_scaling_down() {
+    use std::time::Duration;
+
+    let registry = HeartbeatRegistry::new(Duration::from_millis(10));
+
+    let mut pool = DispatcherPool::new("TEST", registry.clone(), |_, retirement| {
+        tokio::spawn(async move {
+            while !retirement.is_retired() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+    });
+
+    pool.scale(3);
+    assert_eq!(3, pool.instances());
+
+    pool.scale(1);
+    assert_eq!(1, pool.instances());
+
+    let components: Vec<String> = registry
+        .heartbeats()
+        .iter()
+        .map(|heartbeat| heartbeat.component().to_string())
+        .collect();
+    assert_eq!(vec!["TEST_dispatch_0".to_string()], components);
+
+    let handles = pool.close();
+    assert_eq!(3, handles.len());
+
+    pool.scale(2);
+    assert_eq!(0, pool.instances());
+}
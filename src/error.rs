@@ -17,9 +17,73 @@ pub enum ErrorKind {
     InvitationNotFound,
     InvitationHasExpired,
     OrganizationNotFound,
+    UserNotFound,
+    MissingPermission,
+    OrganizationHasOtherUsers,
+    LastAdministrator,
+    VersionConflict,
+    Timeout,
+    RateLimited,
     ProcessReversion,
 }
 
+impl ErrorKind {
+    /// Stable identifier of the kind, which clients may rely upon.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::ApiFailure => "api_failure",
+            ErrorKind::ApiRequestFailure => "api_request_failure",
+            ErrorKind::AutoConfigFailure => "auto_config_failure",
+            ErrorKind::InternalFailure => "internal_failure",
+            ErrorKind::StorageFailure => "storage_failure",
+            ErrorKind::InvalidArgument => "invalid_argument",
+            ErrorKind::InvalidCountry => "invalid_country",
+            ErrorKind::InvalidTelephone => "invalid_telephone",
+            ErrorKind::NameAlreadyTaken => "name_already_taken",
+            ErrorKind::TelephoneAlreadyInUse => "telephone_already_in_use",
+            ErrorKind::UserCannotCreateOrganization => "user_cannot_create_organization",
+            ErrorKind::UserCannotJoinAnyOrganization => "user_cannot_join_any_organization",
+            ErrorKind::InvitationNotFound => "invitation_not_found",
+            ErrorKind::InvitationHasExpired => "invitation_has_expired",
+            ErrorKind::OrganizationNotFound => "organization_not_found",
+            ErrorKind::UserNotFound => "user_not_found",
+            ErrorKind::MissingPermission => "missing_permission",
+            ErrorKind::OrganizationHasOtherUsers => "organization_has_other_users",
+            ErrorKind::LastAdministrator => "last_administrator",
+            ErrorKind::VersionConflict => "version_conflict",
+            ErrorKind::Timeout => "timeout",
+            ErrorKind::RateLimited => "rate_limited",
+            ErrorKind::ProcessReversion => "process_reversion",
+        }
+    }
+
+    /// Whether the failure lies within the service rather than within the request.
+    pub fn is_internal(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::ApiFailure
+                | ErrorKind::AutoConfigFailure
+                | ErrorKind::InternalFailure
+                | ErrorKind::StorageFailure
+                | ErrorKind::Timeout
+                | ErrorKind::ProcessReversion
+        )
+    }
+
+    /// Whether the same request may succeed if sent again later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::ApiFailure
+                | ErrorKind::InternalFailure
+                | ErrorKind::StorageFailure
+                | ErrorKind::Timeout
+                | ErrorKind::RateLimited
+                | ErrorKind::ProcessReversion
+        )
+    }
+}
+
 impl From<cooplan_amqp_api::error::ErrorKind> for ErrorKind {
     fn from(error_kind: cooplan_amqp_api::error::ErrorKind) -> Self {
         match error_kind {
@@ -50,6 +114,8 @@ impl From<cooplan_amqp_api::error::ErrorKind> for ErrorKind {
 pub struct Error {
     pub kind: ErrorKind,
     pub message: String,
+    /// Request parameter which failed validation, if any.
+    pub field: Option<&'static str>,
 }
 
 impl Error {
@@ -57,9 +123,15 @@ impl Error {
         Error {
             kind,
             message: message.into(),
+            field: None,
         }
     }
 
+    pub fn with_field(mut self, field: &'static str) -> Error {
+        self.field = Some(field);
+        self
+    }
+
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
@@ -0,0 +1,103 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Intervals a component may miss before being considered dead.
+const MISSED_BEATS_TOLERANCE: u32 = 3;
+
+/// Proof of life of a component, which must beat at least once every interval.
+#[derive(Clone)]
+pub struct Heartbeat {
+    component: String,
+    interval: Duration,
+    last_beat: Arc<Mutex<Instant>>,
+}
+
+impl Heartbeat {
+    pub fn component(&self) -> &str {
+        self.component.as_str()
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn beat(&self) {
+        if let Ok(mut last_beat) = self.last_beat.lock() {
+            *last_beat = Instant::now();
+        }
+    }
+
+    pub fn since_last_beat(&self) -> Duration {
+        match self.last_beat.lock() {
+            Ok(last_beat) => last_beat.elapsed(),
+            Err(_) => Duration::MAX,
+        }
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.since_last_beat() <= self.interval * MISSED_BEATS_TOLERANCE
+    }
+}
+
+/// Hands out the heartbeats watched by the health monitor.
+#[derive(Clone)]
+pub struct HeartbeatRegistry {
+    interval: Duration,
+    heartbeats: Arc<Mutex<Vec<Heartbeat>>>,
+}
+
+impl HeartbeatRegistry {
+    pub fn new(interval: Duration) -> HeartbeatRegistry {
+        HeartbeatRegistry {
+            interval,
+            heartbeats: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn register(&self, component: String) -> Heartbeat {
+        let heartbeat = Heartbeat {
+            component,
+            interval: self.interval,
+            last_beat: Arc::new(Mutex::new(Instant::now())),
+        };
+
+        if let Ok(mut heartbeats) = self.heartbeats.lock() {
+            heartbeats.push(heartbeat.clone());
+        }
+
+        heartbeat
+    }
+
+    /// Stops watching the component, such as a dispatcher which has been scaled down.
+    pub fn unregister(&self, component: &str) {
+        if let Ok(mut heartbeats) = self.heartbeats.lock() {
+            heartbeats.retain(|heartbeat| heartbeat.component() != component);
+        }
+    }
+
+    pub fn heartbeats(&self) -> Vec<Heartbeat> {
+        match self.heartbeats.lock() {
+            Ok(heartbeats) => heartbeats.clone(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn component_dies_after_missing_beats() {
+    let registry = HeartbeatRegistry::new(Duration::from_millis(10));
+    let heartbeat = registry.register("COMPONENT".to_string());
+
+    assert!(heartbeat.is_alive());
+
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!registry.heartbeats()[0].is_alive());
+
+    heartbeat.beat();
+    assert!(registry.heartbeats()[0].is_alive());
+}
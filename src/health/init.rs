@@ -0,0 +1,21 @@
+use crate::error::Error;
+use crate::health::heartbeat::HeartbeatRegistry;
+use crate::health::monitor;
+use crate::health::readiness::{self, Readiness};
+use cooplan_state_tracker::state_tracker_client::StateTrackerClient;
+
+pub async fn initialize(
+    registry: HeartbeatRegistry,
+    state_tracker_client: StateTrackerClient,
+    readiness_address: Option<String>,
+) -> Result<(), Error> {
+    let readiness = Readiness::default();
+
+    if let Some(readiness_address) = readiness_address {
+        readiness::serve(readiness_address, readiness.clone()).await?;
+    }
+
+    tokio::spawn(monitor::run(registry, state_tracker_client, readiness));
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+pub mod heartbeat;
+pub mod init;
+mod monitor;
+mod readiness;
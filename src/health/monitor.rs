@@ -0,0 +1,52 @@
+use crate::health::heartbeat::{Heartbeat, HeartbeatRegistry};
+use crate::health::readiness::{ComponentStates, Readiness};
+use cooplan_state_tracker::state::State;
+use cooplan_state_tracker::state_tracker_client::StateTrackerClient;
+
+/// Checks every registered heartbeat once per interval, reporting its state through the
+/// state tracker under the component's name and updating the readiness accordingly.
+pub async fn run(
+    registry: HeartbeatRegistry,
+    state_tracker_client: StateTrackerClient,
+    readiness: Readiness,
+) {
+    loop {
+        let states: ComponentStates = registry
+            .heartbeats()
+            .iter()
+            .map(|heartbeat| (heartbeat.component().to_string(), check(heartbeat)))
+            .collect();
+
+        for (component, state) in &states {
+            let state = match state {
+                Ok(()) => State::Valid,
+                Err(error) => {
+                    log::warn!("component '{}' is unhealthy: {}", component, error);
+                    State::Error(error.clone())
+                }
+            };
+
+            let mut state_tracker_client = state_tracker_client.clone();
+            state_tracker_client.set_id(component.clone());
+
+            if let Err(error) = state_tracker_client.send_state(state).await {
+                log::error!("failed to report state of '{}': {}", component, error);
+            }
+        }
+
+        readiness.update(states);
+
+        tokio::time::sleep(registry.interval()).await;
+    }
+}
+
+fn check(heartbeat: &Heartbeat) -> Result<(), String> {
+    if heartbeat.is_alive() {
+        return Ok(());
+    }
+
+    Err(format!(
+        "no heartbeat within the last {} ms",
+        heartbeat.since_last_beat().as_millis()
+    ))
+}
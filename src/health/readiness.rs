@@ -0,0 +1,153 @@
+use crate::error::{Error, ErrorKind};
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde_json::{json, Map, Value};
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::{Arc, RwLock};
+use tokio::net::TcpListener;
+
+/// State of every monitored component, by name.
+pub type ComponentStates = Vec<(String, Result<(), String>)>;
+
+/// Latest state of every monitored component.
+#[derive(Clone, Default)]
+pub struct Readiness {
+    components: Arc<RwLock<ComponentStates>>,
+}
+
+impl Readiness {
+    pub fn update(&self, components: ComponentStates) {
+        if let Ok(mut current) = self.components.write() {
+            *current = components;
+        }
+    }
+
+    /// Ready once the components have been checked, and only while all of them are healthy.
+    pub fn is_ready(&self) -> bool {
+        match self.components.read() {
+            Ok(components) => {
+                !components.is_empty() && components.iter().all(|(_, state)| state.is_ok())
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn report(&self) -> Value {
+        let mut components = Map::new();
+
+        if let Ok(current) = self.components.read() {
+            for (component, state) in current.iter() {
+                let state = match state {
+                    Ok(()) => "ok".to_string(),
+                    Err(error) => error.clone(),
+                };
+
+                components.insert(component.clone(), Value::String(state));
+            }
+        }
+
+        json!({ "ready": self.is_ready(), "components": components })
+    }
+
+    fn response(&self) -> Response<Full<Bytes>> {
+        let status = if self.is_ready() {
+            StatusCode::OK
+        } else {
+            StatusCode::SERVICE_UNAVAILABLE
+        };
+
+        let mut response = Response::new(Full::new(Bytes::from(self.report().to_string())));
+        *response.status_mut() = status;
+
+        response
+    }
+}
+
+/// Answers any request on the address with `200` while ready, or `503` otherwise,
+/// along with the state of every component.
+pub async fn serve(address: String, readiness: Readiness) -> Result<(), Error> {
+    let address = match address.parse::<SocketAddr>() {
+        Ok(address) => address,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("invalid readiness address '{}': {}", address, error),
+            ));
+        }
+    };
+
+    let listener = match TcpListener::bind(address).await {
+        Ok(listener) => listener,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("failed to bind readiness endpoint: {}", error),
+            ));
+        }
+    };
+
+    tokio::spawn(async move {
+        loop {
+            let stream = match listener.accept().await {
+                Ok((stream, _)) => stream,
+                Err(error) => {
+                    log::warn!("failed to accept readiness connection: {}", error);
+                    continue;
+                }
+            };
+
+            let readiness = readiness.clone();
+
+            tokio::spawn(async move {
+                let service = service_fn(move |_| {
+                    let response = readiness.response();
+
+                    async move { Ok::<_, Infallible>(response) }
+                });
+
+                if let Err(error) = http1::Builder::new()
+                    .serve_connection(TokioIo::new(stream), service)
+                    .await
+                {
+                    log::debug!("failed to serve readiness connection: {}", error);
+                }
+            });
+        }
+    });
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn not_ready_while_any_component_is_unhealthy() {
+    let readiness = Readiness::default();
+
+    assert_eq!(
+        readiness.response().status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+
+    readiness.update(vec![
+        ("mongodb".to_string(), Ok(())),
+        (
+            "logic_dispatch_0".to_string(),
+            Err("no heartbeat".to_string()),
+        ),
+    ]);
+
+    assert_eq!(
+        readiness.response().status(),
+        StatusCode::SERVICE_UNAVAILABLE
+    );
+    assert_eq!(readiness.report()["components"]["mongodb"], "ok");
+
+    readiness.update(vec![("mongodb".to_string(), Ok(()))]);
+
+    assert_eq!(readiness.response().status(), StatusCode::OK);
+}
@@ -0,0 +1,12 @@
+pub mod api;
+pub mod config;
+pub mod deadline;
+pub mod dispatcher_pool;
+pub mod error;
+pub mod health;
+pub mod logic;
+pub mod reload;
+pub mod shutdown;
+pub mod storage;
+mod supervisor;
+pub mod telemetry;
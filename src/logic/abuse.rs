@@ -0,0 +1,43 @@
+use crate::logic::audit;
+use crate::logic::elements::abuse_event::AbuseEvent;
+use crate::logic::elements::audit_record::{AuditAction, AuditChange, AuditRecord};
+use crate::logic::storage_request::StorageRequestSender;
+use serde_json::Value;
+use tokio::sync::mpsc::Receiver;
+
+/// Leaves an audit record behind for every abuse event reported by the api.
+/// Those records are not tied to any organization, hence their empty `organization_id`.
+pub async fn run(
+    mut abuse_receiver: Receiver<AbuseEvent>,
+    storage_request_sender: StorageRequestSender,
+) {
+    while let Some(event) = abuse_receiver.recv().await {
+        let record = match event {
+            AbuseEvent::RateLimited { user_id, action } => AuditRecord::new(
+                user_id,
+                String::new(),
+                AuditAction::RateLimited,
+                vec![detail("action", Value::from(action))],
+            ),
+            AbuseEvent::InvitationLockout {
+                user_id,
+                locked_until,
+            } => AuditRecord::new(
+                user_id,
+                String::new(),
+                AuditAction::InvitationLockout,
+                vec![detail("locked_until", Value::from(locked_until))],
+            ),
+        };
+
+        audit::record(record, &storage_request_sender).await;
+    }
+}
+
+fn detail(field: &str, value: Value) -> AuditChange {
+    AuditChange {
+        field: field.to_string(),
+        before: None,
+        after: Some(value),
+    }
+}
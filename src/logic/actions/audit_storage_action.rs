@@ -0,0 +1,34 @@
+use crate::error::Error;
+use crate::logic::elements::audit_record::AuditRecord;
+use tokio::sync::oneshot::Sender;
+
+#[derive(Debug)]
+pub enum AuditStorageAction {
+    Create {
+        record: AuditRecord,
+        replier: Sender<Result<(), Error>>,
+    },
+    /// Returns the most recent records first.
+    FindByOrganizationId {
+        organization_id: String,
+        page: u64,
+        page_size: u64,
+        replier: Sender<Result<Vec<AuditRecord>, Error>>,
+    },
+}
+
+impl AuditStorageAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            AuditStorageAction::Create { .. } => "create",
+            AuditStorageAction::FindByOrganizationId { .. } => "find_by_organization_id",
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            AuditStorageAction::Create { replier, .. } => replier.is_closed(),
+            AuditStorageAction::FindByOrganizationId { replier, .. } => replier.is_closed(),
+        }
+    }
+}
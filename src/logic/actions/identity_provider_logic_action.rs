@@ -0,0 +1,32 @@
+use crate::error::Error;
+use tokio::sync::oneshot::Sender;
+
+/// Changes of a user's lifecycle notified by the identity provider, applying to `user_id`.
+pub enum IdentityProviderLogicAction {
+    Deleted {
+        /// Subject of the token which notified the change.
+        actor: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Blocked {
+        actor: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+    Unblocked {
+        actor: String,
+        user_id: String,
+        replier: Sender<Result<(), Error>>,
+    },
+}
+
+impl IdentityProviderLogicAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            IdentityProviderLogicAction::Deleted { .. } => "user_deleted",
+            IdentityProviderLogicAction::Blocked { .. } => "user_blocked",
+            IdentityProviderLogicAction::Unblocked { .. } => "user_unblocked",
+        }
+    }
+}
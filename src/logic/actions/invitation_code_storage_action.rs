@@ -1,9 +1,19 @@
 use crate::error::Error;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::DomainEvent;
 use crate::logic::elements::invitation::Invitation;
 use tokio::sync::oneshot::Sender;
 
 #[derive(Debug)]
 pub enum InvitationStorageAction {
+    Create {
+        invitation: Invitation,
+        /// Published only if the invitation is created.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the invitation is created.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<(), Error>>,
+    },
     Delete {
         code: String,
         replier: Sender<Result<(), Error>>,
@@ -13,3 +23,21 @@ pub enum InvitationStorageAction {
         replier: Sender<Result<Option<Invitation>, Error>>,
     },
 }
+
+impl InvitationStorageAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            InvitationStorageAction::Create { .. } => "create",
+            InvitationStorageAction::Delete { .. } => "delete",
+            InvitationStorageAction::FindByCode { .. } => "find_by_code",
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            InvitationStorageAction::Create { replier, .. } => replier.is_closed(),
+            InvitationStorageAction::Delete { replier, .. } => replier.is_closed(),
+            InvitationStorageAction::FindByCode { replier, .. } => replier.is_closed(),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+pub mod audit_storage_action;
+pub mod identity_provider_logic_action;
 pub mod invitation_code_storage_action;
 pub mod organization_logic_action;
 pub mod organization_root_logic_action;
@@ -1,5 +1,11 @@
 use crate::error::Error;
+use crate::logic::caller::Caller;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::batch_read::BatchEntry;
 use crate::logic::elements::organization::Organization;
+use crate::logic::elements::organization_listing::{
+    OrganizationFilter, OrganizationPage, OrganizationSort,
+};
 use tokio::sync::oneshot::Sender;
 
 pub enum OrganizationLogicAction {
@@ -11,9 +17,70 @@ pub enum OrganizationLogicAction {
         telephone: String,
         replier: Sender<Result<Organization, Error>>,
     },
+    Update {
+        user_id: String,
+        organization_id: String,
+        /// Version the update is based on.
+        version: u64,
+        name: String,
+        country: String,
+        address: String,
+        telephone: String,
+        replier: Sender<Result<Organization, Error>>,
+    },
+    Delete {
+        user_id: String,
+        organization_id: String,
+        /// Version the deletion is based on.
+        version: u64,
+        replier: Sender<Result<(), Error>>,
+    },
+    Restore {
+        user_id: String,
+        organization_id: String,
+        replier: Sender<Result<Organization, Error>>,
+    },
     Join {
         user_id: String,
         invitation_code: String,
         replier: Sender<Result<Organization, Error>>,
     },
+    /// Reads a page of the organization's audit trail, most recent records first.
+    Audit {
+        caller: Caller,
+        organization_id: String,
+        page: u64,
+        page_size: u64,
+        replier: Sender<Result<Vec<AuditRecord>, Error>>,
+    },
+    /// Reads the organizations themselves, without their users and invitations.
+    ReadMany {
+        caller: Caller,
+        ids: Vec<String>,
+        replier: Sender<Result<Vec<BatchEntry<Organization>>, Error>>,
+    },
+    /// Lists a page of the organizations the caller is a member of, or of all of them for services.
+    List {
+        caller: Caller,
+        filter: OrganizationFilter,
+        sort: OrganizationSort,
+        cursor: Option<String>,
+        page_size: u32,
+        replier: Sender<Result<OrganizationPage, Error>>,
+    },
+}
+
+impl OrganizationLogicAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrganizationLogicAction::Create { .. } => "create",
+            OrganizationLogicAction::Update { .. } => "update",
+            OrganizationLogicAction::Delete { .. } => "delete",
+            OrganizationLogicAction::Restore { .. } => "restore",
+            OrganizationLogicAction::Join { .. } => "join",
+            OrganizationLogicAction::Audit { .. } => "audit",
+            OrganizationLogicAction::ReadMany { .. } => "read_many",
+            OrganizationLogicAction::List { .. } => "list",
+        }
+    }
 }
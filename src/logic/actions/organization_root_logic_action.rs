@@ -1,10 +1,21 @@
 use crate::error::Error;
-use crate::logic::elements::organization_root::OrganizationRoot;
+use crate::logic::caller::Caller;
+use crate::logic::elements::organization_root::{OrganizationRoot, OrganizationRootQuery};
 use tokio::sync::oneshot::Sender;
 
 pub enum OrganizationRootLogicAction {
     Read {
+        caller: Caller,
         organization_id: String,
+        query: OrganizationRootQuery,
         replier: Sender<Result<OrganizationRoot, Error>>,
     },
 }
+
+impl OrganizationRootLogicAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrganizationRootLogicAction::Read { .. } => "read",
+        }
+    }
+}
@@ -1,11 +1,26 @@
 use crate::error::Error;
-use crate::logic::elements::organization_root::OrganizationRoot;
+use crate::logic::elements::organization_root::{OrganizationRoot, OrganizationRootQuery};
 use tokio::sync::oneshot::Sender;
 
 #[derive(Debug)]
 pub enum OrganizationRootStorageAction {
     Read {
         organization_id: String,
+        query: OrganizationRootQuery,
         replier: Sender<Result<OrganizationRoot, Error>>,
     },
 }
+
+impl OrganizationRootStorageAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrganizationRootStorageAction::Read { .. } => "read",
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            OrganizationRootStorageAction::Read { replier, .. } => replier.is_closed(),
+        }
+    }
+}
@@ -1,5 +1,10 @@
 use crate::error::Error;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::DomainEvent;
 use crate::logic::elements::organization::Organization;
+use crate::logic::elements::organization_listing::{
+    OrganizationCursor, OrganizationFilter, OrganizationSort,
+};
 use tokio::sync::oneshot::Sender;
 
 #[derive(Debug)]
@@ -11,14 +16,50 @@ pub enum OrganizationStorageAction {
         telephone: String,
         replier: Sender<Result<Organization, Error>>,
     },
+    /// Overwrites the organization's details, if its version is still the one
+    /// contained by the passed organization.
+    Update {
+        organization: Organization,
+        /// Published only if the organization is updated.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the organization is updated.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<Organization, Error>>,
+    },
     Delete {
         id: String,
         replier: Sender<Result<(), Error>>,
     },
+    /// Hides the organization until it is either restored or purged.
+    SoftDelete {
+        id: String,
+        /// Version the deletion is based on.
+        version: u64,
+        /// Published only if the organization is deleted.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the organization is deleted.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<(), Error>>,
+    },
+    /// Reverts a soft deletion, as long as it happened within the retention period.
+    Restore {
+        id: String,
+        /// Published only if the organization is restored.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the organization is restored,
+        /// once their changes are completed with the restored organization.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<Organization, Error>>,
+    },
     FindById {
         id: String,
         replier: Sender<Result<Option<Organization>, Error>>,
     },
+    /// Only the organizations found, in no particular order.
+    FindByIds {
+        ids: Vec<String>,
+        replier: Sender<Result<Vec<Organization>, Error>>,
+    },
     FindByName {
         name: String,
         replier: Sender<Result<Option<Organization>, Error>>,
@@ -27,4 +68,52 @@ pub enum OrganizationStorageAction {
         telephone: String,
         replier: Sender<Result<Option<Organization>, Error>>,
     },
+    /// Organizations whose name contains `name`, ignoring case, or every organization if none.
+    Search {
+        name: Option<String>,
+        limit: u32,
+        replier: Sender<Result<Vec<Organization>, Error>>,
+    },
+    /// Up to `limit` organizations meeting the filter, in order, starting right after the cursor, if any.
+    List {
+        filter: OrganizationFilter,
+        sort: OrganizationSort,
+        after: Option<OrganizationCursor>,
+        limit: u32,
+        replier: Sender<Result<Vec<Organization>, Error>>,
+    },
+}
+
+impl OrganizationStorageAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrganizationStorageAction::Create { .. } => "create",
+            OrganizationStorageAction::Update { .. } => "update",
+            OrganizationStorageAction::Delete { .. } => "delete",
+            OrganizationStorageAction::SoftDelete { .. } => "soft_delete",
+            OrganizationStorageAction::Restore { .. } => "restore",
+            OrganizationStorageAction::FindById { .. } => "find_by_id",
+            OrganizationStorageAction::FindByIds { .. } => "find_by_ids",
+            OrganizationStorageAction::FindByName { .. } => "find_by_name",
+            OrganizationStorageAction::FindByTelephone { .. } => "find_by_telephone",
+            OrganizationStorageAction::Search { .. } => "search",
+            OrganizationStorageAction::List { .. } => "list",
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            OrganizationStorageAction::Create { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::Update { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::Delete { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::SoftDelete { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::Restore { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::FindById { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::FindByIds { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::FindByName { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::FindByTelephone { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::Search { replier, .. } => replier.is_closed(),
+            OrganizationStorageAction::List { replier, .. } => replier.is_closed(),
+        }
+    }
 }
@@ -1,11 +1,48 @@
 use crate::error::Error;
+use crate::logic::caller::Caller;
+use crate::logic::elements::batch_read::BatchEntry;
 use crate::logic::elements::user::User;
+use std::collections::BTreeMap;
 use tokio::sync::oneshot::Sender;
 
 #[derive(Debug)]
 pub enum UserLogicAction {
     Read {
+        caller: Caller,
         user_id: String,
         replier: Sender<Result<User, Error>>,
     },
+    ReadMany {
+        caller: Caller,
+        user_ids: Vec<String>,
+        replier: Sender<Result<Vec<BatchEntry<User>>, Error>>,
+    },
+    /// Whether each permission applies to the user within the organization.
+    CheckPermission {
+        caller: Caller,
+        user_id: String,
+        organization_id: String,
+        permissions: Vec<String>,
+        replier: Sender<Result<BTreeMap<String, bool>, Error>>,
+    },
+    EditPermissions {
+        user_id: String,
+        target_user_id: String,
+        organization_id: String,
+        permissions: Vec<String>,
+        /// Version of the target user the update is based on.
+        version: u64,
+        replier: Sender<Result<User, Error>>,
+    },
+}
+
+impl UserLogicAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserLogicAction::Read { .. } => "read",
+            UserLogicAction::ReadMany { .. } => "read_many",
+            UserLogicAction::CheckPermission { .. } => "check_permission",
+            UserLogicAction::EditPermissions { .. } => "edit_permissions",
+        }
+    }
 }
@@ -1,4 +1,6 @@
 use crate::error::Error;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::DomainEvent;
 use crate::logic::elements::user::User;
 use crate::logic::elements::user_organization::UserOrganization;
 use tokio::sync::oneshot::Sender;
@@ -8,14 +10,105 @@ pub enum UserStorageAction {
     Create {
         id: String,
         organization: UserOrganization,
+        /// Published only if the user is created.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the user is created.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<User, Error>>,
+    },
+    UpdatePermissions {
+        user_id: String,
+        organization_id: String,
+        permissions: Vec<String>,
+        /// Version the update is based on.
+        version: u64,
+        /// Published only if the permissions are updated.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the permissions are updated.
+        audit_records: Vec<AuditRecord>,
         replier: Sender<Result<User, Error>>,
     },
     Delete {
         id: String,
         replier: Sender<Result<(), Error>>,
     },
+    /// Removes the membership, along with the user once it has no memberships left.
+    RemoveMembership {
+        user_id: String,
+        organization_id: String,
+        /// Published only if the membership is removed.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if the membership is removed.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<(), Error>>,
+    },
+    /// Users which have never been stored have nothing to suspend, so they are ignored.
+    SetSuspended {
+        user_id: String,
+        suspended: bool,
+        /// Published only if a stored user is updated.
+        events: Vec<DomainEvent>,
+        /// Stored along with the events, only if a stored user is updated.
+        audit_records: Vec<AuditRecord>,
+        replier: Sender<Result<(), Error>>,
+    },
+    CountByOrganizationId {
+        organization_id: String,
+        replier: Sender<Result<u64, Error>>,
+    },
+    CountAdministratorsByOrganizationId {
+        organization_id: String,
+        replier: Sender<Result<u64, Error>>,
+    },
     FindUserById {
         user_id: String,
         replier: Sender<Result<Option<User>, Error>>,
     },
+    /// The user along with only their membership of the organization, if they are a member.
+    FindMembership {
+        user_id: String,
+        organization_id: String,
+        replier: Sender<Result<Option<User>, Error>>,
+    },
+    /// Only the users found, in no particular order.
+    FindUsersByIds {
+        user_ids: Vec<String>,
+        replier: Sender<Result<Vec<User>, Error>>,
+    },
+}
+
+impl UserStorageAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserStorageAction::Create { .. } => "create",
+            UserStorageAction::UpdatePermissions { .. } => "update_permissions",
+            UserStorageAction::Delete { .. } => "delete",
+            UserStorageAction::RemoveMembership { .. } => "remove_membership",
+            UserStorageAction::SetSuspended { .. } => "set_suspended",
+            UserStorageAction::CountByOrganizationId { .. } => "count_by_organization_id",
+            UserStorageAction::CountAdministratorsByOrganizationId { .. } => {
+                "count_administrators_by_organization_id"
+            }
+            UserStorageAction::FindUserById { .. } => "find_user_by_id",
+            UserStorageAction::FindUsersByIds { .. } => "find_users_by_ids",
+            UserStorageAction::FindMembership { .. } => "find_membership",
+        }
+    }
+
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            UserStorageAction::Create { replier, .. } => replier.is_closed(),
+            UserStorageAction::UpdatePermissions { replier, .. } => replier.is_closed(),
+            UserStorageAction::Delete { replier, .. } => replier.is_closed(),
+            UserStorageAction::RemoveMembership { replier, .. } => replier.is_closed(),
+            UserStorageAction::SetSuspended { replier, .. } => replier.is_closed(),
+            UserStorageAction::CountByOrganizationId { replier, .. } => replier.is_closed(),
+            UserStorageAction::CountAdministratorsByOrganizationId { replier, .. } => {
+                replier.is_closed()
+            }
+            UserStorageAction::FindUserById { replier, .. } => replier.is_closed(),
+            UserStorageAction::FindUsersByIds { replier, .. } => replier.is_closed(),
+            UserStorageAction::FindMembership { replier, .. } => replier.is_closed(),
+        }
+    }
 }
@@ -0,0 +1,48 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::actions::audit_storage_action::AuditStorageAction;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
+
+/// Stores an audit record which does not describe any mutation, such as an abuse event.
+/// Mutations pass their records to storage instead, to be written within their transaction.
+/// Failures are only logged.
+pub async fn record(record: AuditRecord, storage_request_sender: &StorageRequestSender) {
+    if let Err(error) = try_record(record, storage_request_sender).await {
+        log::error!("failed to store audit record: {}", error);
+    }
+}
+
+/// Stores the audit record, failing if it cannot be stored, such as before granting an access.
+pub async fn try_record(
+    record: AuditRecord,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<(), Error> {
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender
+        .send(StorageRequest::Audit(AuditStorageAction::Create {
+            record,
+            replier,
+        }))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    match receiver.await {
+        Ok(result) => result,
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
+}
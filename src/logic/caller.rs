@@ -0,0 +1,118 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::audit;
+use crate::logic::elements::audit_record::{AuditAction, AuditRecord};
+use crate::logic::storage_request::StorageRequestSender;
+
+/// Actions, by `element.action`, which scopes may grant to services regardless of memberships.
+/// Only read-only actions are eligible.
+pub const SERVICE_ACTIONS: [&str; 5] = [
+    "organization.read",
+    "organization.audit",
+    "organization.list",
+    "user.read",
+    "user.check_permission",
+];
+
+/// Sender of a request.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Caller {
+    /// A person, who may only act upon the organizations they are a member of.
+    User(String),
+    /// Another service, whose token holds a scope granting the action.
+    Service { client_id: String, scope: String },
+}
+
+impl Caller {
+    pub fn id(&self) -> &str {
+        match self {
+            Caller::User(user_id) => user_id.as_str(),
+            Caller::Service { client_id, .. } => client_id.as_str(),
+        }
+    }
+}
+
+/// Records within the audit log the access of a service, granted by its scope rather than
+/// a membership, before the access proceeds. Services are denied any access which cannot be recorded.
+/// Users are not recorded.
+pub async fn record_service_access(
+    caller: &Caller,
+    organization_id: &str,
+    target_user_id: Option<&str>,
+    action: AuditAction,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<(), Error> {
+    let (client_id, scope) = match caller {
+        Caller::User(_) => return Ok(()),
+        Caller::Service { client_id, scope } => (client_id, scope),
+    };
+
+    let mut record = AuditRecord::new(
+        client_id.clone(),
+        organization_id.to_string(),
+        action,
+        Vec::new(),
+    )
+    .with_scope(scope.clone());
+
+    if let Some(target_user_id) = target_user_id {
+        record = record.with_target_user_id(target_user_id.to_string());
+    }
+
+    match audit::try_record(record, storage_request_sender).await {
+        Ok(()) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!("failed to record the access of a service: {}", error),
+        )),
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn record_only_the_accesses_of_services() {
+    use crate::logic::actions::audit_storage_action::AuditStorageAction;
+    use crate::logic::storage_request::StorageRequest;
+
+    let (sender, receiver) = crate::telemetry::traced_channel::bounded(1);
+
+    let user = Caller::User("USER".to_string());
+    record_service_access(
+        &user,
+        "ORGANIZATION",
+        None,
+        AuditAction::ServiceRead,
+        &sender,
+    )
+    .await
+    .unwrap();
+    assert!(receiver.is_empty());
+
+    let storage = tokio::spawn(async move {
+        match receiver.recv().await.unwrap().request {
+            StorageRequest::Audit(AuditStorageAction::Create { record, replier }) => {
+                replier.send(Ok(())).unwrap();
+                record
+            }
+            _ => panic!("expected an audit record"),
+        }
+    });
+
+    let service = Caller::Service {
+        client_id: "BILLING@clients".to_string(),
+        scope: "organization:read:any".to_string(),
+    };
+    record_service_access(
+        &service,
+        "ORGANIZATION",
+        None,
+        AuditAction::ServiceRead,
+        &sender,
+    )
+    .await
+    .unwrap();
+
+    let record = storage.await.unwrap();
+    assert_eq!("BILLING@clients", record.actor);
+    assert_eq!("service_read", record.action);
+    assert_eq!(Some("organization:read:any".to_string()), record.scope);
+}
@@ -0,0 +1,13 @@
+/// Abusive behaviour of a user, rejected by the api before reaching the logic.
+pub enum AbuseEvent {
+    RateLimited {
+        user_id: String,
+        /// `element.action` whose rate limit has been exceeded.
+        action: String,
+    },
+    InvitationLockout {
+        user_id: String,
+        /// Unix timestamp, seconds after the UNIX EPOCH
+        locked_until: u64,
+    },
+}
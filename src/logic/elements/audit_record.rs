@@ -0,0 +1,169 @@
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::BTreeSet;
+use std::time::SystemTime;
+
+/// Mutations, rejections of abusive requests and reads by services, which leave an audit record behind.
+pub enum AuditAction {
+    Create,
+    Join,
+    Update,
+    Delete,
+    Restore,
+    EditPermissions,
+    RemoveMember,
+    Suspend,
+    Reinstate,
+    Invite,
+    RevokeInvitation,
+    RateLimited,
+    InvitationLockout,
+    ServiceRead,
+    ServiceReadAudit,
+    ServiceReadUser,
+    ServiceList,
+    ServiceCheckPermission,
+}
+
+impl AuditAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::Create => "create",
+            AuditAction::Join => "join",
+            AuditAction::Update => "update",
+            AuditAction::Delete => "delete",
+            AuditAction::Restore => "restore",
+            AuditAction::EditPermissions => "edit_permissions",
+            AuditAction::RemoveMember => "remove_member",
+            AuditAction::Suspend => "suspend",
+            AuditAction::Reinstate => "reinstate",
+            AuditAction::Invite => "invite",
+            AuditAction::RevokeInvitation => "revoke_invitation",
+            AuditAction::RateLimited => "rate_limited",
+            AuditAction::InvitationLockout => "invitation_lockout",
+            AuditAction::ServiceRead => "service_read",
+            AuditAction::ServiceReadAudit => "service_read_audit",
+            AuditAction::ServiceReadUser => "service_read_user",
+            AuditAction::ServiceList => "service_list",
+            AuditAction::ServiceCheckPermission => "service_check_permission",
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct AuditRecord {
+    /// Subject of the token which performed the mutation.
+    pub actor: String,
+    pub organization_id: String,
+    /// User whose membership has been affected, if any.
+    pub target_user_id: Option<String>,
+    /// Scope which granted a service the action, if any.
+    pub scope: Option<String>,
+    pub action: String,
+    pub changes: Vec<AuditChange>,
+    /// Unix timestamp, seconds after the UNIX EPOCH
+    pub timestamp: u64,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct AuditChange {
+    pub field: String,
+    pub before: Option<Value>,
+    pub after: Option<Value>,
+}
+
+impl AuditRecord {
+    pub fn new(
+        actor: String,
+        organization_id: String,
+        action: AuditAction,
+        changes: Vec<AuditChange>,
+    ) -> AuditRecord {
+        AuditRecord {
+            actor,
+            organization_id,
+            target_user_id: None,
+            scope: None,
+            action: action.as_str().to_string(),
+            changes,
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+
+    pub fn with_target_user_id(mut self, target_user_id: String) -> AuditRecord {
+        self.target_user_id = Some(target_user_id);
+        self
+    }
+
+    pub fn with_scope(mut self, scope: String) -> AuditRecord {
+        self.scope = Some(scope);
+        self
+    }
+}
+
+/// Lists the top level fields which differ between both states.
+/// A missing state, such as the one before a creation, has no fields at all.
+pub fn changes<T: Serialize>(before: Option<&T>, after: Option<&T>) -> Vec<AuditChange> {
+    let before = to_object(before);
+    let after = to_object(after);
+
+    let fields: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+
+    fields
+        .into_iter()
+        .filter_map(|field| {
+            let before = before.get(field);
+            let after = after.get(field);
+
+            if before == after {
+                return None;
+            }
+
+            Some(AuditChange {
+                field: field.clone(),
+                before: before.cloned(),
+                after: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn to_object<T: Serialize>(state: Option<&T>) -> serde_json::Map<String, Value> {
+    match state.map(serde_json::to_value) {
+        Some(Ok(Value::Object(object))) => object,
+        _ => serde_json::Map::new(),
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn changes_only_contain_modified_fields() {
+    use crate::logic::elements::user_organization::UserOrganization;
+
+    let before = UserOrganization {
+        organization_id: "ORGANIZATION_ID".to_string(),
+        permissions: vec!["read:org".to_string()],
+    };
+
+    let after = UserOrganization {
+        organization_id: "ORGANIZATION_ID".to_string(),
+        permissions: vec!["read:org".to_string(), "update:org".to_string()],
+    };
+
+    assert_eq!(
+        vec![AuditChange {
+            field: "permissions".to_string(),
+            before: Some(serde_json::json!(["read:org"])),
+            after: Some(serde_json::json!(["read:org", "update:org"])),
+        }],
+        changes(Some(&before), Some(&after))
+    );
+
+    let created = changes(None, Some(&after));
+
+    assert_eq!(2, created.len());
+    assert!(created.iter().all(|change| change.before.is_none()));
+}
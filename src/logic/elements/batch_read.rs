@@ -0,0 +1,97 @@
+use crate::error::{Error, ErrorKind};
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Most ids a single batch read accepts.
+pub const MAX_BATCH_SIZE: usize = 100;
+
+/// Outcome of reading one of the requested ids, either its value or the code of its failure.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct BatchEntry<T> {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<&'static str>,
+}
+
+/// Requested ids without duplicates, in their original order.
+pub fn unique_ids(ids: Vec<String>, field: &'static str) -> Result<Vec<String>, Error> {
+    if ids.is_empty() || ids.len() > MAX_BATCH_SIZE {
+        return Err(Error::new(
+            ErrorKind::InvalidArgument,
+            format!("between 1 and {} ids must be requested", MAX_BATCH_SIZE),
+        )
+        .with_field(field));
+    }
+
+    let mut unique_ids: Vec<String> = Vec::with_capacity(ids.len());
+
+    for id in ids {
+        if !unique_ids.contains(&id) {
+            unique_ids.push(id);
+        }
+    }
+
+    Ok(unique_ids)
+}
+
+/// One entry per requested id, in order, those without an element being marked as `missing`.
+pub fn entries<T>(
+    ids: Vec<String>,
+    elements: Vec<T>,
+    id: impl Fn(&T) -> &str,
+    missing: ErrorKind,
+) -> Vec<BatchEntry<T>> {
+    let mut elements: HashMap<String, T> = elements
+        .into_iter()
+        .map(|element| (id(&element).to_string(), element))
+        .collect();
+
+    ids.into_iter()
+        .map(|id| match elements.remove(&id) {
+            Some(element) => BatchEntry {
+                id,
+                value: Some(element),
+                error: None,
+            },
+            None => BatchEntry {
+                id,
+                value: None,
+                error: Some(missing.code()),
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn mark_each_missing_id() {
+    let ids = unique_ids(
+        vec!["B".to_string(), "A".to_string(), "B".to_string()],
+        "ids",
+    )
+    .unwrap();
+    assert_eq!(vec!["B", "A"], ids);
+
+    let entries = entries(
+        ids,
+        vec!["A".to_string()],
+        |element| element.as_str(),
+        ErrorKind::UserNotFound,
+    );
+
+    assert_eq!(
+        serde_json::json!([
+            { "id": "B", "error": "user_not_found" },
+            { "id": "A", "value": "A" },
+        ]),
+        serde_json::to_value(entries).unwrap()
+    );
+}
+
+#[test]
+fn limit_the_ids_requested() {
+    assert!(unique_ids(Vec::new(), "ids").is_err());
+    assert!(unique_ids(vec![String::new(); MAX_BATCH_SIZE + 1], "ids").is_err());
+}
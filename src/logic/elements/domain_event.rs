@@ -0,0 +1,129 @@
+use mongodb::bson::oid::ObjectId;
+use serde::Serialize;
+use std::time::SystemTime;
+
+/// Output element through which domain events are published.
+pub const OUTPUT_ELEMENT_ID: &str = "organization_event";
+
+/// Incremented whenever a payload changes in a way which is not backwards compatible.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// Envelope of every event published to other services:
+/// `{ "id", "schema_version", "occurred_at", "type", "data" }`.
+#[derive(Debug, Serialize)]
+pub struct DomainEvent {
+    /// Unique for each event, so consumers can discard duplicated deliveries.
+    pub id: String,
+    pub schema_version: u32,
+    /// Unix timestamp, seconds after the UNIX EPOCH
+    pub occurred_at: u64,
+    #[serde(flatten)]
+    pub payload: DomainEventPayload,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", content = "data")]
+pub enum DomainEventPayload {
+    OrganizationCreated {
+        organization_id: String,
+        name: String,
+        country: String,
+        address: String,
+        telephone: String,
+        actor: String,
+    },
+    OrganizationUpdated {
+        organization_id: String,
+        name: String,
+        country: String,
+        address: String,
+        telephone: String,
+        version: u64,
+        actor: String,
+    },
+    OrganizationDeleted {
+        organization_id: String,
+        actor: String,
+    },
+    OrganizationRestored {
+        organization_id: String,
+        actor: String,
+    },
+    UserJoined {
+        organization_id: String,
+        user_id: String,
+        permissions: Vec<String>,
+    },
+    /// The user has left the organization, by deleting their account.
+    UserLeft {
+        organization_id: String,
+        user_id: String,
+    },
+    /// The membership has been removed by someone other than the user.
+    UserRemoved {
+        organization_id: String,
+        user_id: String,
+    },
+    /// The user has been blocked within the identity provider.
+    UserSuspended { user_id: String },
+    /// The user has been unblocked within the identity provider.
+    UserReinstated { user_id: String },
+    PermissionsChanged {
+        organization_id: String,
+        user_id: String,
+        permissions: Vec<String>,
+        actor: String,
+    },
+    /// Emitted by the administrative tool, through which invitations are created.
+    InvitationCreated {
+        organization_id: String,
+        permissions: Vec<String>,
+        /// Unix timestamp, seconds after the UNIX EPOCH
+        expires_at: u64,
+    },
+    InvitationConsumed {
+        organization_id: String,
+        user_id: String,
+    },
+}
+
+impl DomainEvent {
+    pub fn new(payload: DomainEventPayload) -> DomainEvent {
+        DomainEvent {
+            id: ObjectId::new().to_hex(),
+            schema_version: SCHEMA_VERSION,
+            occurred_at: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            payload,
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn event_is_serialized_with_a_stable_envelope() {
+    let event = DomainEvent::new(DomainEventPayload::UserJoined {
+        organization_id: "ORGANIZATION_ID".to_string(),
+        user_id: "USER_ID".to_string(),
+        permissions: vec!["read:org".to_string()],
+    });
+
+    let value = serde_json::to_value(&event).unwrap();
+
+    assert_eq!(
+        serde_json::json!({
+            "id": event.id,
+            "schema_version": SCHEMA_VERSION,
+            "occurred_at": event.occurred_at,
+            "type": "UserJoined",
+            "data": {
+                "organization_id": "ORGANIZATION_ID",
+                "user_id": "USER_ID",
+                "permissions": ["read:org"],
+            },
+        }),
+        value
+    );
+}
@@ -1,5 +1,10 @@
+pub mod abuse_event;
+pub mod audit_record;
+pub mod batch_read;
+pub mod domain_event;
 pub mod invitation;
 pub mod organization;
+pub mod organization_listing;
 pub mod organization_root;
 pub mod user;
 pub mod user_organization;
@@ -1,6 +1,6 @@
 use serde::Serialize;
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone)]
 pub struct Organization {
     pub id: String,
     pub name: String,
@@ -8,4 +8,5 @@ pub struct Organization {
     pub address: String,
     pub telephone: String,
     pub permissions: Vec<String>,
+    pub version: u64,
 }
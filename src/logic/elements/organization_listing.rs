@@ -0,0 +1,91 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::elements::organization::Organization;
+use serde::{Deserialize, Serialize};
+
+/// Criteria the listed organizations must meet, all of them optional.
+#[derive(Debug, Default)]
+pub struct OrganizationFilter {
+    /// ISO 3166-1 alpha-2 code.
+    pub country: Option<String>,
+    /// Beginning of the name, ignoring case.
+    pub name_prefix: Option<String>,
+    /// Unix timestamps, in seconds, between which the organization has been created, both inclusive.
+    pub created_after: Option<u64>,
+    pub created_before: Option<u64>,
+    /// Only these organizations, such as the ones the caller is a member of.
+    pub ids: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationSort {
+    #[default]
+    NameAscending,
+    NameDescending,
+    CreatedAscending,
+    CreatedDescending,
+}
+
+/// Position after which the next page starts, handed out to the callers as an opaque string.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct OrganizationCursor {
+    pub name: String,
+    pub id: String,
+}
+
+impl OrganizationCursor {
+    pub fn after(organization: &Organization) -> OrganizationCursor {
+        OrganizationCursor {
+            name: organization.name.clone(),
+            id: organization.id.clone(),
+        }
+    }
+
+    pub fn encode(&self) -> String {
+        serde_json::to_vec(self)
+            .unwrap_or_default()
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    pub fn decode(cursor: &str) -> Result<OrganizationCursor, Error> {
+        let invalid =
+            || Error::new(ErrorKind::InvalidArgument, "invalid cursor").with_field("cursor");
+
+        if !cursor.len().is_multiple_of(2) || !cursor.is_ascii() {
+            return Err(invalid());
+        }
+
+        let bytes = (0..cursor.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(&cursor[index..index + 2], 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| invalid())?;
+
+        serde_json::from_slice(&bytes).map_err(|_| invalid())
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct OrganizationPage {
+    pub organizations: Vec<Organization>,
+    /// Passed as `cursor` in order to read the next page, missing on the last one.
+    pub next_cursor: Option<String>,
+}
+
+#[cfg(test)]
+#[test]
+fn cursors_survive_being_handed_out() {
+    let cursor = OrganizationCursor {
+        name: "Cooperativa Ñandú".to_string(),
+        id: "65a1b2c3d4e5f60718293a4b".to_string(),
+    };
+
+    assert_eq!(
+        cursor,
+        OrganizationCursor::decode(&cursor.encode()).unwrap()
+    );
+    assert!(OrganizationCursor::decode("zz").is_err());
+    assert!(OrganizationCursor::decode("abc").is_err());
+}
@@ -1,11 +1,104 @@
 use crate::logic::elements::invitation::Invitation;
 use crate::logic::elements::organization::Organization;
 use crate::logic::elements::user::User;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize)]
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Sections of the organization root which may be requested.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrganizationRootField {
+    Organization,
+    Users,
+    Invitations,
+    Summary,
+}
+
+/// Up to `size` elements, starting right after the cursor, if any.
+#[derive(Debug)]
+pub struct RootPage {
+    pub after: Option<String>,
+    pub size: u32,
+}
+
+impl Default for RootPage {
+    fn default() -> Self {
+        RootPage {
+            after: None,
+            size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct OrganizationRootQuery {
+    pub fields: Vec<OrganizationRootField>,
+    pub users: RootPage,
+    pub invitations: RootPage,
+}
+
+impl OrganizationRootQuery {
+    pub fn includes(&self, field: OrganizationRootField) -> bool {
+        self.fields.contains(&field)
+    }
+}
+
+impl Default for OrganizationRootQuery {
+    fn default() -> Self {
+        OrganizationRootQuery {
+            fields: vec![
+                OrganizationRootField::Organization,
+                OrganizationRootField::Users,
+                OrganizationRootField::Invitations,
+                OrganizationRootField::Summary,
+            ],
+            users: RootPage::default(),
+            invitations: RootPage::default(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct OrganizationSummary {
+    pub member_count: u64,
+    /// Invitations which have not expired yet.
+    pub pending_invitation_count: u64,
+}
+
+/// Only the requested sections are present.
+#[derive(Debug, Default, Serialize)]
 pub struct OrganizationRoot {
-    pub organization: Organization,
-    pub users: Vec<User>,
-    pub invitations: Vec<Invitation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organization: Option<Organization>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<User>>,
+    /// Passed as `users_cursor` in order to read the next page of users, missing on the last one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_users_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invitations: Option<Vec<Invitation>>,
+    /// Passed as `invitations_cursor` in order to read the next page of invitations, missing on the last one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_invitations_cursor: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<OrganizationSummary>,
+}
+
+#[cfg(test)]
+#[test]
+fn serialize_only_the_requested_sections() {
+    let root = OrganizationRoot {
+        summary: Some(OrganizationSummary {
+            member_count: 3,
+            pending_invitation_count: 1,
+        }),
+        ..Default::default()
+    };
+
+    assert_eq!(
+        serde_json::json!({ "summary": { "member_count": 3, "pending_invitation_count": 1 } }),
+        serde_json::to_value(root).unwrap()
+    );
 }
@@ -5,6 +5,9 @@ use serde::Serialize;
 pub struct User {
     pub id: String,
     pub organizations: Vec<UserOrganization>,
+    pub version: u64,
+    /// Suspended users keep their memberships, but none of their permissions apply.
+    pub suspended: bool,
 }
 
 impl User {
@@ -18,6 +21,8 @@ impl User {
                     "join:organization".to_string(),
                 ],
             }],
+            version: 0,
+            suspended: false,
         }
     }
 }
@@ -0,0 +1,489 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::actions::identity_provider_logic_action::IdentityProviderLogicAction;
+use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
+use crate::logic::actions::user_storage_action::UserStorageAction;
+use crate::logic::elements::audit_record::{changes, AuditAction, AuditRecord};
+use crate::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
+use crate::logic::elements::user::User;
+use crate::logic::elements::user_organization::UserOrganization;
+use crate::logic::permission_cache::PermissionCache;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
+use crate::logic::validation::organization::{
+    get_organization_if_exists, has_organization_other_users,
+};
+use crate::logic::validation::user::{find_user, is_last_administrator};
+use cooplan_util::error_handler::ErrorHandler;
+use serde_json::json;
+
+pub async fn execute(
+    action: IdentityProviderLogicAction,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    match action {
+        IdentityProviderLogicAction::Deleted {
+            actor,
+            user_id,
+            replier,
+        } => {
+            user_deleted(
+                actor,
+                user_id,
+                replier,
+                storage_request_sender,
+                permission_cache,
+            )
+            .await
+        }
+        IdentityProviderLogicAction::Blocked {
+            actor,
+            user_id,
+            replier,
+        } => {
+            user_suspension_changed(
+                actor,
+                user_id,
+                true,
+                replier,
+                storage_request_sender,
+                permission_cache,
+            )
+            .await
+        }
+        IdentityProviderLogicAction::Unblocked {
+            actor,
+            user_id,
+            replier,
+        } => {
+            user_suspension_changed(
+                actor,
+                user_id,
+                false,
+                replier,
+                storage_request_sender,
+                permission_cache,
+            )
+            .await
+        }
+    }
+}
+
+async fn user_suspension_changed(
+    actor: String,
+    user_id: String,
+    suspended: bool,
+    replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    let user = match find_user(&user_id, storage_request_sender).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return reply(replier, Ok(())),
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let result = set_suspended(
+        &actor,
+        &user.id,
+        &user.organizations,
+        suspended,
+        storage_request_sender,
+        permission_cache,
+    )
+    .await;
+
+    reply(replier, result)
+}
+
+/// Memberships are removed unless that would leave an organization without administrators,
+/// in which case the user is suspended until another administrator is appointed.
+async fn user_deleted(
+    actor: String,
+    user_id: String,
+    replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    let user = match find_user(&user_id, storage_request_sender).await {
+        Ok(Some(user)) => user,
+        Ok(None) => return reply(replier, Ok(())),
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let mut kept_memberships = Vec::new();
+
+    for membership in &user.organizations {
+        match remove_membership(
+            &actor,
+            &user,
+            membership,
+            storage_request_sender,
+            permission_cache,
+        )
+        .await
+        {
+            Ok(true) => (),
+            Ok(false) => kept_memberships.push(membership.clone()),
+            Err(error) => return replier.handle_error(error),
+        }
+    }
+
+    if !kept_memberships.is_empty() {
+        log::warn!(
+            "suspending deleted user '{}', since it is the last administrator of an organization",
+            user_id
+        );
+
+        if let Err(error) = set_suspended(
+            &actor,
+            &user_id,
+            &kept_memberships,
+            true,
+            storage_request_sender,
+            permission_cache,
+        )
+        .await
+        {
+            return replier.handle_error(error);
+        }
+    }
+
+    reply(replier, Ok(()))
+}
+
+/// Returns whether the membership has been removed.
+/// Organizations whose only member is the user are deleted instead, so their purge
+/// removes the membership once the retention period expires.
+async fn remove_membership(
+    actor: &str,
+    user: &User,
+    membership: &UserOrganization,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<bool, Error> {
+    let organization_id = &membership.organization_id;
+
+    if !has_organization_other_users(organization_id.clone(), storage_request_sender).await? {
+        let organization =
+            match get_organization_if_exists(organization_id.clone(), storage_request_sender).await
+            {
+                Ok(organization) => organization,
+                // Already deleted, so it is awaiting its purge.
+                Err(error) if error.kind == ErrorKind::OrganizationNotFound => return Ok(true),
+                Err(error) => return Err(error),
+            };
+
+        let (replier, receiver) = tokio::sync::oneshot::channel();
+
+        let request = StorageRequest::Organization(OrganizationStorageAction::SoftDelete {
+            id: organization_id.clone(),
+            version: organization.version,
+            events: vec![DomainEvent::new(DomainEventPayload::OrganizationDeleted {
+                organization_id: organization_id.clone(),
+                actor: actor.to_string(),
+            })],
+            audit_records: vec![AuditRecord::new(
+                actor.to_string(),
+                organization_id.clone(),
+                AuditAction::Delete,
+                changes(Some(&organization), None),
+            )],
+            replier,
+        });
+
+        request_storage(request, receiver, storage_request_sender).await?;
+        permission_cache.invalidate(&user.id, organization_id);
+
+        return Ok(true);
+    }
+
+    if is_last_administrator(user, organization_id, storage_request_sender).await? {
+        return Ok(false);
+    }
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let request = StorageRequest::User(UserStorageAction::RemoveMembership {
+        user_id: user.id.clone(),
+        organization_id: organization_id.clone(),
+        events: vec![DomainEvent::new(DomainEventPayload::UserLeft {
+            organization_id: organization_id.clone(),
+            user_id: user.id.clone(),
+        })],
+        audit_records: vec![AuditRecord::new(
+            actor.to_string(),
+            organization_id.clone(),
+            AuditAction::RemoveMember,
+            changes(Some(membership), None),
+        )
+        .with_target_user_id(user.id.clone())],
+        replier,
+    });
+
+    request_storage(request, receiver, storage_request_sender).await?;
+    permission_cache.invalidate(&user.id, organization_id);
+
+    Ok(true)
+}
+
+/// Records the change within each organization of the given memberships.
+async fn set_suspended(
+    actor: &str,
+    user_id: &str,
+    memberships: &[UserOrganization],
+    suspended: bool,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    let payload = if suspended {
+        DomainEventPayload::UserSuspended {
+            user_id: user_id.to_string(),
+        }
+    } else {
+        DomainEventPayload::UserReinstated {
+            user_id: user_id.to_string(),
+        }
+    };
+
+    let audit_records = memberships
+        .iter()
+        .map(|membership| {
+            let action = if suspended {
+                AuditAction::Suspend
+            } else {
+                AuditAction::Reinstate
+            };
+
+            AuditRecord::new(
+                actor.to_string(),
+                membership.organization_id.clone(),
+                action,
+                changes(
+                    Some(&json!({ "suspended": !suspended })),
+                    Some(&json!({ "suspended": suspended })),
+                ),
+            )
+            .with_target_user_id(user_id.to_string())
+        })
+        .collect();
+
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let request = StorageRequest::User(UserStorageAction::SetSuspended {
+        user_id: user_id.to_string(),
+        suspended,
+        events: vec![DomainEvent::new(payload)],
+        audit_records,
+        replier,
+    });
+
+    request_storage(request, receiver, storage_request_sender).await?;
+    permission_cache.invalidate_user(user_id);
+
+    Ok(())
+}
+
+async fn request_storage<T>(
+    request: StorageRequest,
+    receiver: tokio::sync::oneshot::Receiver<Result<T, Error>>,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<T, Error> {
+    match storage_request_sender.send(request).await {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    match receiver.await {
+        Ok(result) => result,
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
+}
+
+fn reply(
+    replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
+    result: Result<(), Error>,
+) -> Result<(), Error> {
+    if let Err(error) = result {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn suspend_last_administrator_instead_of_removing_it() {
+    use crate::logic::organization_permission::OrganizationPermission;
+
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    let storage = tokio::spawn(async move {
+        let mut suspended = false;
+        let mut recorded = false;
+
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            let request = traced.request;
+
+            match request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: ORGANIZATION_ID.to_string(),
+                            permissions: vec![OrganizationPermission::UpdateUser.to_string()],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::CountByOrganizationId {
+                    replier, ..
+                }) => {
+                    replier.send(Ok(2)).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::CountAdministratorsByOrganizationId {
+                    replier,
+                    ..
+                }) => {
+                    replier.send(Ok(1)).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::SetSuspended {
+                    suspended: true,
+                    audit_records,
+                    replier,
+                    ..
+                }) => {
+                    suspended = true;
+
+                    // Stored within the same transaction as the suspension.
+                    for record in audit_records {
+                        assert_eq!("suspend", record.action);
+                        assert_eq!(Some(USER_ID.to_string()), record.target_user_id);
+                        recorded = true;
+                    }
+
+                    replier.send(Ok(())).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+
+        suspended && recorded
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    user_deleted(
+        "IDENTITY_PROVIDER".to_string(),
+        USER_ID.to_string(),
+        replier,
+        &storage_request_sender,
+        &PermissionCache::new(std::time::Duration::ZERO),
+    )
+    .await
+    .unwrap();
+
+    assert!(listener.await.unwrap().is_ok());
+
+    drop(storage_request_sender);
+    assert!(storage.await.unwrap());
+}
+
+#[tokio::test]
+async fn publish_that_a_deleted_user_left_its_organizations() {
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    let storage = tokio::spawn(async move {
+        let mut left = false;
+
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            match traced.request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: ORGANIZATION_ID.to_string(),
+                            permissions: Vec::new(),
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::CountByOrganizationId {
+                    replier, ..
+                }) => {
+                    replier.send(Ok(2)).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::RemoveMembership {
+                    events,
+                    replier,
+                    ..
+                }) => {
+                    for event in events {
+                        assert!(matches!(
+                            event.payload,
+                            DomainEventPayload::UserLeft { organization_id, user_id }
+                                if organization_id == ORGANIZATION_ID && user_id == USER_ID
+                        ));
+                        left = true;
+                    }
+
+                    replier.send(Ok(())).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+
+        left
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    user_deleted(
+        "IDENTITY_PROVIDER".to_string(),
+        USER_ID.to_string(),
+        replier,
+        &storage_request_sender,
+        &PermissionCache::new(std::time::Duration::ZERO),
+    )
+    .await
+    .unwrap();
+
+    assert!(listener.await.unwrap().is_ok());
+
+    drop(storage_request_sender);
+    assert!(storage.await.unwrap());
+}
@@ -1,3 +1,4 @@
+pub mod identity_provider;
 pub mod organization;
 pub mod organization_root;
 pub mod user;
@@ -1,22 +1,39 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic;
+use crate::logic::actions::audit_storage_action::AuditStorageAction;
+use crate::logic::actions::invitation_code_storage_action::InvitationStorageAction;
 use crate::logic::actions::organization_logic_action::OrganizationLogicAction;
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
 use crate::logic::actions::user_storage_action::UserStorageAction;
+use crate::logic::caller::{record_service_access, Caller};
+use crate::logic::elements::audit_record::{changes, AuditAction, AuditRecord};
+use crate::logic::elements::batch_read::{entries, unique_ids, BatchEntry};
+use crate::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
 use crate::logic::elements::organization::Organization;
+use crate::logic::elements::organization_listing::{
+    OrganizationCursor, OrganizationFilter, OrganizationPage, OrganizationSort,
+};
 use crate::logic::elements::user_organization::UserOrganization;
-use crate::logic::storage_request::StorageRequest;
+use crate::logic::organization_permission::OrganizationPermission;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
 use crate::logic::validation::country::is_country_code_valid;
 use crate::logic::validation::invitation::get_code_if_valid;
 use crate::logic::validation::name::is_name_already_used;
-use crate::logic::validation::telephone::{is_telephone_being_used, is_telephone_valid};
-use crate::logic::validation::user::has_user_no_organization;
-use async_channel::{Receiver, Sender};
+use crate::logic::validation::organization::{
+    get_organization_if_exists, has_organization_other_users,
+};
+use crate::logic::validation::telephone::{
+    is_telephone_being_used, is_telephone_valid, normalize_telephone,
+};
+use crate::logic::validation::user::{find_user, has_user_no_organization, has_user_permission};
 use cooplan_util::error_handler::ErrorHandler;
 
+const MAX_AUDIT_PAGE_SIZE: u64 = 100;
+const MAX_LIST_PAGE_SIZE: u32 = 100;
+
 pub async fn execute(
     action: OrganizationLogicAction,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<(), Error> {
     match action {
         OrganizationLogicAction::Create {
@@ -27,46 +44,140 @@ pub async fn execute(
             telephone,
             replier,
         } => {
-            create(
-                user_id,
+            let organization = Organization {
+                id: String::new(),
+                name,
+                country,
+                address,
+                telephone,
+                permissions: Vec::new(),
+                version: 0,
+            };
+
+            create(user_id, organization, storage_request_sender, replier).await
+        }
+        OrganizationLogicAction::Update {
+            user_id,
+            organization_id,
+            version,
+            name,
+            country,
+            address,
+            telephone,
+            replier,
+        } => {
+            let organization = Organization {
+                id: organization_id,
                 name,
                 country,
                 address,
                 telephone,
+                permissions: Vec::new(),
+                version,
+            };
+
+            update(user_id, organization, storage_request_sender, replier).await
+        }
+        OrganizationLogicAction::Delete {
+            user_id,
+            organization_id,
+            version,
+            replier,
+        } => {
+            delete(
+                user_id,
+                organization_id,
+                version,
                 storage_request_sender,
                 replier,
             )
             .await
         }
+        OrganizationLogicAction::Restore {
+            user_id,
+            organization_id,
+            replier,
+        } => restore(user_id, organization_id, storage_request_sender, replier).await,
         OrganizationLogicAction::Join {
             user_id,
             invitation_code,
             replier,
         } => join(user_id, invitation_code, storage_request_sender, replier).await,
+        OrganizationLogicAction::Audit {
+            caller,
+            organization_id,
+            page,
+            page_size,
+            replier,
+        } => {
+            read_audit(
+                caller,
+                organization_id,
+                page,
+                page_size,
+                storage_request_sender,
+                replier,
+            )
+            .await
+        }
+        OrganizationLogicAction::ReadMany {
+            caller,
+            ids,
+            replier,
+        } => read_many(caller, ids, storage_request_sender, replier).await,
+        OrganizationLogicAction::List {
+            caller,
+            filter,
+            sort,
+            cursor,
+            page_size,
+            replier,
+        } => {
+            list(
+                caller,
+                filter,
+                sort,
+                cursor,
+                page_size,
+                storage_request_sender,
+                replier,
+            )
+            .await
+        }
     }
 }
 
+/// The identifier and version of the passed organization are assigned by the storage.
 async fn create(
     user_id: String,
-    name: String,
-    country: String,
-    address: String,
-    telephone: String,
-    storage_request_sender: &Sender<StorageRequest>,
+    organization: Organization,
+    storage_request_sender: &StorageRequestSender,
     replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
 ) -> Result<(), Error> {
+    let Organization {
+        name,
+        country,
+        address,
+        telephone,
+        ..
+    } = organization;
+
     if !is_country_code_valid(&country) {
-        let error = Error::new(ErrorKind::InvalidCountry, "invalid country code detected");
+        let error = Error::new(ErrorKind::InvalidCountry, "invalid country code detected")
+            .with_field("country");
 
         return replier.handle_error(error);
     }
 
     if !is_telephone_valid(&telephone) {
-        let error = Error::new(ErrorKind::InvalidTelephone, "invalid telephone detected");
+        let error = Error::new(ErrorKind::InvalidTelephone, "invalid telephone detected")
+            .with_field("telephone");
 
         return replier.handle_error(error);
     }
 
+    let telephone = normalize_telephone(&telephone).unwrap_or(telephone);
+
     match has_user_no_organization(&user_id, storage_request_sender).await {
         Ok(can_create) => {
             if !can_create {
@@ -84,7 +195,8 @@ async fn create(
     match is_name_already_used(&name, storage_request_sender).await {
         Ok(is_used) => {
             if is_used {
-                let error = Error::new(ErrorKind::NameAlreadyTaken, "name is already being used");
+                let error = Error::new(ErrorKind::NameAlreadyTaken, "name is already being used")
+                    .with_field("name");
 
                 return replier.handle_error(error);
             }
@@ -98,7 +210,8 @@ async fn create(
                 let error = Error::new(
                     ErrorKind::TelephoneAlreadyInUse,
                     "telephone is already being used",
-                );
+                )
+                .with_field("telephone");
 
                 return replier.handle_error(error);
             }
@@ -154,11 +267,36 @@ async fn create(
         permissions: logic::organization_permission::organization_creator_permissions(),
     };
 
+    let events = vec![
+        DomainEvent::new(DomainEventPayload::OrganizationCreated {
+            organization_id: organization.id.clone(),
+            name: organization.name.clone(),
+            country: organization.country.clone(),
+            address: organization.address.clone(),
+            telephone: organization.telephone.clone(),
+            actor: user_id.clone(),
+        }),
+        DomainEvent::new(DomainEventPayload::UserJoined {
+            organization_id: organization.id.clone(),
+            user_id: user_id.clone(),
+            permissions: user_organization.permissions.clone(),
+        }),
+    ];
+
     let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
 
+    let audit_records = vec![AuditRecord::new(
+        user_id.clone(),
+        organization.id.clone(),
+        AuditAction::Create,
+        changes(None, Some(&organization)),
+    )];
+
     let create_user_request = StorageRequest::User(UserStorageAction::Create {
-        id: user_id,
+        id: user_id.clone(),
         organization: user_organization,
+        events,
+        audit_records,
         replier: storage_replier,
     });
 
@@ -170,19 +308,21 @@ async fn create(
                 format!("failed to send storage request: {}", error),
             );
 
-            restore_create_organization(organization.id, storage_request_sender).await;
+            let compensation =
+                restore_create_organization(organization.id, storage_request_sender).await;
 
-            return replier.handle_error(error);
+            return replier.handle_error(with_compensation(error, compensation));
         }
     }
 
-    let user = match storage_listener.await {
+    match storage_listener.await {
         Ok(result) => match result {
-            Ok(user) => user,
+            Ok(_) => (),
             Err(error) => {
-                restore_create_organization(organization.id, storage_request_sender).await;
+                let compensation =
+                    restore_create_organization(organization.id, storage_request_sender).await;
 
-                return replier.handle_error(error);
+                return replier.handle_error(with_compensation(error, compensation));
             }
         },
         Err(error) => {
@@ -194,9 +334,10 @@ async fn create(
                 ),
             );
 
-            restore_create_organization(organization.id, storage_request_sender).await;
+            let compensation =
+                restore_create_organization(organization.id, storage_request_sender).await;
 
-            return replier.handle_error(error);
+            return replier.handle_error(with_compensation(error, compensation));
         }
     };
 
@@ -215,9 +356,21 @@ async fn create(
     Ok(())
 }
 
+/// Appends the failure of the compensation, if any, to the error which required it.
+fn with_compensation(mut error: Error, compensation: Result<(), Error>) -> Error {
+    if let Err(compensation_error) = compensation {
+        error.message = format!(
+            "{}; failed to undo the creation of the organization: {}",
+            error.message, compensation_error
+        );
+    }
+
+    error
+}
+
 async fn restore_create_organization(
     organization_id: String,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<(), Error> {
     let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
 
@@ -265,18 +418,25 @@ async fn restore_create_organization(
     }
 }
 
-async fn join(
+async fn update(
     user_id: String,
-    invitation_code: String,
-    storage_request_sender: &Sender<StorageRequest>,
-    mut replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
+    mut organization: Organization,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
 ) -> Result<(), Error> {
-    match has_user_no_organization(&user_id, storage_request_sender).await {
-        Ok(can_join) => {
-            if !can_join {
+    match has_user_permission(
+        &user_id,
+        &organization.id,
+        OrganizationPermission::UpdateOrganization,
+        storage_request_sender,
+    )
+    .await
+    {
+        Ok(can_update) => {
+            if !can_update {
                 let error = Error::new(
-                    ErrorKind::UserCannotJoinAnyOrganization,
-                    "user cannot join any organization",
+                    ErrorKind::MissingPermission,
+                    "user cannot update the organization",
                 );
 
                 return replier.handle_error(error);
@@ -285,69 +445,95 @@ async fn join(
         Err(error) => return replier.handle_error(error),
     }
 
-    let invitation = match get_code_if_valid(invitation_code, storage_request_sender).await {
-        Ok(invitation) => invitation,
-        Err(error) => return replier.handle_error(error),
-    };
+    if !is_country_code_valid(&organization.country) {
+        let error = Error::new(ErrorKind::InvalidCountry, "invalid country code detected")
+            .with_field("country");
 
-    let organization = match get_organization_if_exists(
-        invitation.organization_id,
-        storage_request_sender,
-    )
-    .await
-    {
-        Ok(organization) => organization,
-        Err(error) => return replier.handle_error(error),
-    };
+        return replier.handle_error(error);
+    }
 
-    replier = create_user(
-        user_id,
-        organization.id.clone(),
-        invitation.permissions,
-        replier,
-        storage_request_sender,
-    )
-    .await?;
+    if !is_telephone_valid(&organization.telephone) {
+        let error = Error::new(ErrorKind::InvalidTelephone, "invalid telephone detected")
+            .with_field("telephone");
 
-    // An error occurring during the deletion of the invitation is not critical,
-    // since the invitation code will eventually expire.
-    delete_invitation(invitation.code, storage_request_sender).await;
+        return replier.handle_error(error);
+    }
 
-    match replier.send(Ok(organization)) {
-        Ok(_) => (),
-        Err(_) => {
-            log::error!("failed to send response to api");
+    organization.telephone =
+        normalize_telephone(&organization.telephone).unwrap_or(organization.telephone);
 
-            return Err(Error::new(
-                ErrorKind::InternalFailure,
-                "failed to send response to api",
-            ));
+    let current_organization =
+        match get_organization_if_exists(organization.id.clone(), storage_request_sender).await {
+            Ok(current_organization) => current_organization,
+            Err(error) => return replier.handle_error(error),
+        };
+
+    if current_organization.name != organization.name {
+        match is_name_already_used(&organization.name, storage_request_sender).await {
+            Ok(is_used) => {
+                if is_used {
+                    let error =
+                        Error::new(ErrorKind::NameAlreadyTaken, "name is already being used")
+                            .with_field("name");
+
+                    return replier.handle_error(error);
+                }
+            }
+            Err(error) => return replier.handle_error(error),
         }
     }
 
-    Ok(())
-}
+    if current_organization.telephone != organization.telephone {
+        match is_telephone_being_used(&organization.telephone, storage_request_sender).await {
+            Ok(is_used) => {
+                if is_used {
+                    let error = Error::new(
+                        ErrorKind::TelephoneAlreadyInUse,
+                        "telephone is already being used",
+                    );
+
+                    return replier.handle_error(error);
+                }
+            }
+            Err(error) => return replier.handle_error(error),
+        }
+    }
 
-async fn create_user(
-    user_id: String,
-    organization_id: String,
-    permissions: Vec<String>,
-    replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
-    storage_request_sender: &Sender<StorageRequest>,
-) -> Result<tokio::sync::oneshot::Sender<Result<Organization, Error>>, Error> {
-    let user_organization = UserOrganization {
-        organization_id,
-        permissions,
+    let events = vec![DomainEvent::new(DomainEventPayload::OrganizationUpdated {
+        organization_id: organization.id.clone(),
+        name: organization.name.clone(),
+        country: organization.country.clone(),
+        address: organization.address.clone(),
+        telephone: organization.telephone.clone(),
+        version: organization.version + 1,
+        actor: user_id.clone(),
+    })];
+
+    let updated_organization = Organization {
+        version: organization.version + 1,
+        ..organization.clone()
     };
 
+    let audit_records = vec![AuditRecord::new(
+        user_id.clone(),
+        organization.id.clone(),
+        AuditAction::Update,
+        changes(Some(&current_organization), Some(&updated_organization)),
+    )];
+
     let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
-    let create_user_request = StorageRequest::User(UserStorageAction::Create {
-        id: user_id,
-        organization: user_organization,
-        replier: storage_replier,
-    });
 
-    match storage_request_sender.send(create_user_request).await {
+    match storage_request_sender
+        .send(StorageRequest::Organization(
+            OrganizationStorageAction::Update {
+                organization,
+                events,
+                audit_records,
+                replier: storage_replier,
+            },
+        ))
+        .await
+    {
         Ok(_) => (),
         Err(error) => {
             let error = Error::new(
@@ -359,9 +545,9 @@ async fn create_user(
         }
     }
 
-    let user = match storage_listener.await {
+    let organization = match storage_listener.await {
         Ok(result) => match result {
-            Ok(user) => user,
+            Ok(organization) => organization,
             Err(error) => return replier.handle_error(error),
         },
         Err(error) => {
@@ -377,78 +563,821 @@ async fn create_user(
         }
     };
 
-    Ok(replier)
+    match replier.send(Ok(organization)) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
 }
 
-async fn delete_invitation(
-    invitation_code: String,
-    storage_request_sender: &Sender<StorageRequest>,
+async fn delete(
+    user_id: String,
+    organization_id: String,
+    version: u64,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<(), Error>>,
 ) -> Result<(), Error> {
+    match has_user_permission(
+        &user_id,
+        &organization_id,
+        OrganizationPermission::DeleteOrganization,
+        storage_request_sender,
+    )
+    .await
+    {
+        Ok(can_delete) => {
+            if !can_delete {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot delete the organization",
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+        Err(error) => return replier.handle_error(error),
+    }
+
+    match has_organization_other_users(organization_id.clone(), storage_request_sender).await {
+        Ok(has_other_users) => {
+            if has_other_users {
+                let error = Error::new(
+                    ErrorKind::OrganizationHasOtherUsers,
+                    "organization cannot be deleted while it has other users",
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+        Err(error) => return replier.handle_error(error),
+    }
+
+    let current_organization =
+        match get_organization_if_exists(organization_id.clone(), storage_request_sender).await {
+            Ok(current_organization) => current_organization,
+            Err(error) => return replier.handle_error(error),
+        };
+
     let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
-    let delete_invitation_request = StorageRequest::Invitation(InvitationStorageAction::Delete {
-        code: invitation_code,
-        replier: storage_replier,
-    });
 
-    match storage_request_sender.send(delete_invitation_request).await {
+    match storage_request_sender
+        .send(StorageRequest::Organization(
+            OrganizationStorageAction::SoftDelete {
+                id: organization_id.clone(),
+                version,
+                events: vec![DomainEvent::new(DomainEventPayload::OrganizationDeleted {
+                    organization_id: organization_id.clone(),
+                    actor: user_id.clone(),
+                })],
+                audit_records: vec![AuditRecord::new(
+                    user_id.clone(),
+                    organization_id.clone(),
+                    AuditAction::Delete,
+                    changes(Some(&current_organization), None),
+                )],
+                replier: storage_replier,
+            },
+        ))
+        .await
+    {
         Ok(_) => (),
         Err(error) => {
             let error = Error::new(
                 ErrorKind::InternalFailure,
-                format!(
-                    "failed to send storage request for deleting the invitation: {}",
-                    error
-                ),
+                format!("failed to send storage request: {}", error),
             );
 
-            log::error!("{}", error);
-
-            return Err(error);
+            return replier.handle_error(error);
         }
     }
 
-    match storage_listener.await {
-        Ok(result) => match result {
-            Ok(_) => (),
-            Err(error) => {
-                let error = Error::new(
-                    ErrorKind::StorageFailure,
-                    format!("failed to delete invitation: {}", error),
-                );
-
-                log::error!("{}", error);
-
-                return Err(error);
-            }
-        },
+    let result = match storage_listener.await {
+        Ok(result) => result,
         Err(error) => {
             let error = Error::new(
                 ErrorKind::InternalFailure,
                 format!(
-                    "failed to receive response for deleting the invitation: {}",
+                    "failed to receive response for a storage request: {}",
                     error
                 ),
             );
 
-            log::error!("{}", error);
+            return replier.handle_error(error);
+        }
+    };
 
-            return Err(error);
+    match replier.send(result) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
         }
     }
 
     Ok(())
 }
 
-use crate::logic::actions::invitation_code_storage_action::InvitationStorageAction;
-use crate::logic::validation::organization::get_organization_if_exists;
-#[cfg(test)]
-use phonenumber::country::RO;
-
-#[test]
-fn phone_validation_works_on_phone_numbers_with_prefix() {
-    let valid_phone_with_prefix = "+40753313640";
+/// Deleted organizations keep their memberships until they are purged,
+/// so the permission of the user who deleted it still applies.
+async fn restore(
+    user_id: String,
+    organization_id: String,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
+) -> Result<(), Error> {
+    match has_user_permission(
+        &user_id,
+        &organization_id,
+        OrganizationPermission::DeleteOrganization,
+        storage_request_sender,
+    )
+    .await
+    {
+        Ok(can_restore) => {
+            if !can_restore {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot restore the organization",
+                );
 
-    match phonenumber::parse(None, valid_phone_with_prefix) {
+                return replier.handle_error(error);
+            }
+        }
+        Err(error) => return replier.handle_error(error),
+    }
+
+    let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender
+        .send(StorageRequest::Organization(
+            OrganizationStorageAction::Restore {
+                id: organization_id.clone(),
+                events: vec![DomainEvent::new(DomainEventPayload::OrganizationRestored {
+                    organization_id: organization_id.clone(),
+                    actor: user_id.clone(),
+                })],
+                audit_records: vec![AuditRecord::new(
+                    user_id.clone(),
+                    organization_id.clone(),
+                    AuditAction::Restore,
+                    Vec::new(),
+                )],
+                replier: storage_replier,
+            },
+        ))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    let result = match storage_listener.await {
+        Ok(result) => result,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to receive response for a storage request: {}",
+                    error
+                ),
+            );
+
+            return replier.handle_error(error);
+        }
+    };
+
+    match replier.send(result) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn join(
+    user_id: String,
+    invitation_code: String,
+    storage_request_sender: &StorageRequestSender,
+    mut replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
+) -> Result<(), Error> {
+    match has_user_no_organization(&user_id, storage_request_sender).await {
+        Ok(can_join) => {
+            if !can_join {
+                let error = Error::new(
+                    ErrorKind::UserCannotJoinAnyOrganization,
+                    "user cannot join any organization",
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+        Err(error) => return replier.handle_error(error),
+    }
+
+    let invitation = match get_code_if_valid(invitation_code, storage_request_sender).await {
+        Ok(invitation) => invitation,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let organization = match get_organization_if_exists(
+        invitation.organization_id,
+        storage_request_sender,
+    )
+    .await
+    {
+        Ok(organization) => organization,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let user_organization = UserOrganization {
+        organization_id: organization.id.clone(),
+        permissions: invitation.permissions,
+    };
+
+    let events = vec![
+        DomainEvent::new(DomainEventPayload::InvitationConsumed {
+            organization_id: organization.id.clone(),
+            user_id: user_id.clone(),
+        }),
+        DomainEvent::new(DomainEventPayload::UserJoined {
+            organization_id: organization.id.clone(),
+            user_id: user_id.clone(),
+            permissions: user_organization.permissions.clone(),
+        }),
+    ];
+
+    let audit_records = vec![AuditRecord::new(
+        user_id.clone(),
+        organization.id.clone(),
+        AuditAction::Join,
+        changes(None, Some(&user_organization)),
+    )
+    .with_target_user_id(user_id.clone())];
+
+    replier = create_user(
+        user_id.clone(),
+        user_organization.organization_id.clone(),
+        user_organization.permissions.clone(),
+        events,
+        audit_records,
+        replier,
+        storage_request_sender,
+    )
+    .await?;
+
+    // An error occurring during the deletion of the invitation is not critical,
+    // since the invitation code will eventually expire.
+    delete_invitation(invitation.code, storage_request_sender).await;
+
+    match replier.send(Ok(organization)) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn create_user(
+    user_id: String,
+    organization_id: String,
+    permissions: Vec<String>,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: tokio::sync::oneshot::Sender<Result<Organization, Error>>,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<tokio::sync::oneshot::Sender<Result<Organization, Error>>, Error> {
+    let user_organization = UserOrganization {
+        organization_id,
+        permissions,
+    };
+
+    let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+    let create_user_request = StorageRequest::User(UserStorageAction::Create {
+        id: user_id,
+        organization: user_organization,
+        events,
+        audit_records,
+        replier: storage_replier,
+    });
+
+    match storage_request_sender.send(create_user_request).await {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    match storage_listener.await {
+        Ok(result) => match result {
+            Ok(_) => (),
+            Err(error) => return replier.handle_error(error),
+        },
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to receive response for a storage request: {}",
+                    error
+                ),
+            );
+
+            return replier.handle_error(error);
+        }
+    };
+
+    Ok(replier)
+}
+
+async fn delete_invitation(
+    invitation_code: String,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<(), Error> {
+    let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+    let delete_invitation_request = StorageRequest::Invitation(InvitationStorageAction::Delete {
+        code: invitation_code,
+        replier: storage_replier,
+    });
+
+    match storage_request_sender.send(delete_invitation_request).await {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to send storage request for deleting the invitation: {}",
+                    error
+                ),
+            );
+
+            log::error!("{}", error);
+
+            return Err(error);
+        }
+    }
+
+    match storage_listener.await {
+        Ok(result) => match result {
+            Ok(_) => (),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to delete invitation: {}", error),
+                );
+
+                log::error!("{}", error);
+
+                return Err(error);
+            }
+        },
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to receive response for deleting the invitation: {}",
+                    error
+                ),
+            );
+
+            log::error!("{}", error);
+
+            return Err(error);
+        }
+    }
+
+    Ok(())
+}
+
+async fn read_audit(
+    caller: Caller,
+    organization_id: String,
+    page: u64,
+    page_size: u64,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<Vec<AuditRecord>, Error>>,
+) -> Result<(), Error> {
+    if page_size == 0 || page_size > MAX_AUDIT_PAGE_SIZE {
+        let error = Error::new(
+            ErrorKind::InvalidArgument,
+            format!("page size must be between 1 and {}", MAX_AUDIT_PAGE_SIZE),
+        )
+        .with_field("page_size");
+
+        return replier.handle_error(error);
+    }
+
+    match &caller {
+        Caller::User(user_id) => match has_user_permission(
+            user_id,
+            &organization_id,
+            OrganizationPermission::ReadOrganization,
+            storage_request_sender,
+        )
+        .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot read the organization",
+                );
+
+                return replier.handle_error(error);
+            }
+            Err(error) => return replier.handle_error(error),
+        },
+        // The scope grants the access regardless of memberships.
+        Caller::Service { .. } => {
+            if let Err(error) = record_service_access(
+                &caller,
+                &organization_id,
+                None,
+                AuditAction::ServiceReadAudit,
+                storage_request_sender,
+            )
+            .await
+            {
+                return replier.handle_error(error);
+            }
+        }
+    }
+
+    let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender
+        .send(StorageRequest::Audit(
+            AuditStorageAction::FindByOrganizationId {
+                organization_id,
+                page,
+                page_size,
+                replier: storage_replier,
+            },
+        ))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    let result = match storage_listener.await {
+        Ok(result) => result,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to receive response for a storage request: {}",
+                    error
+                ),
+            );
+
+            return replier.handle_error(error);
+        }
+    };
+
+    match replier.send(result) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn read_many(
+    caller: Caller,
+    ids: Vec<String>,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<Vec<BatchEntry<Organization>>, Error>>,
+) -> Result<(), Error> {
+    let ids = match unique_ids(ids, "ids") {
+        Ok(ids) => ids,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    // Users only read the organizations they may read, the others being answered as `missing_permission`.
+    let readable_ids: Option<Vec<String>> = match &caller {
+        Caller::User(user_id) => match find_user(user_id, storage_request_sender).await {
+            Ok(Some(user)) if !user.suspended => {
+                let permission = OrganizationPermission::ReadOrganization.to_string();
+
+                Some(
+                    user.organizations
+                        .into_iter()
+                        .filter(|organization| organization.permissions.contains(&permission))
+                        .map(|organization| organization.organization_id)
+                        .collect(),
+                )
+            }
+            Ok(_) => Some(Vec::new()),
+            Err(error) => return replier.handle_error(error),
+        },
+        // Recorded as if each organization was read on its own.
+        Caller::Service { .. } => {
+            for id in &ids {
+                if let Err(error) = record_service_access(
+                    &caller,
+                    id,
+                    None,
+                    AuditAction::ServiceRead,
+                    storage_request_sender,
+                )
+                .await
+                {
+                    return replier.handle_error(error);
+                }
+            }
+
+            None
+        }
+    };
+
+    let is_readable = |id: &String| {
+        readable_ids
+            .as_ref()
+            .is_none_or(|readable_ids| readable_ids.contains(id))
+    };
+
+    let requested_ids: Vec<String> = ids.iter().filter(|id| is_readable(id)).cloned().collect();
+
+    let organizations = if requested_ids.is_empty() {
+        Vec::new()
+    } else {
+        match find_by_ids(requested_ids, storage_request_sender).await {
+            Ok(organizations) => organizations,
+            Err(error) => return replier.handle_error(error),
+        }
+    };
+
+    let mut entries = entries(
+        ids,
+        organizations,
+        |organization| organization.id.as_str(),
+        ErrorKind::OrganizationNotFound,
+    );
+
+    for entry in entries.iter_mut().filter(|entry| !is_readable(&entry.id)) {
+        entry.error = Some(ErrorKind::MissingPermission.code());
+    }
+
+    match replier.send(Ok(entries)) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_by_ids(
+    ids: Vec<String>,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Vec<Organization>, Error> {
+    let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender
+        .send(StorageRequest::Organization(
+            OrganizationStorageAction::FindByIds {
+                ids,
+                replier: storage_replier,
+            },
+        ))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    match storage_listener.await {
+        Ok(result) => result,
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
+}
+
+async fn list(
+    caller: Caller,
+    mut filter: OrganizationFilter,
+    sort: OrganizationSort,
+    cursor: Option<String>,
+    page_size: u32,
+    storage_request_sender: &StorageRequestSender,
+    replier: tokio::sync::oneshot::Sender<Result<OrganizationPage, Error>>,
+) -> Result<(), Error> {
+    if page_size == 0 || page_size > MAX_LIST_PAGE_SIZE {
+        let error = Error::new(
+            ErrorKind::InvalidArgument,
+            format!("page size must be between 1 and {}", MAX_LIST_PAGE_SIZE),
+        )
+        .with_field("page_size");
+
+        return replier.handle_error(error);
+    }
+
+    if let Some(country) = &filter.country {
+        if !is_country_code_valid(country) {
+            let error = Error::new(ErrorKind::InvalidArgument, "invalid country code")
+                .with_field("country");
+
+            return replier.handle_error(error);
+        }
+    }
+
+    let after = match cursor {
+        Some(cursor) => match OrganizationCursor::decode(&cursor) {
+            Ok(after) => Some(after),
+            Err(error) => return replier.handle_error(error),
+        },
+        None => None,
+    };
+
+    match &caller {
+        Caller::User(user_id) => {
+            let memberships: Vec<String> = match find_user(user_id, storage_request_sender).await {
+                Ok(Some(user)) if !user.suspended => user
+                    .organizations
+                    .into_iter()
+                    .map(|organization| organization.organization_id)
+                    .filter(|organization_id| !organization_id.is_empty())
+                    .collect(),
+                Ok(_) => Vec::new(),
+                Err(error) => return replier.handle_error(error),
+            };
+
+            filter.ids = Some(match filter.ids.take() {
+                Some(ids) => ids
+                    .into_iter()
+                    .filter(|id| memberships.contains(id))
+                    .collect(),
+                None => memberships,
+            });
+        }
+        // The scope grants the access regardless of memberships.
+        Caller::Service { .. } => {
+            if let Err(error) = record_service_access(
+                &caller,
+                "",
+                None,
+                AuditAction::ServiceList,
+                storage_request_sender,
+            )
+            .await
+            {
+                return replier.handle_error(error);
+            }
+        }
+    }
+
+    let mut organizations = if filter.ids.as_ref().is_some_and(|ids| ids.is_empty()) {
+        Vec::new()
+    } else {
+        let (storage_replier, storage_listener) = tokio::sync::oneshot::channel();
+
+        // One more than requested tells whether another page follows.
+        match storage_request_sender
+            .send(StorageRequest::Organization(
+                OrganizationStorageAction::List {
+                    filter,
+                    sort,
+                    after,
+                    limit: page_size + 1,
+                    replier: storage_replier,
+                },
+            ))
+            .await
+        {
+            Ok(_) => (),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::InternalFailure,
+                    format!("failed to send storage request: {}", error),
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+
+        match storage_listener.await {
+            Ok(Ok(organizations)) => organizations,
+            Ok(Err(error)) => return replier.handle_error(error),
+            Err(error) => {
+                let error = Error::new(
+                    ErrorKind::InternalFailure,
+                    format!(
+                        "failed to receive response for a storage request: {}",
+                        error
+                    ),
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+    };
+
+    let next_cursor = if organizations.len() > page_size as usize {
+        organizations.truncate(page_size as usize);
+        organizations
+            .last()
+            .map(|organization| OrganizationCursor::after(organization).encode())
+    } else {
+        None
+    };
+
+    let page = OrganizationPage {
+        organizations,
+        next_cursor,
+    };
+
+    match replier.send(Ok(page)) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to api");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to api",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+use async_channel::Receiver;
+#[cfg(test)]
+use phonenumber::country::RO;
+
+#[test]
+fn phone_validation_works_on_phone_numbers_with_prefix() {
+    let valid_phone_with_prefix = "+40753313640";
+
+    match phonenumber::parse(None, valid_phone_with_prefix) {
         Ok(phone_number) => {
             assert_eq!(
                 phonenumber::country::RO,
@@ -473,13 +1402,17 @@ fn phone_validation_works_on_phone_numbers_with_prefix() {
     }
 }
 
+#[cfg(test)]
 async fn setup() -> (
     String,
     String,
     String,
     String,
     String,
-    (Sender<StorageRequest>, Receiver<StorageRequest>),
+    (
+        StorageRequestSender,
+        Receiver<crate::telemetry::traced_channel::Traced<StorageRequest>>,
+    ),
 ) {
     (
         "USER_ID".to_string(),
@@ -487,7 +1420,7 @@ async fn setup() -> (
         "RO".to_string(),
         "ADDRESS".to_string(),
         "+40753313640".to_string(),
-        async_channel::bounded(100),
+        crate::telemetry::traced_channel::bounded(100),
     )
 }
 
@@ -499,16 +1432,17 @@ async fn detect_invalid_country() {
 
     let (replier, listener) = tokio::sync::oneshot::channel();
 
-    let function_result = create(
-        user_id,
+    let organization = Organization {
+        id: String::new(),
         name,
-        INVALID_COUNTRY.to_string(),
+        country: INVALID_COUNTRY.to_string(),
         address,
-        phone,
-        &storage_request_sender,
-        replier,
-    )
-    .await;
+        telephone: phone,
+        permissions: Vec::new(),
+        version: 0,
+    };
+
+    let function_result = create(user_id, organization, &storage_request_sender, replier).await;
 
     assert!(function_result.is_err());
     assert_eq!(ErrorKind::InvalidCountry, function_result.unwrap_err().kind);
@@ -527,16 +1461,17 @@ async fn detect_invalid_phone() {
 
     let (replier, listener) = tokio::sync::oneshot::channel();
 
-    let function_result = create(
-        user_id,
+    let organization = Organization {
+        id: String::new(),
         name,
         country,
         address,
-        INVALID_PHONE.to_string(),
-        &storage_request_sender,
-        replier,
-    )
-    .await;
+        telephone: INVALID_PHONE.to_string(),
+        permissions: Vec::new(),
+        version: 0,
+    };
+
+    let function_result = create(user_id, organization, &storage_request_sender, replier).await;
 
     assert!(function_result.is_err());
     assert_eq!(
@@ -549,3 +1484,151 @@ async fn detect_invalid_phone() {
     assert!(result.is_err());
     assert_eq!(ErrorKind::InvalidTelephone, result.unwrap_err().kind);
 }
+
+#[tokio::test]
+async fn detect_organization_with_other_users_on_delete() {
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (user_id, _, _, _, _, (storage_request_sender, storage_request_receiver)) = setup().await;
+
+    tokio::spawn(async move {
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            let request = traced.request;
+
+            match request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = crate::logic::elements::user::User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: ORGANIZATION_ID.to_string(),
+                            permissions: vec![
+                                OrganizationPermission::DeleteOrganization.to_string()
+                            ],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::CountByOrganizationId {
+                    replier, ..
+                }) => {
+                    replier.send(Ok(2)).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let function_result = delete(
+        user_id,
+        ORGANIZATION_ID.to_string(),
+        0,
+        &storage_request_sender,
+        replier,
+    )
+    .await;
+
+    assert!(function_result.is_err());
+    assert_eq!(
+        ErrorKind::OrganizationHasOtherUsers,
+        listener.await.unwrap().unwrap_err().kind
+    );
+}
+
+#[tokio::test]
+async fn detect_invalid_audit_page_size() {
+    let (user_id, _, _, _, _, (storage_request_sender, _)) = setup().await;
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let function_result = read_audit(
+        Caller::User(user_id),
+        "ORGANIZATION_ID".to_string(),
+        0,
+        MAX_AUDIT_PAGE_SIZE + 1,
+        &storage_request_sender,
+        replier,
+    )
+    .await;
+
+    assert!(function_result.is_err());
+    assert_eq!(
+        ErrorKind::InvalidArgument,
+        listener.await.unwrap().unwrap_err().kind
+    );
+}
+
+#[tokio::test]
+async fn answer_organizations_users_cannot_read_as_missing_permission() {
+    const READABLE_ID: &str = "READABLE_ID";
+    const UNREADABLE_ID: &str = "UNREADABLE_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    tokio::spawn(async move {
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            match traced.request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = logic::elements::user::User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: READABLE_ID.to_string(),
+                            permissions: vec![OrganizationPermission::ReadOrganization.to_string()],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::Organization(OrganizationStorageAction::FindByIds {
+                    ids,
+                    replier,
+                }) => {
+                    assert_eq!(vec![READABLE_ID.to_string()], ids);
+
+                    let organizations = ids
+                        .into_iter()
+                        .map(|id| Organization {
+                            id,
+                            name: "NAME".to_string(),
+                            country: "RO".to_string(),
+                            address: "ADDRESS".to_string(),
+                            telephone: "+40712345678".to_string(),
+                            permissions: Vec::new(),
+                            version: 0,
+                        })
+                        .collect();
+
+                    replier.send(Ok(organizations)).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    read_many(
+        Caller::User("USER_ID".to_string()),
+        vec![UNREADABLE_ID.to_string(), READABLE_ID.to_string()],
+        &storage_request_sender,
+        replier,
+    )
+    .await
+    .unwrap();
+
+    let entries = listener.await.unwrap().unwrap();
+
+    assert_eq!(UNREADABLE_ID, entries[0].id);
+    assert!(entries[0].value.is_none());
+    assert_eq!(Some("missing_permission"), entries[0].error);
+
+    assert_eq!(READABLE_ID, entries[1].id);
+    assert!(entries[1].value.is_some());
+}
@@ -1,37 +1,99 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_root_logic_action::OrganizationRootLogicAction;
 use crate::logic::actions::organization_root_storage_action::OrganizationRootStorageAction;
-use crate::logic::elements::organization_root::OrganizationRoot;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::caller::{record_service_access, Caller};
+use crate::logic::elements::audit_record::AuditAction;
+use crate::logic::elements::organization_root::{
+    OrganizationRoot, OrganizationRootQuery, RootPage, MAX_PAGE_SIZE,
+};
+use crate::logic::organization_permission::OrganizationPermission;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
+use crate::logic::validation::user::has_user_permission;
 use cooplan_util::error_handler::ErrorHandler;
 
 pub async fn execute(
     action: OrganizationRootLogicAction,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<(), Error> {
     match action {
         OrganizationRootLogicAction::Read {
+            caller,
             organization_id,
+            query,
             replier,
         } => {
-            read(organization_id, replier, storage_request_sender).await?;
+            read(
+                caller,
+                organization_id,
+                query,
+                replier,
+                storage_request_sender,
+            )
+            .await?;
         }
     }
     Ok(())
 }
 
 async fn read(
+    caller: Caller,
     organization_id: String,
+    query: OrganizationRootQuery,
     replier: tokio::sync::oneshot::Sender<Result<OrganizationRoot, Error>>,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<(), Error> {
+    for (page, field) in [
+        (&query.users, "users_page_size"),
+        (&query.invitations, "invitations_page_size"),
+    ] {
+        if let Err(error) = validate_page_size(page, field) {
+            return replier.handle_error(error);
+        }
+    }
+
+    match &caller {
+        Caller::User(user_id) => match has_user_permission(
+            user_id,
+            &organization_id,
+            OrganizationPermission::ReadOrganization,
+            storage_request_sender,
+        )
+        .await
+        {
+            Ok(true) => (),
+            Ok(false) => {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot read the organization",
+                );
+
+                return replier.handle_error(error);
+            }
+            Err(error) => return replier.handle_error(error),
+        },
+        // The scope grants the access regardless of memberships.
+        Caller::Service { .. } => {
+            if let Err(error) = record_service_access(
+                &caller,
+                &organization_id,
+                None,
+                AuditAction::ServiceRead,
+                storage_request_sender,
+            )
+            .await
+            {
+                return replier.handle_error(error);
+            }
+        }
+    }
+
     let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
 
     match storage_request_sender
         .send(StorageRequest::OrganizationRoot(
             OrganizationRootStorageAction::Read {
                 organization_id,
+                query,
                 replier: storage_replier,
             },
         ))
@@ -72,3 +134,64 @@ async fn read(
 
     Ok(())
 }
+
+fn validate_page_size(page: &RootPage, field: &'static str) -> Result<(), Error> {
+    if page.size == 0 || page.size > MAX_PAGE_SIZE {
+        return Err(Error::new(
+            ErrorKind::InvalidArgument,
+            format!("page size must be between 1 and {}", MAX_PAGE_SIZE),
+        )
+        .with_field(field));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn refuse_users_who_are_not_members() {
+    use crate::logic::actions::user_storage_action::UserStorageAction;
+    use crate::logic::elements::user::User;
+    use crate::logic::elements::user_organization::UserOrganization;
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    // The organization root must not be read for a user who is not a member.
+    tokio::spawn(async move {
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            match traced.request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: "OTHER_ORGANIZATION_ID".to_string(),
+                            permissions: vec![OrganizationPermission::ReadOrganization.to_string()],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let _ = read(
+        Caller::User("USER_ID".to_string()),
+        "ORGANIZATION_ID".to_string(),
+        OrganizationRootQuery::default(),
+        replier,
+        &storage_request_sender,
+    )
+    .await;
+
+    assert_eq!(
+        ErrorKind::MissingPermission,
+        listener.await.unwrap().unwrap_err().kind
+    );
+}
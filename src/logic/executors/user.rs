@@ -1,28 +1,91 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::user_logic_action::UserLogicAction;
 use crate::logic::actions::user_storage_action::UserStorageAction;
+use crate::logic::caller::{record_service_access, Caller};
+use crate::logic::elements::audit_record::{changes, AuditAction, AuditRecord};
+use crate::logic::elements::batch_read::{entries, unique_ids, BatchEntry};
+use crate::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
 use crate::logic::elements::user::User;
 use crate::logic::elements::user_organization::UserOrganization;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::organization_permission::{is_administrator, OrganizationPermission};
+use crate::logic::permission_cache::PermissionCache;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
+use crate::logic::validation::user::{find_user, has_user_permission, is_last_administrator};
 use cooplan_util::error_handler::ErrorHandler;
+use std::collections::BTreeMap;
+use std::str::FromStr;
 
 pub async fn execute(
     action: UserLogicAction,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
 ) -> Result<(), Error> {
     match action {
-        UserLogicAction::Read { user_id, replier } => {
-            read(user_id, replier, storage_request_sender).await?;
+        UserLogicAction::Read {
+            caller,
+            user_id,
+            replier,
+        } => {
+            read(caller, user_id, replier, storage_request_sender).await?;
+        }
+        UserLogicAction::ReadMany {
+            caller,
+            user_ids,
+            replier,
+        } => {
+            read_many(caller, user_ids, replier, storage_request_sender).await?;
+        }
+        UserLogicAction::CheckPermission {
+            caller,
+            user_id,
+            organization_id,
+            permissions,
+            replier,
+        } => {
+            check_permission(
+                caller,
+                user_id,
+                organization_id,
+                permissions,
+                replier,
+                storage_request_sender,
+                permission_cache,
+            )
+            .await?;
+        }
+        UserLogicAction::EditPermissions {
+            user_id,
+            target_user_id,
+            organization_id,
+            permissions,
+            version,
+            replier,
+        } => {
+            let membership = UserOrganization {
+                organization_id,
+                permissions,
+            };
+
+            edit_permissions(
+                user_id,
+                target_user_id,
+                membership,
+                version,
+                replier,
+                storage_request_sender,
+                permission_cache,
+            )
+            .await?;
         }
     }
     Ok(())
 }
 
 async fn read(
+    caller: Caller,
     user_id: String,
     replier: tokio::sync::oneshot::Sender<Result<User, Error>>,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<(), Error> {
     let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
 
@@ -71,6 +134,10 @@ async fn read(
         }
     };
 
+    if let Err(error) = record_user_read(&caller, &user, storage_request_sender).await {
+        return replier.handle_error(error);
+    }
+
     match replier.send(Ok(user)) {
         Ok(_) => (),
         Err(_) => {
@@ -83,3 +150,756 @@ async fn read(
 
     Ok(())
 }
+
+async fn read_many(
+    caller: Caller,
+    user_ids: Vec<String>,
+    replier: tokio::sync::oneshot::Sender<Result<Vec<BatchEntry<User>>, Error>>,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<(), Error> {
+    let user_ids = match unique_ids(user_ids, "user_ids") {
+        Ok(user_ids) => user_ids,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
+
+    let action = UserStorageAction::FindUsersByIds {
+        user_ids: user_ids.clone(),
+        replier: storage_replier,
+    };
+
+    match storage_request_sender
+        .send(StorageRequest::User(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    let users = match storage_receiver.await {
+        Ok(Ok(users)) => users,
+        Ok(Err(error)) => return replier.handle_error(error),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to receive storage result: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    };
+
+    for user in &users {
+        if let Err(error) = record_user_read(&caller, user, storage_request_sender).await {
+            return replier.handle_error(error);
+        }
+    }
+
+    let entries = entries(
+        user_ids,
+        users,
+        |user| user.id.as_str(),
+        ErrorKind::UserNotFound,
+    );
+
+    match replier.send(Ok(entries)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send logic result",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Services may check any user, while users may only check themselves.
+/// Checks by services are recorded before being answered, even from the cache.
+async fn check_permission(
+    caller: Caller,
+    user_id: String,
+    organization_id: String,
+    permissions: Vec<String>,
+    replier: tokio::sync::oneshot::Sender<Result<BTreeMap<String, bool>, Error>>,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    if permissions.is_empty() {
+        let error = Error::new(ErrorKind::InvalidArgument, "no permissions to check")
+            .with_field("permissions");
+
+        return replier.handle_error(error);
+    }
+
+    if let Some(permission) = permissions
+        .iter()
+        .find(|permission| OrganizationPermission::from_str(permission).is_err())
+    {
+        let error = Error::new(
+            ErrorKind::InvalidArgument,
+            format!("unknown permission '{}'", permission),
+        )
+        .with_field("permissions");
+
+        return replier.handle_error(error);
+    }
+
+    match &caller {
+        Caller::User(caller_id) => {
+            if *caller_id != user_id {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot check the permissions of other users",
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+        // The scope grants the check regardless of memberships.
+        Caller::Service { .. } => {
+            if let Err(error) = record_service_access(
+                &caller,
+                &organization_id,
+                Some(&user_id),
+                AuditAction::ServiceCheckPermission,
+                storage_request_sender,
+            )
+            .await
+            {
+                return replier.handle_error(error);
+            }
+        }
+    }
+
+    let granted = match permission_cache.get(&user_id, &organization_id) {
+        Some(granted) => granted,
+        None => {
+            let generation = permission_cache.generation();
+
+            let granted =
+                match find_membership(&user_id, &organization_id, storage_request_sender).await {
+                    Ok(granted) => granted,
+                    Err(error) => return replier.handle_error(error),
+                };
+
+            permission_cache.insert(user_id, organization_id, granted.clone(), generation);
+
+            granted
+        }
+    };
+
+    let result = permissions
+        .into_iter()
+        .map(|permission| {
+            let is_granted = granted.contains(&permission);
+            (permission, is_granted)
+        })
+        .collect();
+
+    match replier.send(Ok(result)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send logic result",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Permissions applying to the user within the organization, none if they are not a member or are suspended.
+async fn find_membership(
+    user_id: &str,
+    organization_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Vec<String>, Error> {
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
+
+    let action = UserStorageAction::FindMembership {
+        user_id: user_id.to_string(),
+        organization_id: organization_id.to_string(),
+        replier: storage_replier,
+    };
+
+    match storage_request_sender
+        .send(StorageRequest::User(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    let user = match storage_receiver.await {
+        Ok(result) => result?,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to receive storage result: {}", error),
+            ));
+        }
+    };
+
+    let permissions = match user {
+        Some(user) if !user.suspended => user
+            .organizations
+            .into_iter()
+            .find(|membership| membership.organization_id == organization_id)
+            .map(|membership| membership.permissions)
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    };
+
+    Ok(permissions)
+}
+
+/// Services leave an audit record for each membership of the user they read.
+async fn record_user_read(
+    caller: &Caller,
+    user: &User,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<(), Error> {
+    let mut organization_ids: Vec<&str> = user
+        .organizations
+        .iter()
+        .map(|organization| organization.organization_id.as_str())
+        .collect();
+
+    if organization_ids.is_empty() {
+        organization_ids.push("");
+    }
+
+    for organization_id in organization_ids {
+        record_service_access(
+            caller,
+            organization_id,
+            Some(&user.id),
+            AuditAction::ServiceReadUser,
+            storage_request_sender,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Overwrites the permissions of the target user within the membership's organization.
+async fn edit_permissions(
+    user_id: String,
+    target_user_id: String,
+    membership: UserOrganization,
+    version: u64,
+    replier: tokio::sync::oneshot::Sender<Result<User, Error>>,
+    storage_request_sender: &StorageRequestSender,
+    permission_cache: &PermissionCache,
+) -> Result<(), Error> {
+    let UserOrganization {
+        organization_id,
+        permissions,
+    } = membership;
+
+    match has_user_permission(
+        &user_id,
+        &organization_id,
+        OrganizationPermission::UpdateUser,
+        storage_request_sender,
+    )
+    .await
+    {
+        Ok(can_update) => {
+            if !can_update {
+                let error = Error::new(
+                    ErrorKind::MissingPermission,
+                    "user cannot update the permissions of other users",
+                );
+
+                return replier.handle_error(error);
+            }
+        }
+        Err(error) => return replier.handle_error(error),
+    }
+
+    if let Some(permission) = permissions
+        .iter()
+        .find(|permission| OrganizationPermission::from_str(permission).is_err())
+    {
+        let error = Error::new(
+            ErrorKind::InvalidArgument,
+            format!("unknown permission '{}'", permission),
+        )
+        .with_field("permissions");
+
+        return replier.handle_error(error);
+    }
+
+    let target_user = match find_user(&target_user_id, storage_request_sender).await {
+        Ok(target_user) => target_user,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    if let Some(target_user) = &target_user {
+        if !is_administrator(&permissions) {
+            match is_last_administrator(target_user, &organization_id, storage_request_sender).await
+            {
+                Ok(true) => {
+                    let error = Error::new(
+                        ErrorKind::LastAdministrator,
+                        "the last administrator of an organization cannot be demoted",
+                    );
+
+                    return replier.handle_error(error);
+                }
+                Ok(false) => (),
+                Err(error) => return replier.handle_error(error),
+            }
+        }
+    }
+
+    let (storage_replier, storage_receiver) = tokio::sync::oneshot::channel();
+
+    let events = vec![DomainEvent::new(DomainEventPayload::PermissionsChanged {
+        organization_id: organization_id.clone(),
+        user_id: target_user_id.clone(),
+        permissions: permissions.clone(),
+        actor: user_id.clone(),
+    })];
+
+    let before = target_user
+        .as_ref()
+        .and_then(|target_user| find_user_organization(target_user, &organization_id));
+
+    let after = UserOrganization {
+        organization_id: organization_id.clone(),
+        permissions: permissions.clone(),
+    };
+
+    let audit_records = vec![AuditRecord::new(
+        user_id.clone(),
+        organization_id.clone(),
+        AuditAction::EditPermissions,
+        changes(before, Some(&after)),
+    )
+    .with_target_user_id(target_user_id.clone())];
+
+    let action = UserStorageAction::UpdatePermissions {
+        user_id: target_user_id.clone(),
+        organization_id: organization_id.clone(),
+        permissions,
+        version,
+        events,
+        audit_records,
+        replier: storage_replier,
+    };
+
+    match storage_request_sender
+        .send(StorageRequest::User(action))
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    let result = match storage_receiver.await {
+        Ok(result) => result,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to receive storage result: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    };
+
+    if result.is_ok() {
+        permission_cache.invalidate(&target_user_id, &organization_id);
+    }
+
+    match replier.send(result) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send logic result",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn find_user_organization<'a>(
+    user: &'a User,
+    organization_id: &str,
+) -> Option<&'a UserOrganization> {
+    user.organizations
+        .iter()
+        .find(|organization| organization.organization_id == organization_id)
+}
+
+#[cfg(test)]
+use crate::logic::actions::audit_storage_action::AuditStorageAction;
+
+#[cfg(test)]
+#[tokio::test]
+async fn detect_unknown_permission() {
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    tokio::spawn(async move {
+        if let Ok(StorageRequest::User(UserStorageAction::FindUserById { user_id, replier })) =
+            storage_request_receiver
+                .recv()
+                .await
+                .map(|traced| traced.request)
+        {
+            let user = User {
+                id: user_id,
+                organizations: vec![UserOrganization {
+                    organization_id: ORGANIZATION_ID.to_string(),
+                    permissions: vec![OrganizationPermission::UpdateUser.to_string()],
+                }],
+                version: 0,
+                suspended: false,
+            };
+
+            replier.send(Ok(Some(user))).unwrap();
+        }
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let membership = UserOrganization {
+        organization_id: ORGANIZATION_ID.to_string(),
+        permissions: vec!["fly:org".to_string()],
+    };
+
+    let function_result = edit_permissions(
+        USER_ID.to_string(),
+        "TARGET_USER_ID".to_string(),
+        membership,
+        0,
+        replier,
+        &storage_request_sender,
+        &PermissionCache::new(std::time::Duration::ZERO),
+    )
+    .await;
+
+    assert!(function_result.is_err());
+    assert_eq!(
+        ErrorKind::InvalidArgument,
+        listener.await.unwrap().unwrap_err().kind
+    );
+}
+
+#[tokio::test]
+async fn detect_last_administrator_demotion() {
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    tokio::spawn(async move {
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            let request = traced.request;
+
+            match request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier }) => {
+                    let user = User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id: ORGANIZATION_ID.to_string(),
+                            permissions: vec![OrganizationPermission::UpdateUser.to_string()],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::CountAdministratorsByOrganizationId {
+                    replier,
+                    ..
+                }) => {
+                    replier.send(Ok(1)).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+    });
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let membership = UserOrganization {
+        organization_id: ORGANIZATION_ID.to_string(),
+        permissions: vec![OrganizationPermission::ReadOrganization.to_string()],
+    };
+
+    let function_result = edit_permissions(
+        USER_ID.to_string(),
+        USER_ID.to_string(),
+        membership,
+        0,
+        replier,
+        &storage_request_sender,
+        &PermissionCache::new(std::time::Duration::ZERO),
+    )
+    .await;
+
+    assert!(function_result.is_err());
+    assert_eq!(
+        ErrorKind::LastAdministrator,
+        listener.await.unwrap().unwrap_err().kind
+    );
+}
+
+#[tokio::test]
+async fn answer_permission_checks_from_the_cache() {
+    use std::time::Duration;
+
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    // Storage finds the membership only once, so the second check must be answered from the cache.
+    // Every check by the service is recorded, though.
+    let storage = tokio::spawn(async move {
+        let mut found = false;
+        let mut recorded = 0;
+
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            match traced.request {
+                StorageRequest::User(UserStorageAction::FindMembership {
+                    user_id,
+                    organization_id,
+                    replier,
+                }) if !found => {
+                    found = true;
+
+                    let user = User {
+                        id: user_id,
+                        organizations: vec![UserOrganization {
+                            organization_id,
+                            permissions: vec![OrganizationPermission::ReadOrganization.to_string()],
+                        }],
+                        version: 0,
+                        suspended: false,
+                    };
+
+                    replier.send(Ok(Some(user))).unwrap();
+                }
+                StorageRequest::Audit(AuditStorageAction::Create { record, replier }) => {
+                    assert_eq!("service_check_permission", record.action);
+                    assert_eq!(Some(USER_ID.to_string()), record.target_user_id);
+                    recorded += 1;
+
+                    replier.send(Ok(())).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+
+        recorded
+    });
+
+    let permission_cache = PermissionCache::new(Duration::from_secs(60));
+    let service = Caller::Service {
+        client_id: "BILLING@clients".to_string(),
+        scope: "membership:check".to_string(),
+    };
+
+    for _ in 0..2 {
+        let (replier, listener) = tokio::sync::oneshot::channel();
+
+        check_permission(
+            service.clone(),
+            USER_ID.to_string(),
+            ORGANIZATION_ID.to_string(),
+            vec!["read:org".to_string(), "update:org".to_string()],
+            replier,
+            &storage_request_sender,
+            &permission_cache,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            Some(BTreeMap::from([
+                ("read:org".to_string(), true),
+                ("update:org".to_string(), false),
+            ])),
+            listener.await.unwrap().ok()
+        );
+    }
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    let _ = check_permission(
+        Caller::User("OTHER_USER_ID".to_string()),
+        USER_ID.to_string(),
+        ORGANIZATION_ID.to_string(),
+        vec!["read:org".to_string()],
+        replier,
+        &storage_request_sender,
+        &permission_cache,
+    )
+    .await;
+
+    assert_eq!(
+        ErrorKind::MissingPermission,
+        listener.await.unwrap().unwrap_err().kind
+    );
+
+    drop(storage_request_sender);
+    assert_eq!(2, storage.await.unwrap());
+}
+
+#[tokio::test]
+async fn deny_permissions_right_after_their_revocation() {
+    use std::time::Duration;
+
+    const ADMINISTRATOR_ID: &str = "ADMINISTRATOR_ID";
+    const USER_ID: &str = "USER_ID";
+    const ORGANIZATION_ID: &str = "ORGANIZATION_ID";
+
+    let (storage_request_sender, storage_request_receiver) =
+        crate::telemetry::traced_channel::bounded(1);
+
+    tokio::spawn(async move {
+        let mut permissions = vec![OrganizationPermission::ReadOrganization.to_string()];
+
+        let user = |user_id: &str, permissions: Vec<String>| User {
+            id: user_id.to_string(),
+            organizations: vec![UserOrganization {
+                organization_id: ORGANIZATION_ID.to_string(),
+                permissions,
+            }],
+            version: 0,
+            suspended: false,
+        };
+
+        while let Ok(traced) = storage_request_receiver.recv().await {
+            match traced.request {
+                StorageRequest::User(UserStorageAction::FindUserById { user_id, replier })
+                    if user_id == ADMINISTRATOR_ID =>
+                {
+                    let administrator = user(
+                        ADMINISTRATOR_ID,
+                        vec![OrganizationPermission::UpdateUser.to_string()],
+                    );
+
+                    replier.send(Ok(Some(administrator))).unwrap();
+                }
+                StorageRequest::User(UserStorageAction::FindUserById { replier, .. })
+                | StorageRequest::User(UserStorageAction::FindMembership { replier, .. }) => {
+                    replier
+                        .send(Ok(Some(user(USER_ID, permissions.clone()))))
+                        .unwrap();
+                }
+                StorageRequest::User(UserStorageAction::UpdatePermissions {
+                    permissions: updated_permissions,
+                    replier,
+                    ..
+                }) => {
+                    permissions = updated_permissions;
+
+                    replier
+                        .send(Ok(user(USER_ID, permissions.clone())))
+                        .unwrap();
+                }
+                StorageRequest::Audit(AuditStorageAction::Create { replier, .. }) => {
+                    replier.send(Ok(())).unwrap();
+                }
+                _ => panic!("unexpected storage request"),
+            }
+        }
+    });
+
+    let permission_cache = PermissionCache::new(Duration::from_secs(60));
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    check_permission(
+        Caller::User(USER_ID.to_string()),
+        USER_ID.to_string(),
+        ORGANIZATION_ID.to_string(),
+        vec!["read:org".to_string()],
+        replier,
+        &storage_request_sender,
+        &permission_cache,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(BTreeMap::from([("read:org".to_string(), true)])),
+        listener.await.unwrap().ok()
+    );
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    edit_permissions(
+        ADMINISTRATOR_ID.to_string(),
+        USER_ID.to_string(),
+        UserOrganization {
+            organization_id: ORGANIZATION_ID.to_string(),
+            permissions: Vec::new(),
+        },
+        0,
+        replier,
+        &storage_request_sender,
+        &permission_cache,
+    )
+    .await
+    .unwrap();
+
+    assert!(listener.await.unwrap().is_ok());
+
+    let (replier, listener) = tokio::sync::oneshot::channel();
+
+    check_permission(
+        Caller::User(USER_ID.to_string()),
+        USER_ID.to_string(),
+        ORGANIZATION_ID.to_string(),
+        vec!["read:org".to_string()],
+        replier,
+        &storage_request_sender,
+        &permission_cache,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        Some(BTreeMap::from([("read:org".to_string(), false)])),
+        listener.await.unwrap().ok()
+    );
+}
@@ -1,23 +1,46 @@
-use async_channel::{Receiver, Sender};
-
+use crate::dispatcher_pool::DispatcherPool;
 use crate::error::Error;
+use crate::health::heartbeat::HeartbeatRegistry;
+use crate::logic::abuse;
+use crate::logic::elements::abuse_event::AbuseEvent;
 use crate::logic::logic_request::LogicRequest;
 use crate::logic::logic_request_dispatch::LogicRequestDispatch;
-use crate::logic::storage_request::StorageRequest;
+use crate::logic::permission_cache::PermissionCache;
+use crate::logic::storage_request::StorageRequestSender;
+use crate::supervisor;
+use crate::telemetry::traced_channel::Traced;
+use async_channel::Receiver;
 
 pub async fn initialize(
     concurrent_dispatchers: u16,
-    logic_request_receiver: Receiver<LogicRequest>,
-    storage_request_sender: Sender<StorageRequest>,
-) -> Result<(), Error> {
-    for _ in 0..concurrent_dispatchers {
-        let logic_request_dispatch = LogicRequestDispatch::new(
-            logic_request_receiver.clone(),
-            storage_request_sender.clone(),
-        );
+    logic_request_receiver: Receiver<Traced<LogicRequest>>,
+    abuse_receiver: tokio::sync::mpsc::Receiver<AbuseEvent>,
+    storage_request_sender: StorageRequestSender,
+    permission_cache: PermissionCache,
+    heartbeat_registry: &HeartbeatRegistry,
+) -> Result<DispatcherPool, Error> {
+    // Abuse keeps being reported until the process exits, so its task is not drained on shutdown.
+    tokio::spawn(abuse::run(abuse_receiver, storage_request_sender.clone()));
+
+    let mut dispatchers = DispatcherPool::new(
+        "logic",
+        heartbeat_registry.clone(),
+        move |heartbeat, retirement| {
+            let logic_request_dispatch = LogicRequestDispatch::new(
+                logic_request_receiver.clone(),
+                storage_request_sender.clone(),
+                permission_cache.clone(),
+                heartbeat,
+                retirement,
+            );
+
+            tokio::spawn(supervisor::supervise("logic", move || {
+                logic_request_dispatch.clone().run()
+            }))
+        },
+    );
 
-        tokio::spawn(logic_request_dispatch.run());
-    }
+    dispatchers.scale(concurrent_dispatchers as usize);
 
-    Ok(())
+    Ok(dispatchers)
 }
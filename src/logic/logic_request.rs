@@ -1,9 +1,35 @@
+use crate::logic::actions::identity_provider_logic_action::IdentityProviderLogicAction;
 use crate::logic::actions::organization_logic_action::OrganizationLogicAction;
 use crate::logic::actions::organization_root_logic_action::OrganizationRootLogicAction;
 use crate::logic::actions::user_logic_action::UserLogicAction;
+use crate::telemetry::traced_channel::TracedSender;
 
 pub enum LogicRequest {
     Organization(OrganizationLogicAction),
     OrganizationRoot(OrganizationRootLogicAction),
     User(UserLogicAction),
+    IdentityProvider(IdentityProviderLogicAction),
+}
+
+pub type LogicRequestSender = TracedSender<LogicRequest>;
+
+impl LogicRequest {
+    /// Input element whose action is requested.
+    pub fn element(&self) -> &'static str {
+        match self {
+            LogicRequest::Organization(_) => "organization",
+            LogicRequest::OrganizationRoot(_) => "organization_root",
+            LogicRequest::User(_) => "user",
+            LogicRequest::IdentityProvider(_) => "identity_provider",
+        }
+    }
+
+    pub fn action(&self) -> &'static str {
+        match self {
+            LogicRequest::Organization(action) => action.name(),
+            LogicRequest::OrganizationRoot(action) => action.name(),
+            LogicRequest::User(action) => action.name(),
+            LogicRequest::IdentityProvider(action) => action.name(),
+        }
+    }
 }
@@ -1,80 +1,147 @@
-use async_channel::{Receiver, Sender};
+use async_channel::Receiver;
+use std::time::Instant;
+use tracing::{Instrument, Span};
 
-use crate::error::ErrorKind;
-use crate::logic::executors::{organization, organization_root, user};
+use crate::deadline;
+use crate::dispatcher_pool::Retirement;
+use crate::error::{Error, ErrorKind};
+use crate::health::heartbeat::Heartbeat;
+use crate::logic::executors::{identity_provider, organization, organization_root, user};
 use crate::logic::logic_request::LogicRequest;
-use crate::logic::storage_request::StorageRequest;
+use crate::logic::permission_cache::PermissionCache;
+use crate::logic::storage_request::StorageRequestSender;
+use crate::telemetry::metrics;
+use crate::telemetry::traced_channel::Traced;
 
+#[derive(Clone)]
 pub struct LogicRequestDispatch {
-    request_receiver: Receiver<LogicRequest>,
-    storage_request_sender: Sender<StorageRequest>,
+    request_receiver: Receiver<Traced<LogicRequest>>,
+    storage_request_sender: StorageRequestSender,
+    permission_cache: PermissionCache,
+    heartbeat: Heartbeat,
+    retirement: Retirement,
 }
 
 impl LogicRequestDispatch {
     pub fn new(
-        request_receiver: Receiver<LogicRequest>,
-        storage_request_sender: Sender<StorageRequest>,
+        request_receiver: Receiver<Traced<LogicRequest>>,
+        storage_request_sender: StorageRequestSender,
+        permission_cache: PermissionCache,
+        heartbeat: Heartbeat,
+        retirement: Retirement,
     ) -> LogicRequestDispatch {
         LogicRequestDispatch {
             request_receiver,
             storage_request_sender,
+            permission_cache,
+            heartbeat,
+            retirement,
         }
     }
 
+    /// Runs until the requests channel is closed and every queued request has been executed,
+    /// or until the dispatcher is retired.
     pub async fn run(self) {
         loop {
-            match self.request_receiver.recv().await {
-                Ok(request) => {
-                    log::info!("received logic request");
+            if self.retirement.is_retired() {
+                log::info!("logic dispatcher retired, stopping");
+                break;
+            }
 
-                    match request {
-                        LogicRequest::Organization(organization_action) => {
-                            match organization::execute(
-                                organization_action,
-                                &self.storage_request_sender,
-                            )
-                            .await
-                            {
-                                Ok(_) => (),
-                                Err(error) => {
-                                    log::info!("failed to execute organization action: {}", error);
-                                    continue;
-                                }
-                            }
-                        }
-                        LogicRequest::OrganizationRoot(organization_root_action) => {
-                            match organization_root::execute(
-                                organization_root_action,
-                                &self.storage_request_sender,
-                            )
-                            .await
-                            {
-                                Ok(_) => (),
-                                Err(error) => {
-                                    log::info!(
-                                        "failed to execute organization root action: {}",
-                                        error
-                                    );
-                                    continue;
-                                }
-                            }
-                        }
-                        LogicRequest::User(user_action) => {
-                            match user::execute(user_action, &self.storage_request_sender).await {
-                                Ok(_) => (),
-                                Err(error) => {
-                                    log::info!("failed to execute user action: {}", error);
-                                    continue;
-                                }
-                            }
+            let received =
+                match tokio::time::timeout(self.heartbeat.interval(), self.request_receiver.recv())
+                    .await
+                {
+                    Ok(received) => received,
+                    Err(_) => {
+                        self.heartbeat.beat();
+                        continue;
+                    }
+                };
+
+            self.heartbeat.beat();
+
+            match received {
+                Ok(Traced {
+                    span,
+                    deadline,
+                    request,
+                }) => {
+                    metrics::record_queued_requests("logic", self.request_receiver.len());
+
+                    span.in_scope(|| log::info!("received logic request"));
+
+                    let element = request.element();
+                    let action = request.action();
+                    let started = Instant::now();
+
+                    // The api has already answered requests whose deadline has been reached.
+                    let result = if deadline::has_expired(deadline) {
+                        Err(Error::new(
+                            ErrorKind::Timeout,
+                            "deadline reached before execution",
+                        ))
+                    } else {
+                        match deadline::within(deadline, self.execute(request, &span)).await {
+                            Some(result) => result,
+                            None => Err(Error::new(
+                                ErrorKind::Timeout,
+                                "deadline reached during execution",
+                            )),
                         }
                     };
+
+                    metrics::record_logic_request(element, action, started.elapsed(), &result);
+
+                    if let Err(error) = result {
+                        span.in_scope(|| {
+                            log::info!(
+                                "failed to execute {} action '{}': {}",
+                                element,
+                                action,
+                                error
+                            )
+                        });
+                    }
                 }
-                Err(error) => {
-                    // TODO: Block requests until the channel has been reinitialized.
-                    log::error!("failed to receive request: {}", error);
+                Err(_) => {
+                    log::info!("logic requests channel closed and drained, stopping dispatcher");
+                    break;
                 }
             }
         }
     }
+
+    async fn execute(&self, request: LogicRequest, span: &Span) -> Result<(), Error> {
+        match request {
+            LogicRequest::Organization(organization_action) => {
+                organization::execute(organization_action, &self.storage_request_sender)
+                    .instrument(tracing::info_span!(parent: span, "organization_logic"))
+                    .await
+            }
+            LogicRequest::OrganizationRoot(organization_root_action) => {
+                organization_root::execute(organization_root_action, &self.storage_request_sender)
+                    .instrument(tracing::info_span!(parent: span, "organization_root_logic"))
+                    .await
+            }
+            LogicRequest::User(user_action) => {
+                user::execute(
+                    user_action,
+                    &self.storage_request_sender,
+                    &self.permission_cache,
+                )
+                .instrument(tracing::info_span!(parent: span, "user_logic"))
+                .await
+            }
+            LogicRequest::IdentityProvider(identity_provider_action) => {
+                identity_provider::execute(
+                    identity_provider_action,
+                    &self.storage_request_sender,
+                    &self.permission_cache,
+                )
+                .instrument(tracing::info_span!(parent: span, "identity_provider_logic"))
+                .await
+            }
+        }
+    }
 }
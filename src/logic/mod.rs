@@ -1,9 +1,13 @@
+pub mod abuse;
 pub mod actions;
+pub mod audit;
+pub mod caller;
 pub mod elements;
 pub mod executors;
 pub mod init;
 pub mod logic_request;
 mod logic_request_dispatch;
 pub mod organization_permission;
+pub mod permission_cache;
 pub mod storage_request;
 pub mod validation;
@@ -58,3 +58,9 @@ pub fn organization_creator_permissions() -> Vec<String> {
         OrganizationPermission::DeleteUser.to_string(),
     ]
 }
+
+/// Administrators are able to grant permissions to the rest of the members,
+/// so an organization without any of them can no longer be managed.
+pub fn is_administrator(permissions: &[String]) -> bool {
+    permissions.contains(&OrganizationPermission::UpdateUser.to_string())
+}
@@ -0,0 +1,208 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Entries kept at most, beyond which the expired ones are evicted, or all of them if none has expired.
+const CAPACITY: usize = 10_000;
+
+/// Permissions by user and organization, along with when they expire.
+type Entries = HashMap<(String, String), (Instant, Vec<String>)>;
+
+struct State {
+    entries: Entries,
+    /// Incremented on every invalidation, so permissions read from storage before
+    /// an invalidation are not cached afterwards.
+    generation: u64,
+}
+
+/// Permissions applying to users within organizations, shared by every logic dispatcher.
+/// Entries are invalidated whenever the permissions, suspension or memberships of their user change.
+#[derive(Clone)]
+pub struct PermissionCache {
+    time_to_live: Duration,
+    state: Arc<Mutex<State>>,
+}
+
+impl PermissionCache {
+    /// Nothing is cached with a time to live of zero.
+    pub fn new(time_to_live: Duration) -> PermissionCache {
+        PermissionCache {
+            time_to_live,
+            state: Arc::new(Mutex::new(State {
+                entries: HashMap::new(),
+                generation: 0,
+            })),
+        }
+    }
+
+    /// Permissions applying to the user within the organization, unless expired or never cached.
+    pub fn get(&self, user_id: &str, organization_id: &str) -> Option<Vec<String>> {
+        let state = self.state.lock().ok()?;
+
+        match state
+            .entries
+            .get(&(user_id.to_string(), organization_id.to_string()))
+        {
+            Some((expires_at, permissions)) if *expires_at > Instant::now() => {
+                Some(permissions.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// To be read before the permissions are read from storage, then passed to `insert`.
+    pub fn generation(&self) -> u64 {
+        match self.state.lock() {
+            Ok(state) => state.generation,
+            // Never matches, so nothing is inserted.
+            Err(_) => u64::MAX,
+        }
+    }
+
+    /// Permissions are empty for users who are not members or are suspended.
+    /// Ignored if anything has been invalidated since `generation` was read,
+    /// as the permissions may predate the invalidation.
+    pub fn insert(
+        &self,
+        user_id: String,
+        organization_id: String,
+        permissions: Vec<String>,
+        generation: u64,
+    ) {
+        if self.time_to_live.is_zero() {
+            return;
+        }
+
+        if let Ok(mut state) = self.state.lock() {
+            if state.generation != generation {
+                return;
+            }
+
+            let now = Instant::now();
+
+            if state.entries.len() >= CAPACITY {
+                state.entries.retain(|_, (expires_at, _)| *expires_at > now);
+
+                if state.entries.len() >= CAPACITY {
+                    state.entries.clear();
+                }
+            }
+
+            state.entries.insert(
+                (user_id, organization_id),
+                (now + self.time_to_live, permissions),
+            );
+        }
+    }
+
+    pub fn invalidate(&self, user_id: &str, organization_id: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.generation = state.generation.wrapping_add(1);
+            state
+                .entries
+                .remove(&(user_id.to_string(), organization_id.to_string()));
+        }
+    }
+
+    /// Forgets the permissions of the user within every organization.
+    pub fn invalidate_user(&self, user_id: &str) {
+        if let Ok(mut state) = self.state.lock() {
+            state.generation = state.generation.wrapping_add(1);
+            state
+                .entries
+                .retain(|(cached_user_id, _), _| cached_user_id != user_id);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut state) = self.state.lock() {
+            state.generation = state.generation.wrapping_add(1);
+            state.entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn forget_permissions_once_expired() {
+    let cache = PermissionCache::new(Duration::from_millis(50));
+    cache.insert(
+        "USER".to_string(),
+        "ORGANIZATION".to_string(),
+        vec!["read:org".to_string()],
+        cache.generation(),
+    );
+
+    assert_eq!(
+        Some(vec!["read:org".to_string()]),
+        cache.get("USER", "ORGANIZATION")
+    );
+    assert_eq!(None, cache.get("USER", "OTHER_ORGANIZATION"));
+
+    std::thread::sleep(Duration::from_millis(60));
+    assert_eq!(None, cache.get("USER", "ORGANIZATION"));
+
+    let disabled = PermissionCache::new(Duration::ZERO);
+    disabled.insert(
+        "USER".to_string(),
+        "ORGANIZATION".to_string(),
+        Vec::new(),
+        disabled.generation(),
+    );
+    assert_eq!(None, disabled.get("USER", "ORGANIZATION"));
+}
+
+#[test]
+fn forget_invalidated_permissions() {
+    let cache = PermissionCache::new(Duration::from_secs(60));
+
+    for (user_id, organization_id) in [
+        ("USER", "ORGANIZATION"),
+        ("USER", "OTHER_ORGANIZATION"),
+        ("OTHER_USER", "ORGANIZATION"),
+    ] {
+        cache.insert(
+            user_id.to_string(),
+            organization_id.to_string(),
+            vec!["read:org".to_string()],
+            cache.generation(),
+        );
+    }
+
+    cache.invalidate("USER", "ORGANIZATION");
+    assert_eq!(None, cache.get("USER", "ORGANIZATION"));
+    assert!(cache.get("USER", "OTHER_ORGANIZATION").is_some());
+
+    cache.invalidate_user("USER");
+    assert_eq!(None, cache.get("USER", "OTHER_ORGANIZATION"));
+    assert!(cache.get("OTHER_USER", "ORGANIZATION").is_some());
+
+    cache.clear();
+    assert_eq!(None, cache.get("OTHER_USER", "ORGANIZATION"));
+}
+
+#[test]
+fn ignore_permissions_read_before_an_invalidation() {
+    let cache = PermissionCache::new(Duration::from_secs(60));
+
+    let generation = cache.generation();
+
+    // Lands while the permissions are being read from storage.
+    cache.invalidate_user("USER");
+
+    cache.insert(
+        "USER".to_string(),
+        "ORGANIZATION".to_string(),
+        vec!["read:org".to_string()],
+        generation,
+    );
+    assert_eq!(None, cache.get("USER", "ORGANIZATION"));
+
+    cache.insert(
+        "USER".to_string(),
+        "ORGANIZATION".to_string(),
+        vec!["read:org".to_string()],
+        cache.generation(),
+    );
+    assert!(cache.get("USER", "ORGANIZATION").is_some());
+}
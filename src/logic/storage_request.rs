@@ -1,11 +1,50 @@
+use crate::logic::actions::audit_storage_action::AuditStorageAction;
 use crate::logic::actions::invitation_code_storage_action::InvitationStorageAction;
 use crate::logic::actions::organization_root_storage_action::OrganizationRootStorageAction;
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
 use crate::logic::actions::user_storage_action::UserStorageAction;
+use crate::telemetry::traced_channel::TracedSender;
 
 pub enum StorageRequest {
     Organization(OrganizationStorageAction),
     User(UserStorageAction),
     Invitation(InvitationStorageAction),
     OrganizationRoot(OrganizationRootStorageAction),
+    Audit(AuditStorageAction),
 }
+
+impl StorageRequest {
+    /// Stored element the action operates on.
+    pub fn element(&self) -> &'static str {
+        match self {
+            StorageRequest::Organization(_) => "organization",
+            StorageRequest::User(_) => "user",
+            StorageRequest::Invitation(_) => "invitation",
+            StorageRequest::OrganizationRoot(_) => "organization_root",
+            StorageRequest::Audit(_) => "audit",
+        }
+    }
+
+    pub fn action(&self) -> &'static str {
+        match self {
+            StorageRequest::Organization(action) => action.name(),
+            StorageRequest::User(action) => action.name(),
+            StorageRequest::Invitation(action) => action.name(),
+            StorageRequest::OrganizationRoot(action) => action.name(),
+            StorageRequest::Audit(action) => action.name(),
+        }
+    }
+
+    /// Whether the requester has stopped waiting for the response, such as after a timeout.
+    pub fn is_abandoned(&self) -> bool {
+        match self {
+            StorageRequest::Organization(action) => action.is_abandoned(),
+            StorageRequest::User(action) => action.is_abandoned(),
+            StorageRequest::Invitation(action) => action.is_abandoned(),
+            StorageRequest::OrganizationRoot(action) => action.is_abandoned(),
+            StorageRequest::Audit(action) => action.is_abandoned(),
+        }
+    }
+}
+
+pub type StorageRequestSender = TracedSender<StorageRequest>;
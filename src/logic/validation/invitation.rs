@@ -1,12 +1,11 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::invitation_code_storage_action::InvitationStorageAction;
 use crate::logic::elements::invitation::Invitation;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
 
 pub async fn get_code_if_valid(
     invitation_code: String,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<Invitation, Error> {
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
@@ -34,7 +33,8 @@ pub async fn get_code_if_valid(
                 Some(invitation) => invitation,
                 None => {
                     let error =
-                        Error::new(ErrorKind::InvitationNotFound, "invitation code not found");
+                        Error::new(ErrorKind::InvitationNotFound, "invitation code not found")
+                            .with_field("invitation_code");
 
                     return Err(error);
                 }
@@ -56,7 +56,8 @@ pub async fn get_code_if_valid(
         return Err(Error::new(
             ErrorKind::InvitationHasExpired,
             "invitation code has expired",
-        ));
+        )
+        .with_field("invitation_code"));
     }
 
     Ok(invitation)
@@ -1,11 +1,10 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
 
 pub async fn is_name_already_used(
     name: &str,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<bool, Error> {
     let (replier, listener) = tokio::sync::oneshot::channel();
 
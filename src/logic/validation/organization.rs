@@ -1,12 +1,12 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
+use crate::logic::actions::user_storage_action::UserStorageAction;
 use crate::logic::elements::organization::Organization;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
 
 pub async fn get_organization_if_exists(
     id: String,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<Organization, Error> {
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
@@ -48,3 +48,41 @@ pub async fn get_organization_if_exists(
 
     Ok(organization)
 }
+
+/// Whether the organization has any other user apart from the one performing the request.
+pub async fn has_organization_other_users(
+    organization_id: String,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<bool, Error> {
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    let request = StorageRequest::User(UserStorageAction::CountByOrganizationId {
+        organization_id,
+        replier,
+    });
+
+    match storage_request_sender.send(request).await {
+        Ok(_) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to send storage request: {}", error),
+            ));
+        }
+    }
+
+    let count = match receiver.await {
+        Ok(result) => result?,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!(
+                    "failed to receive response for a storage request: {}",
+                    error
+                ),
+            ));
+        }
+    };
+
+    Ok(count > 1)
+}
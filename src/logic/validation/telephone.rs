@@ -1,7 +1,7 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
+use phonenumber::Mode;
 use std::panic;
 
 /// Warning: it is not supposed to work with specific international prefixes such as '00'.
@@ -16,9 +16,18 @@ pub fn is_telephone_valid(telephone: &str) -> bool {
     }
 }
 
+/// Formats a valid telephone following E.164, so equal telephones are always stored equally.
+pub fn normalize_telephone(telephone: &str) -> Option<String> {
+    panic::catch_unwind(|| match phonenumber::parse(None, telephone) {
+        Ok(phone_number) => Some(phone_number.format().mode(Mode::E164).to_string()),
+        Err(_) => None,
+    })
+    .unwrap_or_default()
+}
+
 pub async fn is_telephone_being_used(
     telephone: &str,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<bool, Error> {
     let (replier, listener) = tokio::sync::oneshot::channel();
 
@@ -58,3 +67,17 @@ pub async fn is_telephone_being_used(
 
     Ok(result)
 }
+
+#[cfg(test)]
+#[test]
+fn normalizes_telephones_into_e164() {
+    assert_eq!(
+        Some("+40753313640".to_string()),
+        normalize_telephone("+40 753 313 640")
+    );
+    assert_eq!(
+        Some("+40753313640".to_string()),
+        normalize_telephone("+40-753-313-640")
+    );
+    assert_eq!(None, normalize_telephone("INVALID_PHONE"));
+}
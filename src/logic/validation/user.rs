@@ -1,19 +1,69 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::user_storage_action::UserStorageAction;
-use crate::logic::storage_request::StorageRequest;
-use async_channel::Sender;
+use crate::logic::elements::user::User;
+use crate::logic::organization_permission::{is_administrator, OrganizationPermission};
+use crate::logic::storage_request::{StorageRequest, StorageRequestSender};
 
 pub async fn has_user_no_organization(
     user_id: &str,
-    storage_request_sender: &Sender<StorageRequest>,
+    storage_request_sender: &StorageRequestSender,
 ) -> Result<bool, Error> {
+    let user = find_user(user_id, storage_request_sender).await?;
+
+    // User can only create an organization if it has no organizations.
+    let result = match user {
+        Some(user) => user.organizations.is_empty(),
+        None => true,
+    };
+
+    Ok(result)
+}
+
+pub async fn has_user_permission(
+    user_id: &str,
+    organization_id: &str,
+    permission: OrganizationPermission,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<bool, Error> {
+    let user = find_user(user_id, storage_request_sender).await?;
+
+    let permission = permission.to_string();
+
+    let result = match user {
+        Some(user) if !user.suspended => user.organizations.iter().any(|organization| {
+            organization.organization_id == organization_id
+                && organization.permissions.contains(&permission)
+        }),
+        _ => false,
+    };
+
+    Ok(result)
+}
+
+/// Whether the user is the only administrator left within the organization.
+pub async fn is_last_administrator(
+    user: &User,
+    organization_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<bool, Error> {
+    let is_user_administrator = user.organizations.iter().any(|organization| {
+        organization.organization_id == organization_id
+            && is_administrator(&organization.permissions)
+    });
+
+    if !is_user_administrator {
+        return Ok(false);
+    }
+
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
     match storage_request_sender
-        .send(StorageRequest::User(UserStorageAction::FindUserById {
-            user_id: user_id.to_string(),
-            replier,
-        }))
+        .send(StorageRequest::User(
+            UserStorageAction::CountAdministratorsByOrganizationId {
+                organization_id: organization_id.to_string(),
+                replier,
+            },
+        ))
         .await
     {
         Ok(_) => (),
@@ -25,27 +75,48 @@ pub async fn has_user_no_organization(
         }
     }
 
-    let user = match receiver.await {
-        Ok(result) => match result {
-            Ok(optional_user) => optional_user,
-            Err(error) => return Err(error),
-        },
+    match receiver.await {
+        Ok(result) => Ok(result? <= 1),
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
+}
+
+pub async fn find_user(
+    user_id: &str,
+    storage_request_sender: &StorageRequestSender,
+) -> Result<Option<User>, Error> {
+    let (replier, receiver) = tokio::sync::oneshot::channel();
+
+    match storage_request_sender
+        .send(StorageRequest::User(UserStorageAction::FindUserById {
+            user_id: user_id.to_string(),
+            replier,
+        }))
+        .await
+    {
+        Ok(_) => (),
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::InternalFailure,
-                format!(
-                    "failed to receive response for a storage request: {}",
-                    error
-                ),
+                format!("failed to send storage request: {}", error),
             ));
         }
-    };
-
-    // User can only create an organization if it has no organizations.
-    let result = match user {
-        Some(user) => user.organizations.is_empty(),
-        None => true,
-    };
+    }
 
-    Ok(result)
+    match receiver.await {
+        Ok(result) => result,
+        Err(error) => Err(Error::new(
+            ErrorKind::InternalFailure,
+            format!(
+                "failed to receive response for a storage request: {}",
+                error
+            ),
+        )),
+    }
 }
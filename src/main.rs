@@ -1,43 +1,76 @@
-extern crate core;
-
-use crate::config::config::Config;
-use crate::logic::logic_request::LogicRequest;
-use crate::logic::storage_request::StorageRequest;
 use cooplan_amqp_api::api::initialization_package::InitializationPackage;
 use cooplan_lapin_wrapper::config::api::Api;
+use cooplan_organization::api::input::rate_limit::RateLimiter;
+use cooplan_organization::api::output::publisher::ConfirmedPublisher;
+use cooplan_organization::config::config::Config;
+use cooplan_organization::deadline::RequestTimeouts;
+use cooplan_organization::dispatcher_pool::DispatcherPool;
+use cooplan_organization::logic::elements::abuse_event::AbuseEvent;
+use cooplan_organization::logic::logic_request::LogicRequest;
+use cooplan_organization::logic::permission_cache::PermissionCache;
+use cooplan_organization::logic::storage_request::StorageRequest;
+use cooplan_organization::reload::LiveSettings;
+use cooplan_organization::telemetry::traced_channel::Traced;
+use cooplan_organization::{api, config, health, logic, reload, shutdown, storage, telemetry};
 use serde_json::Value;
 use std::io::{Error, ErrorKind};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::time::Duration;
+use tokio::task::JoinHandle;
 
-mod api;
-pub mod config;
-mod error;
-mod logic;
-mod storage;
+const ABUSE_EVENTS_BOUNDARY: usize = 1024;
 
 #[tokio::main]
 async fn main() -> Result<(), Error> {
-    match simple_logger::init() {
-        Ok(_) => (),
+    let (arguments, flags) = config::source::split_arguments(std::env::args().skip(1));
+
+    let api = get_api(&arguments).await?;
+
+    let config_file = match arguments.get(1) {
+        Some(config_file) => config_file.clone(),
+        None => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                "no config file provided",
+            ))
+        }
+    };
+
+    let (raw_config, api_config, config) = get_configs(&config_file, &flags).await?;
+
+    let tracer_provider = match telemetry::init::initialize(config.otlp_endpoint.clone()) {
+        Ok(tracer_provider) => tracer_provider,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::Other,
-                format!("failed to initialize logger: {}", error),
+                format!("failed to initialize tracing: {}", error),
             ));
         }
-    }
-
-    let api = get_api().await?;
+    };
 
-    let api_config = get_api_config().await?;
+    match telemetry::metrics::initialize(config.metrics_address.clone()) {
+        Ok(()) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("failed to initialize metrics: {}", error),
+            ));
+        }
+    }
 
-    let config = get_config().await?;
+    if config.migration_dry_run {
+        return report_migrations(config.mongodb_uri).await;
+    }
 
     let (logic_request_sender, logic_request_receiver) =
-        async_channel::bounded::<LogicRequest>(config.logic_requests_boundary);
+        async_channel::bounded::<Traced<LogicRequest>>(config.logic_requests_boundary);
+
+    // The api requires an output channel, although nothing is ever sent through it: events are
+    // published by the outbox relay, since the output elements do not wait for the broker to confirm
+    // them. The sender is kept alive, as the output api spins once every sender is dropped.
+    let (_unused_output_sender, output_receiver) = tokio::sync::mpsc::channel::<(String, Value)>(1);
 
-    let (output_sender, output_receiver) =
-        tokio::sync::mpsc::channel::<(String, Value)>(config.output_channel_boundary);
+    let event_publisher = get_event_publisher(&api, &raw_config).await?;
 
     let state_tracker_client = cooplan_state_tracker::state_tracker_client::build(
         config.state_tracking_config,
@@ -45,14 +78,47 @@ async fn main() -> Result<(), Error> {
     )
     .await;
 
+    let request_timeouts = RequestTimeouts::new(
+        Duration::from_millis(config.default_request_timeout_in_milliseconds),
+        config
+            .request_timeouts_in_milliseconds
+            .iter()
+            .map(|(action, timeout)| (action.clone(), Duration::from_millis(*timeout)))
+            .collect(),
+    );
+
+    let (abuse_sender, abuse_receiver) =
+        tokio::sync::mpsc::channel::<AbuseEvent>(ABUSE_EVENTS_BOUNDARY);
+
+    let rate_limiter = RateLimiter::new(
+        config.rate_limits,
+        config.invitation_lockout,
+        config.claim_mapping.clone(),
+        abuse_sender,
+    );
+
+    // The api shares them with the reloads.
+    let registered_timeouts = request_timeouts.clone();
+    let registered_limiter = rate_limiter.clone();
+    let claim_mapping = config.claim_mapping;
+    let service_scopes = Arc::new(config.service_scopes);
+
     let api_package = InitializationPackage::new(
-        logic_request_sender,
-        Box::new(api::input::registration::register),
+        logic_request_sender.clone(),
+        Box::new(move |api| {
+            api::input::registration::register(
+                api,
+                registered_timeouts,
+                registered_limiter,
+                claim_mapping.clone(),
+                service_scopes.clone(),
+            )
+        }),
         output_receiver,
-        Box::new(api::output::registration::register),
+        Box::new(|_, _| Ok(Vec::new())),
         api,
         api_config,
-        state_tracker_client,
+        state_tracker_client.clone(),
     );
 
     match cooplan_amqp_api::api::init::initialize(api_package).await {
@@ -65,48 +131,150 @@ async fn main() -> Result<(), Error> {
         }
     }
 
+    let heartbeat_registry = health::heartbeat::HeartbeatRegistry::new(Duration::from_secs(
+        config.health_check_interval_in_seconds,
+    ));
+
     let (storage_request_sender, storage_request_receiver) =
-        async_channel::bounded::<StorageRequest>(config.storage_requests_boundary);
+        telemetry::traced_channel::bounded::<StorageRequest>(config.storage_requests_boundary);
 
-    match logic::init::initialize(
+    // Shared with the storage, which invalidates the changes applied by other processes.
+    let permission_cache = PermissionCache::new(Duration::from_millis(
+        config.permission_cache_ttl_in_milliseconds,
+    ));
+
+    let logic_dispatchers = match logic::init::initialize(
         config.logic_request_dispatch_instances,
         logic_request_receiver,
-        storage_request_sender,
+        abuse_receiver,
+        storage_request_sender.clone(),
+        permission_cache.clone(),
+        &heartbeat_registry,
     )
     .await
     {
-        Ok(()) => (),
+        Ok(logic_dispatchers) => logic_dispatchers,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::Other,
                 format!("failed to initialize logic: {}", error),
             ));
         }
-    }
+    };
+
+    let (stop_sender, stop_receiver) = tokio::sync::watch::channel(false);
 
-    match storage::init::initialize(
+    let (storage_dispatchers, storage_tasks) = match storage::init::initialize(
         config.storage_request_dispatch_instances,
         storage_request_receiver,
-        config.mongodb_uri
+        config.mongodb_uri,
+        Duration::from_secs(config.organization_retention_in_seconds),
+        Duration::from_secs(config.organization_purge_interval_in_seconds),
+        event_publisher,
+        Duration::from_millis(config.outbox_relay_interval_in_milliseconds),
+        permission_cache,
+        stop_receiver,
+        &heartbeat_registry,
     )
     .await
     {
-        Ok(()) => (),
+        Ok(storage) => storage,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::Other,
                 format!("failed to initialize storage: {}", error),
             ));
         }
+    };
+
+    match health::init::initialize(
+        heartbeat_registry,
+        state_tracker_client,
+        config.readiness_address,
+    )
+    .await
+    {
+        Ok(()) => (),
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("failed to initialize health monitoring: {}", error),
+            ));
+        }
     }
 
-    std::thread::sleep(Duration::MAX);
+    let logic_dispatchers = Arc::new(Mutex::new(logic_dispatchers));
+    let storage_dispatchers = Arc::new(Mutex::new(storage_dispatchers));
+
+    tokio::spawn(reload::run(
+        config_file,
+        flags,
+        raw_config,
+        LiveSettings {
+            logic_dispatchers: logic_dispatchers.clone(),
+            storage_dispatchers: storage_dispatchers.clone(),
+            request_timeouts,
+            rate_limiter,
+        },
+    ));
+
+    shutdown::signal().await?;
+
+    let deadline =
+        tokio::time::Instant::now() + Duration::from_secs(config.shutdown_timeout_in_seconds);
+
+    // Requests received from now on are held unacknowledged by the input elements,
+    // so the broker requeues them once the connection closes.
+    logic_request_sender.close();
+    shutdown::drain("logic", close(&logic_dispatchers), deadline).await;
+
+    storage_request_sender.close();
+    shutdown::drain("storage", close(&storage_dispatchers), deadline).await;
+
+    // The relay publishes the events of the drained requests before returning.
+    if stop_sender.send(true).is_err() {
+        log::warn!("background tasks had already stopped");
+    }
+
+    shutdown::drain("background", storage_tasks, deadline).await;
+
+    if let Some(tracer_provider) = tracer_provider {
+        if let Err(error) = tracer_provider.shutdown() {
+            log::warn!("failed to flush spans: {}", error);
+        }
+    }
+
+    Ok(())
+}
+
+/// Stops the pool from being scaled by a reload, handing over its dispatchers.
+fn close(dispatchers: &Mutex<DispatcherPool>) -> Vec<JoinHandle<()>> {
+    dispatchers
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .close()
+}
+
+async fn report_migrations(mongodb_uri: String) -> Result<(), Error> {
+    let reports = match storage::init::report_migrations(mongodb_uri).await {
+        Ok(reports) => reports,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::Other,
+                format!("failed to report migrations: {}", error),
+            ));
+        }
+    };
+
+    for report in reports {
+        log::info!("{}", report);
+    }
 
     Ok(())
 }
 
-async fn get_api() -> Result<Api, Error> {
-    let api_file = match std::env::args().nth(1) {
+async fn get_api(arguments: &[String]) -> Result<Api, Error> {
+    let api_file = match arguments.first() {
         Some(api_file) => api_file,
         None => {
             return Err(Error::new(ErrorKind::InvalidInput, "no api file provided"));
@@ -126,20 +294,45 @@ async fn get_api() -> Result<Api, Error> {
     Ok(api)
 }
 
-async fn get_api_config() -> Result<cooplan_amqp_api::config::config::Config, Error> {
-    let api_config_file = match std::env::args().nth(2) {
-        Some(config_file) => config_file,
+/// Connects a publisher of its own, with the same connection settings as the api.
+async fn get_event_publisher(api: &Api, raw_config: &Value) -> Result<ConfirmedPublisher, Error> {
+    let connect_config = match raw_config.get("amqp_connect_config").cloned() {
+        Some(connect_config) => connect_config,
         None => {
             return Err(Error::new(
                 ErrorKind::InvalidInput,
-                "no config file provided",
+                "no amqp_connect_config provided",
             ))
         }
     };
 
-    let api_config = match cooplan_amqp_api::config::config::try_read_config(&api_config_file).await
-    {
-        Ok(api_config) => api_config,
+    let connect_config = match serde_json::from_value(connect_config) {
+        Ok(connect_config) => connect_config,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("failed to deserialize amqp_connect_config: {}", error),
+            ))
+        }
+    };
+
+    match api::output::elements::organization_event::publisher(api, connect_config).await {
+        Ok(publisher) => Ok(publisher),
+        Err(error) => Err(Error::new(
+            ErrorKind::Other,
+            format!("failed to initialize event publisher: {}", error),
+        )),
+    }
+}
+
+/// Reads the config file once, along with its overrides, for both the api and the service.
+/// The config is also answered before being deserialized, so reloads can tell what has changed.
+async fn get_configs(
+    config_file: &str,
+    flags: &[String],
+) -> Result<(Value, cooplan_amqp_api::config::config::Config, Config), Error> {
+    let config = match config::source::read(config_file, flags).await {
+        Ok(config) => config,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::Other,
@@ -148,22 +341,18 @@ async fn get_api_config() -> Result<cooplan_amqp_api::config::config::Config, Er
         }
     };
 
-    Ok(api_config)
-}
-
-async fn get_config() -> Result<Config, Error> {
-    let config_file = match std::env::args().nth(2) {
-        Some(config_file) => config_file,
-        None => {
+    let api_config = match serde_json::from_value(config.clone()) {
+        Ok(api_config) => api_config,
+        Err(error) => {
             return Err(Error::new(
-                ErrorKind::InvalidInput,
-                "no config file provided",
-            ))
+                ErrorKind::Other,
+                format!("failed to deserialize api config: {}", error),
+            ));
         }
     };
 
-    let config = match config::config::try_read_config(&config_file).await {
-        Ok(config) => config,
+    let service_config = match config::config::try_from_value(config.clone()) {
+        Ok(service_config) => service_config,
         Err(error) => {
             return Err(Error::new(
                 ErrorKind::Other,
@@ -172,5 +361,5 @@ async fn get_config() -> Result<Config, Error> {
         }
     };
 
-    Ok(config)
+    Ok((config, api_config, service_config))
 }
@@ -0,0 +1,177 @@
+use crate::api::input::rate_limit::RateLimiter;
+use crate::config::config::{self, Config};
+use crate::config::source;
+use crate::deadline::RequestTimeouts;
+use crate::dispatcher_pool::DispatcherPool;
+use serde_json::{Map, Value};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+use tokio::signal::unix::SignalKind;
+
+/// Top level settings which are applied without restarting the service.
+const LIVE_SETTINGS: [&str; 6] = [
+    "logic_request_dispatch_instances",
+    "storage_request_dispatch_instances",
+    "default_request_timeout_in_milliseconds",
+    "request_timeouts_in_milliseconds",
+    "rate_limits",
+    "invitation_lockout",
+];
+
+/// Components of the running service affected by the live settings.
+pub struct LiveSettings {
+    pub logic_dispatchers: Arc<Mutex<DispatcherPool>>,
+    pub storage_dispatchers: Arc<Mutex<DispatcherPool>>,
+    pub request_timeouts: RequestTimeouts,
+    pub rate_limiter: RateLimiter,
+}
+
+impl LiveSettings {
+    fn apply(&self, config: Config) {
+        self.logic_dispatchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .scale(config.logic_request_dispatch_instances as usize);
+
+        self.storage_dispatchers
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .scale(config.storage_request_dispatch_instances as usize);
+
+        self.request_timeouts.update(
+            Duration::from_millis(config.default_request_timeout_in_milliseconds),
+            config
+                .request_timeouts_in_milliseconds
+                .iter()
+                .map(|(action, timeout)| (action.clone(), Duration::from_millis(*timeout)))
+                .collect(),
+        );
+
+        self.rate_limiter
+            .update(config.rate_limits, config.invitation_lockout);
+    }
+}
+
+/// Reads the config again, along with its overrides, every time SIGHUP is received.
+/// Changes of the live settings are applied, while any other change is logged and ignored.
+/// `current` is the config, before being deserialized, the service has been started with.
+pub async fn run(config_file: String, flags: Vec<String>, mut current: Value, live: LiveSettings) {
+    let mut hangup = match tokio::signal::unix::signal(SignalKind::hangup()) {
+        Ok(hangup) => hangup,
+        Err(error) => {
+            log::warn!(
+                "failed to listen for SIGHUP, config will not be reloaded: {}",
+                error
+            );
+            return;
+        }
+    };
+
+    while hangup.recv().await.is_some() {
+        log::info!("received SIGHUP, reloading config");
+
+        current = reload(&config_file, &flags, current, &live).await;
+    }
+}
+
+/// Answers with the config the service is running with after the reload.
+async fn reload(config_file: &str, flags: &[String], current: Value, live: &LiveSettings) -> Value {
+    let reloaded = match source::read(config_file, flags).await {
+        Ok(reloaded) => reloaded,
+        Err(error) => {
+            log::warn!(
+                "failed to reload config, keeping the current one: {}",
+                error
+            );
+            return current;
+        }
+    };
+
+    let config = match config::try_from_value(reloaded.clone()) {
+        Ok(config) => config,
+        Err(error) => {
+            log::warn!(
+                "failed to reload config, keeping the current one: {}",
+                error
+            );
+            return current;
+        }
+    };
+
+    let (applied, ignored): (Vec<String>, Vec<String>) = changed_settings(&current, &reloaded)
+        .into_iter()
+        .partition(|setting| LIVE_SETTINGS.contains(&setting.as_str()));
+
+    for setting in &ignored {
+        log::warn!("ignoring change of '{}', which requires a restart", setting);
+    }
+
+    if applied.is_empty() {
+        log::info!("no live setting has changed");
+        return current;
+    }
+
+    live.apply(config);
+
+    log::info!(
+        "reloaded config, applying changes of {}",
+        applied.join(", ")
+    );
+
+    let mut current = match current {
+        Value::Object(current) => current,
+        _ => Map::new(),
+    };
+
+    for setting in applied {
+        match reloaded.get(&setting) {
+            Some(value) => current.insert(setting, value.clone()),
+            None => current.remove(&setting),
+        };
+    }
+
+    Value::Object(current)
+}
+
+/// Top level settings whose value differs between both configs, including those missing in either.
+fn changed_settings(current: &Value, reloaded: &Value) -> Vec<String> {
+    let empty = Map::new();
+    let current = current.as_object().unwrap_or(&empty);
+    let reloaded = reloaded.as_object().unwrap_or(&empty);
+
+    let mut changed: Vec<String> = current
+        .keys()
+        .chain(reloaded.keys().filter(|key| !current.contains_key(*key)))
+        .filter(|key| current.get(*key) != reloaded.get(*key))
+        .cloned()
+        .collect();
+
+    changed.sort();
+    changed
+}
+
+#[cfg(test)]
+#[test]
+fn detect_changed_settings() {
+    let current = serde_json::json!({
+        "mongodb_uri": "mongodb://localhost:27017",
+        "logic_request_dispatch_instances": 4,
+        "rate_limits": { "organization.create": { "requests": 5, "window_in_seconds": 3600 } }
+    });
+
+    let reloaded = serde_json::json!({
+        "mongodb_uri": "mongodb://localhost:27017",
+        "logic_request_dispatch_instances": 8,
+        "invitation_lockout": { "failures": 1 }
+    });
+
+    assert_eq!(
+        vec![
+            "invitation_lockout".to_string(),
+            "logic_request_dispatch_instances".to_string(),
+            "rate_limits".to_string()
+        ],
+        changed_settings(&current, &reloaded)
+    );
+    assert!(changed_settings(&current, &current).is_empty());
+}
@@ -0,0 +1,66 @@
+use std::io::Error;
+use tokio::signal::unix::SignalKind;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// Resolves once SIGTERM or SIGINT is received.
+pub async fn signal() -> Result<(), Error> {
+    let mut terminate = match tokio::signal::unix::signal(SignalKind::terminate()) {
+        Ok(terminate) => terminate,
+        Err(error) => {
+            return Err(Error::other(format!(
+                "failed to listen for SIGTERM: {}",
+                error
+            )));
+        }
+    };
+
+    tokio::select! {
+        _ = terminate.recv() => log::info!("received SIGTERM"),
+        _ = tokio::signal::ctrl_c() => log::info!("received SIGINT"),
+    }
+
+    Ok(())
+}
+
+/// Waits for the dispatchers of a layer to execute their outstanding requests,
+/// aborting those still running once the deadline is reached.
+/// Returns whether every dispatcher has finished in time.
+pub async fn drain(layer: &str, dispatchers: Vec<JoinHandle<()>>, deadline: Instant) -> bool {
+    let mut drained = true;
+
+    for mut dispatcher in dispatchers {
+        if tokio::time::timeout_at(deadline, &mut dispatcher)
+            .await
+            .is_err()
+        {
+            dispatcher.abort();
+            drained = false;
+        }
+    }
+
+    if drained {
+        log::info!("drained {} requests", layer);
+    } else {
+        log::warn!(
+            "aborted {} requests which could not be drained in time",
+            layer
+        );
+    }
+
+    drained
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn abort_dispatchers_which_exceed_the_deadline() {
+    use std::time::Duration;
+
+    let finishing = tokio::spawn(async {});
+    let stuck = tokio::spawn(tokio::time::sleep(Duration::MAX));
+
+    let deadline = Instant::now() + Duration::from_millis(50);
+
+    assert!(drain("TEST", vec![finishing], deadline).await);
+    assert!(!drain("TEST", vec![stuck], deadline).await);
+}
@@ -0,0 +1,15 @@
+use mongodb::error::{Error, ErrorKind, WriteFailure};
+
+const DUPLICATE_KEY_ERROR_CODE: i32 = 11000;
+
+/// Whether the write was refused by a unique index.
+pub fn is_duplicate_key_error(error: &Error) -> bool {
+    match error.kind.as_ref() {
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) => {
+            write_error.code == DUPLICATE_KEY_ERROR_CODE
+        }
+        // Writes within a transaction report their failures as command errors.
+        ErrorKind::Command(command_error) => command_error.code == DUPLICATE_KEY_ERROR_CODE,
+        _ => false,
+    }
+}
@@ -0,0 +1,70 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Bson};
+use mongodb::{Client, IndexModel};
+use serde::Deserialize;
+
+pub const DATABASE: &str = "cooplan_organization";
+pub const COLLECTION: &str = "audit_record";
+
+#[derive(Debug, Deserialize)]
+pub struct AuditRecord {
+    pub _id: ObjectId,
+    pub actor: String,
+    pub organization_id: String,
+    pub target_user_id: Option<String>,
+    #[serde(default)]
+    pub scope: Option<String>,
+    pub action: String,
+    pub changes: Vec<AuditChange>,
+    /// Unix timestamp, seconds after the UNIX EPOCH
+    pub timestamp: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AuditChange {
+    pub field: String,
+    pub before: Option<Bson>,
+    pub after: Option<Bson>,
+}
+
+impl From<AuditRecord> for logic::elements::audit_record::AuditRecord {
+    fn from(record: AuditRecord) -> Self {
+        logic::elements::audit_record::AuditRecord {
+            actor: record.actor,
+            organization_id: record.organization_id,
+            target_user_id: record.target_user_id,
+            scope: record.scope,
+            action: record.action,
+            changes: record
+                .changes
+                .into_iter()
+                .map(|change| logic::elements::audit_record::AuditChange {
+                    field: change.field,
+                    before: change.before.map(Bson::into_relaxed_extjson),
+                    after: change.after.map(Bson::into_relaxed_extjson),
+                })
+                .collect(),
+            timestamp: record.timestamp,
+        }
+    }
+}
+
+pub async fn initialize(client: &Client) -> Result<(), Error> {
+    let index = IndexModel::builder()
+        .keys(doc! { "organization_id": 1u32, "timestamp": -1i32 })
+        .build();
+
+    match client
+        .database(DATABASE)
+        .collection::<AuditRecord>(COLLECTION)
+        .create_index(index, None)
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => return Err(Error::new(ErrorKind::InternalFailure, error.to_string())),
+    }
+
+    Ok(())
+}
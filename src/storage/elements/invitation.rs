@@ -6,7 +6,7 @@ use mongodb::options::IndexOptions;
 use mongodb::{Client, IndexModel};
 use serde::Deserialize;
 
-pub const DATABASE: &str = "local";
+pub const DATABASE: &str = "cooplan_organization";
 pub const COLLECTION: &str = "invitation";
 
 #[derive(Debug, Deserialize)]
@@ -36,7 +36,7 @@ pub async fn initialize(client: &Client) -> Result<(), Error> {
     let options = IndexOptions::builder().unique(true).build();
 
     let index = IndexModel::builder()
-        .keys(doc! { "code": "text" })
+        .keys(doc! { "code": 1u32 })
         .options(Some(options))
         .build();
 
@@ -1,3 +1,6 @@
+pub mod audit_record;
 pub mod invitation;
 pub mod organization;
+pub mod outbox;
+pub mod schema_migration;
 pub mod user;
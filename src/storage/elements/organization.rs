@@ -8,9 +8,15 @@ use serde::Deserialize;
 
 // Values are used within the integration tests.
 // Therefore, any update must be reflected in the tests.
-pub const DATABASE: &str = "local";
+pub const DATABASE: &str = "cooplan_organization";
 pub const COLLECTION: &str = "organization";
 
+/// Stored as `name_lowercase` alongside the name, so listing by a name prefix
+/// ignores case while still using an index.
+pub fn lowercase_name(name: &str) -> String {
+    name.to_lowercase()
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Organization {
     pub _id: ObjectId,
@@ -19,6 +25,9 @@ pub struct Organization {
     pub address: String,
     pub telephone: String,
     pub permissions: Vec<String>,
+    /// Incremented on every update, in order to detect concurrent modifications.
+    #[serde(default)]
+    pub version: u64,
 }
 
 impl Into<logic::elements::organization::Organization> for Organization {
@@ -30,6 +39,7 @@ impl Into<logic::elements::organization::Organization> for Organization {
             address: self.address,
             telephone: self.telephone,
             permissions: self.permissions,
+            version: self.version,
         }
     }
 }
@@ -39,13 +49,22 @@ pub async fn initialize(client: &Client) -> Result<(), Error> {
 
     let indexes = vec![
         IndexModel::builder()
-            .keys(doc! { "name": "text" })
+            .keys(doc! { "name": 1u32 })
             .options(Some(options.clone()))
             .build(),
         IndexModel::builder()
             .keys(doc! { "telephone": 1u32 })
             .options(Some(options))
             .build(),
+        IndexModel::builder()
+            .keys(doc! { "name_lowercase": 1u32 })
+            .build(),
+        // Soft deleted organizations have a `deleted_at` unix timestamp,
+        // which hides them from every read until they are restored or purged.
+        IndexModel::builder()
+            .keys(doc! { "deleted_at": 1u32 })
+            .options(Some(IndexOptions::builder().sparse(true).build()))
+            .build(),
     ];
 
     match client
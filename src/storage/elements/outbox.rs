@@ -0,0 +1,37 @@
+use crate::error::{Error, ErrorKind};
+use mongodb::bson::{doc, Document};
+use mongodb::{Client, IndexModel};
+use serde::Deserialize;
+
+pub const DATABASE: &str = "cooplan_organization";
+pub const COLLECTION: &str = "outbox";
+
+/// Event waiting to be published, written within the same transaction as its mutation.
+#[derive(Debug, Deserialize)]
+pub struct OutboxEntry {
+    /// Identifier of the event, which consumers use to discard duplicated deliveries.
+    pub _id: String,
+    /// Output element through which the event is published.
+    pub element: String,
+    pub payload: Document,
+    pub attempts: u32,
+}
+
+/// Entries are relayed by ascending `available_at`, the Unix timestamp from which they are relayed.
+pub async fn initialize(client: &Client) -> Result<(), Error> {
+    let index = IndexModel::builder()
+        .keys(doc! { "available_at": 1u32 })
+        .build();
+
+    match client
+        .database(DATABASE)
+        .collection::<OutboxEntry>(COLLECTION)
+        .create_index(index, None)
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => return Err(Error::new(ErrorKind::InternalFailure, error.to_string())),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,39 @@
+use crate::error::{Error, ErrorKind};
+use mongodb::bson::doc;
+use mongodb::options::IndexOptions;
+use mongodb::{Client, IndexModel};
+use serde::{Deserialize, Serialize};
+
+pub const DATABASE: &str = "cooplan_organization";
+pub const COLLECTION: &str = "schema_migrations";
+
+/// Record of a migration which has already been applied.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SchemaMigration {
+    pub version: u32,
+    pub name: String,
+    /// Unix timestamp, seconds after the UNIX EPOCH
+    pub applied_at: u64,
+}
+
+pub async fn initialize(client: &Client) -> Result<(), Error> {
+    // The migration lock lives within the same collection and has no version.
+    let options = IndexOptions::builder().unique(true).sparse(true).build();
+
+    let index = IndexModel::builder()
+        .keys(doc! { "version": 1u32 })
+        .options(Some(options))
+        .build();
+
+    match client
+        .database(DATABASE)
+        .collection::<SchemaMigration>(COLLECTION)
+        .create_index(index, None)
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => return Err(Error::new(ErrorKind::InternalFailure, error.to_string())),
+    }
+
+    Ok(())
+}
@@ -8,7 +8,7 @@ use mongodb::{Client, IndexModel};
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 
-pub const DATABASE: &str = "local";
+pub const DATABASE: &str = "cooplan_organization";
 pub const COLLECTION: &str = "user";
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -16,6 +16,12 @@ pub struct User {
     pub _id: ObjectId,
     pub id: String,
     pub organizations: Vec<UserOrganization>,
+    /// Incremented on every update, in order to detect concurrent modifications.
+    #[serde(default)]
+    pub version: u64,
+    /// Set while the user is blocked within the identity provider.
+    #[serde(default)]
+    pub suspended: bool,
 }
 
 impl Into<logic::elements::user::User> for User {
@@ -23,6 +29,8 @@ impl Into<logic::elements::user::User> for User {
         logic::elements::user::User {
             id: self.id,
             organizations: self.organizations,
+            version: self.version,
+            suspended: self.suspended,
         }
     }
 }
@@ -30,15 +38,21 @@ impl Into<logic::elements::user::User> for User {
 pub async fn initialize(client: &Client) -> Result<(), Error> {
     let options = IndexOptions::builder().unique(true).build();
 
-    let index = IndexModel::builder()
-        .keys(doc! { "id": 1u32 })
-        .options(Some(options))
-        .build();
+    let indexes = vec![
+        IndexModel::builder()
+            .keys(doc! { "id": 1u32 })
+            .options(Some(options))
+            .build(),
+        // Members of an organization are looked up far more often than they change.
+        IndexModel::builder()
+            .keys(doc! { "organizations.organization_id": 1u32 })
+            .build(),
+    ];
 
     match client
         .database(DATABASE)
         .collection::<User>(COLLECTION)
-        .create_index(index, None)
+        .create_indexes(indexes, None)
         .await
     {
         Ok(_) => (),
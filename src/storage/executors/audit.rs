@@ -0,0 +1,167 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic;
+use crate::logic::actions::audit_storage_action::AuditStorageAction;
+use crate::storage::elements::audit_record;
+use crate::storage::elements::audit_record::AuditRecord;
+use cooplan_util::error_handler::ErrorHandler;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, to_bson, Document};
+use mongodb::options::FindOptions;
+use mongodb::{Client, ClientSession};
+use tokio::sync::oneshot::Sender;
+
+pub async fn execute(action: AuditStorageAction, client: &Client) -> Result<(), Error> {
+    match action {
+        AuditStorageAction::Create { record, replier } => create(record, replier, client).await?,
+        AuditStorageAction::FindByOrganizationId {
+            organization_id,
+            page,
+            page_size,
+            replier,
+        } => find_by_organization_id(organization_id, page, page_size, replier, client).await?,
+    }
+
+    Ok(())
+}
+
+async fn create(
+    record: logic::elements::audit_record::AuditRecord,
+    replier: Sender<Result<(), Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let document = match to_document(&record) {
+        Ok(document) => document,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    match client
+        .database(audit_record::DATABASE)
+        .collection::<Document>(audit_record::COLLECTION)
+        .insert_one(document, None)
+        .await
+    {
+        Ok(_) => (),
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to insert audit record: {}", error),
+            ));
+        }
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the records within the session's transaction, so they are only stored
+/// if the mutation they describe is committed.
+pub async fn write(
+    records: &[logic::elements::audit_record::AuditRecord],
+    client: &Client,
+    session: &mut ClientSession,
+) -> Result<(), Error> {
+    if records.is_empty() {
+        return Ok(());
+    }
+
+    let mut documents = Vec::with_capacity(records.len());
+
+    for record in records {
+        documents.push(to_document(record)?);
+    }
+
+    match client
+        .database(audit_record::DATABASE)
+        .collection::<Document>(audit_record::COLLECTION)
+        .insert_many_with_session(documents, None, session)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to insert audit records: {}", error),
+        )),
+    }
+}
+
+fn to_document(record: &logic::elements::audit_record::AuditRecord) -> Result<Document, Error> {
+    let changes = match to_bson(&record.changes) {
+        Ok(changes) => changes,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to serialize audit record changes: {}", error),
+            ));
+        }
+    };
+
+    Ok(doc! {
+        "actor": &record.actor,
+        "organization_id": &record.organization_id,
+        "target_user_id": &record.target_user_id,
+        "scope": &record.scope,
+        "action": &record.action,
+        "changes": changes,
+        "timestamp": record.timestamp as i64,
+    })
+}
+
+async fn find_by_organization_id(
+    organization_id: String,
+    page: u64,
+    page_size: u64,
+    replier: Sender<Result<Vec<logic::elements::audit_record::AuditRecord>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let options = FindOptions::builder()
+        .sort(doc! { "timestamp": -1i32, "_id": -1i32 })
+        .skip(page.saturating_mul(page_size))
+        .limit(page_size as i64)
+        .build();
+
+    let records: Vec<AuditRecord> = match client
+        .database(audit_record::DATABASE)
+        .collection::<AuditRecord>(audit_record::COLLECTION)
+        .find(doc! { "organization_id": organization_id }, options)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(records) => records,
+            Err(error) => {
+                return replier.handle_error(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read audit records: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to find audit records: {}", error),
+            ));
+        }
+    };
+
+    let records = records.into_iter().map(|record| record.into()).collect();
+
+    match replier.send(Ok(records)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
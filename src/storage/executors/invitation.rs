@@ -1,14 +1,26 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::invitation_code_storage_action::InvitationStorageAction;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::DomainEvent;
 use crate::logic::elements::invitation::Invitation;
 use crate::storage;
+use crate::storage::{outbox, transaction};
 use cooplan_util::error_handler::ErrorHandler;
-use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Document};
 use mongodb::Client;
 use tokio::sync::oneshot::Sender;
 
 pub async fn execute(action: InvitationStorageAction, client: &Client) -> Result<(), Error> {
     match action {
+        InvitationStorageAction::Create {
+            invitation,
+            events,
+            audit_records,
+            replier,
+        } => {
+            create(invitation, events, audit_records, replier, client).await?;
+        }
         InvitationStorageAction::Delete { code, replier } => {
             delete(code, replier, client).await?;
         }
@@ -20,6 +32,74 @@ pub async fn execute(action: InvitationStorageAction, client: &Client) -> Result
     Ok(())
 }
 
+async fn create(
+    invitation: Invitation,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<(), Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let organization_id = match ObjectId::parse_str(&invitation.organization_id) {
+        Ok(organization_id) => organization_id,
+        Err(error) => {
+            let error = Error::new(
+                ErrorKind::InvalidArgument,
+                format!("failed to parse organization id: {}", error),
+            )
+            .with_field("organization_id");
+
+            return replier.handle_error(error);
+        }
+    };
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    match client
+        .database(storage::elements::invitation::DATABASE)
+        .collection::<Document>(storage::elements::invitation::COLLECTION)
+        .insert_one_with_session(
+            doc! {
+                "code": invitation.code,
+                "organization_id": organization_id,
+                "permissions": invitation.permissions,
+                "created_at": invitation.created_at as i64,
+                "expires_after": invitation.expires_after as i64,
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(_) => {}
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            let error = Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to insert invitation: {}", error),
+            );
+
+            return replier.handle_error(error);
+        }
+    }
+
+    if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => {}
+        Err(_) => {
+            return Err(Error::new(ErrorKind::StorageFailure, "failed to reply"));
+        }
+    }
+
+    Ok(())
+}
+
 async fn find_by_code(
     code: String,
     replier: Sender<Result<Option<Invitation>, Error>>,
@@ -1,3 +1,4 @@
+pub mod audit;
 pub mod invitation;
 pub mod organization;
 pub mod organization_root;
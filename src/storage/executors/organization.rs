@@ -1,15 +1,29 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_storage_action::OrganizationStorageAction;
+use crate::logic::elements::audit_record::{changes, AuditRecord};
+use crate::logic::elements::domain_event::DomainEvent;
+use crate::logic::elements::organization_listing::{
+    OrganizationCursor, OrganizationFilter, OrganizationSort,
+};
+use crate::storage::duplicate_key::is_duplicate_key_error;
 use crate::storage::elements::organization;
 use crate::storage::elements::organization::Organization;
+use crate::storage::{outbox, transaction};
 use crate::{logic, storage};
 use cooplan_util::error_handler::ErrorHandler;
+use futures::TryStreamExt;
 use mongodb::bson::oid::ObjectId;
-use mongodb::bson::{doc, Bson};
+use mongodb::bson::{doc, Bson, Document, Regex};
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument};
 use mongodb::Client;
+use std::time::{Duration, SystemTime};
 use tokio::sync::oneshot::Sender;
 
-pub async fn execute(action: OrganizationStorageAction, client: &Client) -> Result<(), Error> {
+pub async fn execute(
+    action: OrganizationStorageAction,
+    client: &Client,
+    organization_retention: Duration,
+) -> Result<(), Error> {
     match action {
         OrganizationStorageAction::Create {
             name,
@@ -18,16 +32,62 @@ pub async fn execute(action: OrganizationStorageAction, client: &Client) -> Resu
             telephone,
             replier,
         } => create(name, country, address, telephone, replier, client).await?,
+        OrganizationStorageAction::Update {
+            organization,
+            events,
+            audit_records,
+            replier,
+        } => update(organization, events, audit_records, replier, client).await?,
         OrganizationStorageAction::Delete { id, replier } => delete(id, replier, client).await?,
+        OrganizationStorageAction::SoftDelete {
+            id,
+            version,
+            events,
+            audit_records,
+            replier,
+        } => soft_delete(id, version, events, audit_records, replier, client).await?,
+        OrganizationStorageAction::Restore {
+            id,
+            events,
+            audit_records,
+            replier,
+        } => {
+            restore(
+                id,
+                organization_retention,
+                events,
+                audit_records,
+                replier,
+                client,
+            )
+            .await?
+        }
         OrganizationStorageAction::FindById { id, replier } => {
             find_by_id(id, replier, client).await?
         }
+        OrganizationStorageAction::FindByIds { ids, replier } => {
+            find_by_ids(ids, replier, client).await?
+        }
+        // Soft deleted organizations keep their name and telephone until purged,
+        // as both remain covered by the unique indexes.
         OrganizationStorageAction::FindByName { name, replier } => {
-            find_by_key_and_value("name", &name, replier, client).await?
+            find_one(doc! { "name": &name }, replier, client).await?
         }
         OrganizationStorageAction::FindByTelephone { telephone, replier } => {
-            find_by_key_and_value("telephone", &telephone, replier, client).await?
+            find_one(doc! { "telephone": &telephone }, replier, client).await?
         }
+        OrganizationStorageAction::Search {
+            name,
+            limit,
+            replier,
+        } => search(name, limit, replier, client).await?,
+        OrganizationStorageAction::List {
+            filter,
+            sort,
+            after,
+            limit,
+            replier,
+        } => list(filter, sort, after, limit, replier, client).await?,
         _ => {
             return Err(Error::new(
                 ErrorKind::InvalidArgument,
@@ -53,10 +113,12 @@ async fn create(
         .insert_one(
             doc! {
                 "name": &name,
+                "name_lowercase": storage::elements::organization::lowercase_name(&name),
                 "country": &country,
                 "address": &address,
                 "telephone": &telephone,
-                "permissions": []
+                "permissions": [],
+                "version": 0i64,
             },
             None,
         )
@@ -99,13 +161,15 @@ async fn create(
             }
         },
         Err(error) => {
-            return create_handle_error(
-                replier,
-                Error::new(
+            let error = match duplicate_key_error(&error) {
+                Some(error) => error,
+                None => Error::new(
                     ErrorKind::InternalFailure,
                     format!("failed to insert organization: {}", error),
                 ),
-            )
+            };
+
+            return create_handle_error(replier, error);
         }
     };
 
@@ -116,6 +180,7 @@ async fn create(
         address,
         telephone,
         permissions: Vec::new(),
+        version: 0,
     };
 
     match replier.send(Ok(organization)) {
@@ -145,6 +210,130 @@ fn create_handle_error(
     Err(error)
 }
 
+/// Only succeeds if the stored version matches the organization's version,
+/// so concurrent updates cannot silently overwrite each other.
+async fn update(
+    organization: logic::elements::organization::Organization,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<logic::elements::organization::Organization, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let organization_id = match ObjectId::parse_str(&organization.id) {
+        Ok(organization_id) => organization_id,
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("failed to parse organization id: {}", error),
+            ))
+        }
+    };
+
+    let options = FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build();
+
+    let collection = client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION);
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let updated_organization = match collection
+        .find_one_and_update_with_session(
+            doc! {
+                "_id": organization_id,
+                "version": organization.version as i64,
+                "deleted_at": Bson::Null,
+            },
+            doc! {
+                "$set": {
+                    "name": &organization.name,
+                    "name_lowercase": storage::elements::organization::lowercase_name(&organization.name),
+                    "country": &organization.country,
+                    "address": &organization.address,
+                    "telephone": &organization.telephone,
+                },
+                "$inc": { "version": 1i64 },
+            },
+            options,
+            &mut session,
+        )
+        .await
+    {
+        Ok(optional_organization) => optional_organization,
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            let error = match duplicate_key_error(&error) {
+                Some(error) => error,
+                None => Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to update organization: {}", error),
+                ),
+            };
+
+            return replier.handle_error(error);
+        }
+    };
+
+    let updated_organization = match updated_organization {
+        Some(updated_organization) => {
+            if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await
+            {
+                return replier.handle_error(error);
+            }
+
+            updated_organization
+        }
+        None => {
+            transaction::abort(&mut session).await;
+
+            let error = match collection
+                .count_documents(
+                    doc! { "_id": organization_id, "deleted_at": Bson::Null },
+                    None,
+                )
+                .await
+            {
+                // Deleted organizations are hidden from updates as they are from reads.
+                Ok(0) => Error::new(ErrorKind::OrganizationNotFound, "organization not found"),
+                Ok(_) => Error::new(
+                    ErrorKind::VersionConflict,
+                    format!(
+                        "organization has been modified since version {}",
+                        organization.version
+                    ),
+                )
+                .with_field("version"),
+                Err(error) => Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to find organization: {}", error),
+                ),
+            };
+
+            return replier.handle_error(error);
+        }
+    };
+
+    match replier.send(Ok(updated_organization.into())) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to logic");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 async fn delete(
     id: String,
     replier: Sender<Result<(), Error>>,
@@ -210,6 +399,221 @@ fn delete_handle_error(replier: Sender<Result<(), Error>>, error: Error) -> Resu
     Err(error)
 }
 
+/// Only succeeds if the stored version matches the expected `version`.
+async fn soft_delete(
+    id: String,
+    version: u64,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<(), Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let organization_id = match ObjectId::parse_str(&id) {
+        Ok(organization_id) => organization_id,
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("failed to parse organization id: {}", error),
+            ))
+        }
+    };
+
+    let collection = client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION);
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let modified_count = match collection
+        .update_one_with_session(
+            doc! {
+                "_id": organization_id,
+                "version": version as i64,
+                "deleted_at": Bson::Null,
+            },
+            doc! {
+                "$set": { "deleted_at": unix_timestamp() as i64 },
+                "$inc": { "version": 1i64 },
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(result) => result.modified_count,
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to delete organization: {}", error),
+            ));
+        }
+    };
+
+    if modified_count == 0 {
+        transaction::abort(&mut session).await;
+
+        let error = match collection
+            .count_documents(
+                doc! { "_id": organization_id, "deleted_at": Bson::Null },
+                None,
+            )
+            .await
+        {
+            Ok(0) => Error::new(ErrorKind::OrganizationNotFound, "organization not found"),
+            Ok(_) => Error::new(
+                ErrorKind::VersionConflict,
+                format!("organization has been modified since version {}", version),
+            )
+            .with_field("version"),
+            Err(error) => Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to find organization: {}", error),
+            ),
+        };
+
+        return replier.handle_error(error);
+    }
+
+    if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to logic");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+async fn restore(
+    id: String,
+    organization_retention: Duration,
+    events: Vec<DomainEvent>,
+    mut audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<logic::elements::organization::Organization, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let organization_id = match ObjectId::parse_str(&id) {
+        Ok(organization_id) => organization_id,
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::InvalidArgument,
+                format!("failed to parse organization id: {}", error),
+            ))
+        }
+    };
+
+    let deleted_after = unix_timestamp().saturating_sub(organization_retention.as_secs());
+
+    let options = FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build();
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let organization = match client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION)
+        .find_one_and_update_with_session(
+            doc! {
+                "_id": organization_id,
+                "deleted_at": { "$gte": deleted_after as i64 },
+            },
+            doc! {
+                "$set": { "deleted_at": Bson::Null },
+                "$inc": { "version": 1i64 },
+            },
+            options,
+            &mut session,
+        )
+        .await
+    {
+        Ok(Some(organization)) => organization,
+        Ok(None) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::OrganizationNotFound,
+                "no deleted organization found within the retention period",
+            ));
+        }
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to restore organization: {}", error),
+            ));
+        }
+    };
+
+    let organization: logic::elements::organization::Organization = organization.into();
+
+    for record in &mut audit_records {
+        record.changes = changes(None, Some(&organization));
+    }
+
+    if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(organization)) {
+        Ok(_) => (),
+        Err(_) => {
+            log::error!("failed to send response to logic");
+
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Another organization took the name or telephone after they were validated.
+fn duplicate_key_error(error: &mongodb::error::Error) -> Option<Error> {
+    if !is_duplicate_key_error(error) {
+        return None;
+    }
+
+    let error = if error.to_string().contains("telephone") {
+        Error::new(
+            ErrorKind::TelephoneAlreadyInUse,
+            "telephone is already being used",
+        )
+        .with_field("telephone")
+    } else {
+        Error::new(ErrorKind::NameAlreadyTaken, "name is already being used").with_field("name")
+    };
+
+    Some(error)
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 async fn find_by_id(
     id: String,
     replier: Sender<Result<Option<logic::elements::organization::Organization>, Error>>,
@@ -225,24 +629,83 @@ async fn find_by_id(
         }
     };
 
-    find_by_key_and_value("_id", id, replier, client).await
+    find_one(
+        doc! { "_id": id, "deleted_at": Bson::Null },
+        replier,
+        client,
+    )
+    .await
 }
 
-pub async fn find_by_key_and_value<ValueType: Into<Bson>>(
-    key: &str,
-    value: ValueType,
+async fn find_by_ids(
+    ids: Vec<String>,
+    replier: Sender<Result<Vec<logic::elements::organization::Organization>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    // Ids which cannot belong to any organization are simply not found.
+    let ids: Vec<ObjectId> = ids
+        .iter()
+        .filter_map(|id| ObjectId::parse_str(id).ok())
+        .collect();
+
+    let organizations: Vec<Organization> = match client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION)
+        .find(
+            doc! { "_id": { "$in": ids }, "deleted_at": Bson::Null },
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(organizations) => organizations,
+            Err(error) => {
+                return replier.handle_error(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read organizations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to find organizations by ids: {}", error),
+            ));
+        }
+    };
+
+    let organizations = organizations
+        .into_iter()
+        .map(|organization| organization.into())
+        .collect();
+
+    match replier.send(Ok(organizations)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_one(
+    filter: Document,
     replier: Sender<Result<Option<logic::elements::organization::Organization>, Error>>,
     client: &Client,
 ) -> Result<(), Error> {
     let result: Option<logic::elements::organization::Organization> = match client
         .database(storage::elements::organization::DATABASE)
         .collection::<Organization>(storage::elements::organization::COLLECTION)
-        .find_one(doc! { key: value.into() }, None)
+        .find_one(filter, None)
         .await
     {
         Ok(result) => result.map(|organization| organization.into()),
         Err(error) => {
-            return find_by_key_and_value_handle_error(
+            return find_one_handle_error(
                 replier,
                 Error::new(
                     ErrorKind::InternalFailure,
@@ -267,7 +730,233 @@ pub async fn find_by_key_and_value<ValueType: Into<Bson>>(
     Ok(())
 }
 
-fn find_by_key_and_value_handle_error(
+async fn search(
+    name: Option<String>,
+    limit: u32,
+    replier: Sender<Result<Vec<logic::elements::organization::Organization>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let mut filter = doc! { "deleted_at": Bson::Null };
+
+    if let Some(name) = name {
+        filter.insert(
+            "name",
+            Bson::RegularExpression(Regex {
+                pattern: escape_regex(&name),
+                options: "i".to_string(),
+            }),
+        );
+    }
+
+    let options = FindOptions::builder()
+        .sort(doc! { "name": 1i32 })
+        .limit(limit as i64)
+        .build();
+
+    let organizations: Vec<Organization> = match client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION)
+        .find(filter, options)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(organizations) => organizations,
+            Err(error) => {
+                return replier.handle_error(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read organizations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to search organizations: {}", error),
+            ));
+        }
+    };
+
+    let organizations = organizations
+        .into_iter()
+        .map(|organization| organization.into())
+        .collect();
+
+    match replier.send(Ok(organizations)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+/// Pages through the organizations by their sort key followed by their id, which tells apart
+/// those sharing a name and, being an `ObjectId`, holds their creation time.
+async fn list(
+    filter: OrganizationFilter,
+    sort: OrganizationSort,
+    after: Option<OrganizationCursor>,
+    limit: u32,
+    replier: Sender<Result<Vec<logic::elements::organization::Organization>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let filter = match list_filter(filter, sort, after) {
+        Ok(filter) => filter,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let options = FindOptions::builder()
+        .sort(list_sort(sort))
+        .limit(limit as i64)
+        .build();
+
+    let organizations: Vec<Organization> = match client
+        .database(storage::elements::organization::DATABASE)
+        .collection::<Organization>(storage::elements::organization::COLLECTION)
+        .find(filter, options)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(organizations) => organizations,
+            Err(error) => {
+                return replier.handle_error(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read organizations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to list organizations: {}", error),
+            ));
+        }
+    };
+
+    let organizations = organizations
+        .into_iter()
+        .map(|organization| organization.into())
+        .collect();
+
+    match replier.send(Ok(organizations)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+fn list_filter(
+    filter: OrganizationFilter,
+    sort: OrganizationSort,
+    after: Option<OrganizationCursor>,
+) -> Result<Document, Error> {
+    let mut conditions = vec![doc! { "deleted_at": Bson::Null }];
+
+    if let Some(country) = filter.country {
+        conditions.push(doc! { "country": country });
+    }
+
+    if let Some(name_prefix) = filter.name_prefix {
+        // Anchored and case sensitive, so only the index bounds of the prefix are scanned.
+        conditions.push(doc! {
+            "name_lowercase": Bson::RegularExpression(Regex {
+                pattern: format!(
+                    "^{}",
+                    escape_regex(&storage::elements::organization::lowercase_name(&name_prefix))
+                ),
+                options: String::new(),
+            })
+        });
+    }
+
+    if let Some(created_after) = filter.created_after {
+        conditions.push(doc! { "_id": { "$gte": object_id_at(created_after) } });
+    }
+
+    if let Some(created_before) = filter.created_before {
+        conditions.push(doc! { "_id": { "$lt": object_id_at(created_before.saturating_add(1)) } });
+    }
+
+    if let Some(ids) = filter.ids {
+        // Ids which cannot belong to any organization simply match none.
+        let ids: Vec<ObjectId> = ids
+            .iter()
+            .filter_map(|id| ObjectId::parse_str(id).ok())
+            .collect();
+
+        conditions.push(doc! { "_id": { "$in": ids } });
+    }
+
+    if let Some(after) = after {
+        let id = match ObjectId::parse_str(&after.id) {
+            Ok(id) => id,
+            Err(_) => {
+                return Err(
+                    Error::new(ErrorKind::InvalidArgument, "invalid cursor").with_field("cursor")
+                )
+            }
+        };
+
+        conditions.push(match sort {
+            OrganizationSort::NameAscending => doc! { "$or": [
+                { "name": { "$gt": &after.name } },
+                { "name": &after.name, "_id": { "$gt": id } },
+            ] },
+            OrganizationSort::NameDescending => doc! { "$or": [
+                { "name": { "$lt": &after.name } },
+                { "name": &after.name, "_id": { "$lt": id } },
+            ] },
+            OrganizationSort::CreatedAscending => doc! { "_id": { "$gt": id } },
+            OrganizationSort::CreatedDescending => doc! { "_id": { "$lt": id } },
+        });
+    }
+
+    Ok(doc! { "$and": conditions })
+}
+
+fn list_sort(sort: OrganizationSort) -> Document {
+    match sort {
+        OrganizationSort::NameAscending => doc! { "name": 1i32, "_id": 1i32 },
+        OrganizationSort::NameDescending => doc! { "name": -1i32, "_id": -1i32 },
+        OrganizationSort::CreatedAscending => doc! { "_id": 1i32 },
+        OrganizationSort::CreatedDescending => doc! { "_id": -1i32 },
+    }
+}
+
+/// Lowest id of the documents created at the unix timestamp, in seconds.
+fn object_id_at(timestamp: u64) -> ObjectId {
+    let mut bytes = [0u8; 12];
+    bytes[..4].copy_from_slice(&(timestamp.min(u32::MAX as u64) as u32).to_be_bytes());
+
+    ObjectId::from_bytes(bytes)
+}
+
+/// Matches the text literally within a regular expression.
+fn escape_regex(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for character in text.chars() {
+        if "\\.+*?()|[]{}^$".contains(character) {
+            escaped.push('\\');
+        }
+
+        escaped.push(character);
+    }
+
+    escaped
+}
+
+fn find_one_handle_error(
     replier: Sender<Result<Option<logic::elements::organization::Organization>, Error>>,
     error: Error,
 ) -> Result<(), Error> {
@@ -299,7 +988,7 @@ async fn setup() -> Client {
 #[ignore]
 async fn create_organization_successfully() {
     let client = setup().await;
-    let (replier, receiver) = tokio::sync::oneshot::channel();
+    let (replier, _receiver) = tokio::sync::oneshot::channel();
 
     let result = create(
         "test".to_string(),
@@ -345,8 +1034,58 @@ async fn create_and_delete_organization_successfully() {
     let organization = receiver.await.unwrap().unwrap();
     let (replier, receiver) = tokio::sync::oneshot::channel();
 
-    delete(organization.id, replier, &client).await;
+    delete(organization.id, replier, &client).await.unwrap();
     let delete_result = receiver.await.unwrap();
 
     assert!(delete_result.is_ok());
 }
+
+#[test]
+fn search_names_literally() {
+    assert_eq!("ACME\\. \\(EU\\)", escape_regex("ACME. (EU)"));
+}
+
+#[test]
+fn list_after_the_cursor_in_sort_order() {
+    let after = OrganizationCursor {
+        name: "ACME".to_string(),
+        id: "65a1b2c3d4e5f60718293a4b".to_string(),
+    };
+    let id = ObjectId::parse_str(&after.id).unwrap();
+    let filter = OrganizationFilter {
+        name_prefix: Some("Ac.".to_string()),
+        created_before: Some(1_700_000_000),
+        ..Default::default()
+    };
+
+    let filter = list_filter(filter, OrganizationSort::NameDescending, Some(after)).unwrap();
+    let conditions = filter.get_array("$and").unwrap();
+
+    assert_eq!(4, conditions.len());
+    assert_eq!(
+        Bson::Document(doc! { "name_lowercase": Bson::RegularExpression(Regex {
+            pattern: "^ac\\.".to_string(),
+            options: String::new(),
+        }) }),
+        conditions[1]
+    );
+    assert_eq!(
+        Bson::Document(doc! { "_id": { "$lt": object_id_at(1_700_000_001) } }),
+        conditions[2]
+    );
+    assert_eq!(
+        Bson::Document(doc! { "$or": [
+            { "name": { "$lt": "ACME" } },
+            { "name": "ACME", "_id": { "$lt": id } },
+        ] }),
+        conditions[3]
+    );
+    assert_eq!(
+        doc! { "name": -1i32, "_id": -1i32 },
+        list_sort(OrganizationSort::NameDescending)
+    );
+    assert_eq!(
+        1_700_000_000,
+        object_id_at(1_700_000_000).timestamp().timestamp_millis() / 1000
+    );
+}
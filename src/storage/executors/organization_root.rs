@@ -1,23 +1,29 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic::actions::organization_root_storage_action::OrganizationRootStorageAction;
-use crate::logic::elements::organization_root::OrganizationRoot;
+use crate::logic::elements::organization_root::{
+    OrganizationRoot, OrganizationRootField, OrganizationRootQuery, OrganizationSummary, RootPage,
+};
 use crate::storage::elements::invitation::Invitation;
 use crate::storage::elements::organization::Organization;
 use crate::storage::elements::user::User;
+use crate::storage::transaction;
 use crate::{logic, storage};
 use cooplan_util::error_handler::ErrorHandler;
 use futures::TryStreamExt;
-use mongodb::bson::doc;
 use mongodb::bson::oid::ObjectId;
-use mongodb::Client;
+use mongodb::bson::{doc, Bson, Document};
+use mongodb::options::FindOptions;
+use mongodb::{Client, ClientSession};
+use std::time::SystemTime;
 
 pub async fn execute(action: OrganizationRootStorageAction, client: &Client) -> Result<(), Error> {
     match action {
         OrganizationRootStorageAction::Read {
             organization_id,
+            query,
             replier,
         } => {
-            read(organization_id, replier, client).await?;
+            read(organization_id, query, replier, client).await?;
         }
     }
     Ok(())
@@ -25,6 +31,7 @@ pub async fn execute(action: OrganizationRootStorageAction, client: &Client) ->
 
 async fn read(
     organization_id: String,
+    query: OrganizationRootQuery,
     replier: tokio::sync::oneshot::Sender<Result<OrganizationRoot, Error>>,
     client: &Client,
 ) -> Result<(), Error> {
@@ -40,32 +47,68 @@ async fn read(
         }
     };
 
-    let organization = match get_organization_by_id(&organization_id, client).await {
+    // Every section is read from the same snapshot, so that a join in flight shows up either as
+    // a pending invitation or as a user, yet never as both.
+    let mut session = match transaction::snapshot(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    // Read even if not requested, since missing organizations must not be mistaken for empty ones.
+    let organization = match get_organization_by_id(&organization_id, client, &mut session).await {
         Ok(organization) => organization,
         Err(error) => {
             return replier.handle_error(error);
         }
     };
 
-    let users = match get_users_by_organization_id(&organization_id, client).await {
-        Ok(users) => users,
-        Err(error) => {
-            return replier.handle_error(error);
+    let mut organization_root = OrganizationRoot::default();
+
+    if query.includes(OrganizationRootField::Organization) {
+        organization_root.organization = Some(organization);
+    }
+
+    if query.includes(OrganizationRootField::Users) {
+        match get_users_by_organization_id(&organization_id, &query.users, client, &mut session)
+            .await
+        {
+            Ok((users, next_cursor)) => {
+                organization_root.users = Some(users);
+                organization_root.next_users_cursor = next_cursor;
+            }
+            Err(error) => {
+                return replier.handle_error(error);
+            }
         }
-    };
+    }
 
-    let invitations = match get_invitations_by_organization_id(&organization_id, client).await {
-        Ok(invitations) => invitations,
-        Err(error) => {
-            return replier.handle_error(error);
+    if query.includes(OrganizationRootField::Invitations) {
+        match get_invitations_by_organization_id(
+            &organization_id,
+            &query.invitations,
+            client,
+            &mut session,
+        )
+        .await
+        {
+            Ok((invitations, next_cursor)) => {
+                organization_root.invitations = Some(invitations);
+                organization_root.next_invitations_cursor = next_cursor;
+            }
+            Err(error) => {
+                return replier.handle_error(error);
+            }
         }
-    };
+    }
 
-    let organization_root = OrganizationRoot {
-        organization,
-        users,
-        invitations,
-    };
+    if query.includes(OrganizationRootField::Summary) {
+        match get_summary(&organization_id, client, &mut session).await {
+            Ok(summary) => organization_root.summary = Some(summary),
+            Err(error) => {
+                return replier.handle_error(error);
+            }
+        }
+    }
 
     match replier.send(Ok(organization_root)) {
         Ok(_) => (),
@@ -83,15 +126,18 @@ async fn read(
 async fn get_organization_by_id(
     organization_id: &ObjectId,
     client: &Client,
+    session: &mut ClientSession,
 ) -> Result<logic::elements::organization::Organization, Error> {
     let organization = match client
         .database(storage::elements::organization::DATABASE)
         .collection::<Organization>(storage::elements::organization::COLLECTION)
-        .find_one(
+        .find_one_with_session(
             doc! {
                 "_id": organization_id,
+                "deleted_at": Bson::Null,
             },
             None,
+            session,
         )
         .await
     {
@@ -117,20 +163,23 @@ async fn get_organization_by_id(
 
 async fn get_users_by_organization_id(
     organization_id: &ObjectId,
+    page: &RootPage,
     client: &Client,
-) -> Result<Vec<logic::elements::user::User>, Error> {
+    session: &mut ClientSession,
+) -> Result<(Vec<logic::elements::user::User>, Option<String>), Error> {
+    let filter = page_filter(
+        doc! { "organizations.organization_id": organization_id.to_string() },
+        page,
+        "users_cursor",
+    )?;
+
     let users: Vec<User> = match client
         .database(storage::elements::user::DATABASE)
         .collection::<User>(storage::elements::user::COLLECTION)
-        .find(
-            doc! {
-                "organizations.organization_id": organization_id.to_string(),
-            },
-            None,
-        )
+        .find_with_session(filter, page_options(page), session)
         .await
     {
-        Ok(cursor) => match cursor.try_collect().await {
+        Ok(mut cursor) => match cursor.stream(session).try_collect().await {
             Ok(users) => users,
             Err(error) => {
                 return Err(Error::new(
@@ -147,25 +196,33 @@ async fn get_users_by_organization_id(
         }
     };
 
-    Ok(users.into_iter().map(|user| user.into()).collect())
+    let (users, next_cursor) = split_page(users, page, |user| user._id);
+
+    Ok((
+        users.into_iter().map(|user| user.into()).collect(),
+        next_cursor,
+    ))
 }
 
 async fn get_invitations_by_organization_id(
     organization_id: &ObjectId,
+    page: &RootPage,
     client: &Client,
-) -> Result<Vec<logic::elements::invitation::Invitation>, Error> {
+    session: &mut ClientSession,
+) -> Result<(Vec<logic::elements::invitation::Invitation>, Option<String>), Error> {
+    let filter = page_filter(
+        doc! { "organization_id": organization_id },
+        page,
+        "invitations_cursor",
+    )?;
+
     let invitations: Vec<Invitation> = match client
         .database(storage::elements::invitation::DATABASE)
         .collection::<Invitation>(storage::elements::invitation::COLLECTION)
-        .find(
-            doc! {
-                "organization_id": organization_id,
-            },
-            None,
-        )
+        .find_with_session(filter, page_options(page), session)
         .await
     {
-        Ok(cursor) => match cursor.try_collect().await {
+        Ok(mut cursor) => match cursor.stream(session).try_collect().await {
             Ok(invitations) => invitations,
             Err(error) => {
                 return Err(Error::new(
@@ -182,8 +239,148 @@ async fn get_invitations_by_organization_id(
         }
     };
 
-    Ok(invitations
-        .into_iter()
-        .map(|invitation| invitation.into())
-        .collect())
+    let (invitations, next_cursor) = split_page(invitations, page, |invitation| invitation._id);
+
+    Ok((
+        invitations
+            .into_iter()
+            .map(|invitation| invitation.into())
+            .collect(),
+        next_cursor,
+    ))
+}
+
+async fn get_summary(
+    organization_id: &ObjectId,
+    client: &Client,
+    session: &mut ClientSession,
+) -> Result<OrganizationSummary, Error> {
+    let member_count = match client
+        .database(storage::elements::user::DATABASE)
+        .collection::<User>(storage::elements::user::COLLECTION)
+        .count_documents_with_session(
+            doc! { "organizations.organization_id": organization_id.to_string() },
+            None,
+            session,
+        )
+        .await
+    {
+        Ok(count) => count,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to count users: {}", error),
+            ));
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+
+    let pending_invitation_count = match client
+        .database(storage::elements::invitation::DATABASE)
+        .collection::<Invitation>(storage::elements::invitation::COLLECTION)
+        .count_documents_with_session(
+            doc! {
+                "organization_id": organization_id,
+                "$expr": { "$gt": [{ "$add": ["$created_at", "$expires_after"] }, now] },
+            },
+            None,
+            session,
+        )
+        .await
+    {
+        Ok(count) => count,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                format!("failed to count invitations: {}", error),
+            ));
+        }
+    };
+
+    Ok(OrganizationSummary {
+        member_count,
+        pending_invitation_count,
+    })
+}
+
+/// Restricts the filter to the documents following the cursor, being the id of the last document read.
+fn page_filter(
+    mut filter: Document,
+    page: &RootPage,
+    field: &'static str,
+) -> Result<Document, Error> {
+    if let Some(after) = &page.after {
+        let after = match ObjectId::parse_str(after) {
+            Ok(after) => after,
+            Err(_) => {
+                return Err(
+                    Error::new(ErrorKind::InvalidArgument, "invalid cursor").with_field(field)
+                );
+            }
+        };
+
+        filter.insert("_id", doc! { "$gt": after });
+    }
+
+    Ok(filter)
+}
+
+/// One more than requested tells whether another page follows.
+fn page_options(page: &RootPage) -> FindOptions {
+    FindOptions::builder()
+        .sort(doc! { "_id": 1i32 })
+        .limit(page.size as i64 + 1)
+        .build()
+}
+
+fn split_page<T>(
+    mut documents: Vec<T>,
+    page: &RootPage,
+    id: impl Fn(&T) -> ObjectId,
+) -> (Vec<T>, Option<String>) {
+    if documents.len() <= page.size as usize {
+        return (documents, None);
+    }
+
+    documents.truncate(page.size as usize);
+    let next_cursor = documents.last().map(|document| id(document).to_hex());
+
+    (documents, next_cursor)
+}
+
+#[cfg(test)]
+#[test]
+fn page_after_the_cursor() {
+    let page = RootPage {
+        after: Some("65a1b2c3d4e5f60718293a4b".to_string()),
+        size: 2,
+    };
+    let ids: Vec<ObjectId> = (0..3).map(|_| ObjectId::new()).collect();
+
+    let (documents, next_cursor) = split_page(ids.clone(), &page, |id| *id);
+    assert_eq!(ids[..2], documents[..]);
+    assert_eq!(Some(ids[1].to_hex()), next_cursor);
+
+    let (documents, next_cursor) = split_page(ids[..2].to_vec(), &page, |id| *id);
+    assert_eq!(2, documents.len());
+    assert_eq!(None, next_cursor);
+
+    let filter = page_filter(doc! { "organization_id": 1i32 }, &page, "users_cursor").unwrap();
+    assert_eq!(
+        doc! {
+            "organization_id": 1i32,
+            "_id": { "$gt": ObjectId::parse_str("65a1b2c3d4e5f60718293a4b").unwrap() },
+        },
+        filter
+    );
+
+    let page = RootPage {
+        after: Some("not an id".to_string()),
+        size: 2,
+    };
+    assert!(page_filter(Document::new(), &page, "users_cursor").is_err());
 }
@@ -1,10 +1,16 @@
 use crate::error::{Error, ErrorKind};
 use crate::logic;
 use crate::logic::actions::user_storage_action::UserStorageAction;
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::DomainEvent;
 use crate::logic::elements::user_organization::UserOrganization;
+use crate::logic::organization_permission::OrganizationPermission;
 use crate::storage::elements::user::User;
+use crate::storage::{outbox, transaction};
 use cooplan_util::error_handler::ErrorHandler;
+use futures::TryStreamExt;
 use mongodb::bson::{doc, Bson};
+use mongodb::options::{FindOneAndUpdateOptions, FindOneOptions, ReturnDocument};
 use mongodb::Client;
 use tokio::sync::oneshot::Sender;
 
@@ -13,12 +19,81 @@ pub async fn execute(action: UserStorageAction, client: &Client) -> Result<(), E
         UserStorageAction::Create {
             id,
             organization,
+            events,
+            audit_records,
             replier,
-        } => create(id, organization, replier, client).await?,
+        } => create(id, organization, events, audit_records, replier, client).await?,
+        UserStorageAction::UpdatePermissions {
+            user_id,
+            organization_id,
+            permissions,
+            version,
+            events,
+            audit_records,
+            replier,
+        } => {
+            let organization = UserOrganization {
+                organization_id,
+                permissions,
+            };
+
+            update_permissions(
+                user_id,
+                organization,
+                version,
+                events,
+                audit_records,
+                replier,
+                client,
+            )
+            .await?
+        }
         UserStorageAction::Delete { id, replier } => delete(id, replier, client).await?,
+        UserStorageAction::RemoveMembership {
+            user_id,
+            organization_id,
+            events,
+            audit_records,
+            replier,
+        } => {
+            remove_membership(
+                user_id,
+                organization_id,
+                events,
+                audit_records,
+                replier,
+                client,
+            )
+            .await?
+        }
+        UserStorageAction::SetSuspended {
+            user_id,
+            suspended,
+            events,
+            audit_records,
+            replier,
+        } => set_suspended(user_id, suspended, events, audit_records, replier, client).await?,
+        UserStorageAction::CountByOrganizationId {
+            organization_id,
+            replier,
+        } => count_by_organization_id(organization_id, replier, client).await?,
+        UserStorageAction::CountAdministratorsByOrganizationId {
+            organization_id,
+            replier,
+        } => count_administrators_by_organization_id(organization_id, replier, client).await?,
         UserStorageAction::FindUserById { user_id, replier } => {
             find_user_by_id(user_id, replier, client).await?;
         }
+        UserStorageAction::FindUsersByIds { user_ids, replier } => {
+            find_users_by_ids(user_ids, replier, client).await?;
+        }
+        UserStorageAction::FindMembership {
+            user_id,
+            organization_id,
+            replier,
+        } => {
+            find_membership(user_id, organization_id, replier, client).await?;
+        }
     }
 
     Ok(())
@@ -27,53 +102,49 @@ pub async fn execute(action: UserStorageAction, client: &Client) -> Result<(), E
 async fn create(
     id: String,
     organization: UserOrganization,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
     replier: Sender<Result<logic::elements::user::User, Error>>,
     client: &Client,
 ) -> Result<(), Error> {
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
     let bson_organization: Bson = organization.clone().into();
     match client
         .database(user::DATABASE)
         .collection(user::COLLECTION)
-        .insert_one(
+        .insert_one_with_session(
             doc! {
                 "id": &id,
                 "organizations": [
                     bson_organization
                 ],
+                "version": 0i64,
             },
             None,
+            &mut session,
         )
         .await
     {
         Ok(result) => match result.inserted_id.as_object_id() {
             Some(_) => (),
             None => {
-                return match client
-                    .database(user::DATABASE)
-                    .collection::<User>(user::COLLECTION)
-                    .delete_one(doc! {"id": &id}, None)
-                    .await
-                {
-                    Ok(_) => {
-                        let error = Error::new(
-                            ErrorKind::ProcessReversion,
-                            "failed to get user id, process reverted",
-                        );
-
-                        replier.handle_error(error)
-                    }
-                    Err(error) => {
-                        let error = Error::new(
-                            ErrorKind::InternalFailure,
-                            format!("failed to revert create user: {}", error),
-                        );
+                transaction::abort(&mut session).await;
 
-                        replier.handle_error(error)
-                    }
-                }
+                let error = Error::new(
+                    ErrorKind::ProcessReversion,
+                    "failed to get user id, process reverted",
+                );
+
+                return replier.handle_error(error);
             }
         },
         Err(error) => {
+            transaction::abort(&mut session).await;
+
             let error = Error::new(
                 ErrorKind::InvalidArgument,
                 format!("failed to create user: {}", error),
@@ -83,9 +154,15 @@ async fn create(
         }
     };
 
+    if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await {
+        return replier.handle_error(error);
+    }
+
     let user = logic::elements::user::User {
         id,
         organizations: vec![organization],
+        version: 0,
+        suspended: false,
     };
 
     match replier.send(Ok(user)) {
@@ -101,6 +178,341 @@ async fn create(
     Ok(())
 }
 
+/// Only succeeds if the stored version matches the expected `version`,
+/// so concurrent updates cannot silently overwrite each other.
+async fn update_permissions(
+    user_id: String,
+    organization: UserOrganization,
+    version: u64,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<logic::elements::user::User, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let UserOrganization {
+        organization_id,
+        permissions,
+    } = organization;
+
+    let options = FindOneAndUpdateOptions::builder()
+        .return_document(ReturnDocument::After)
+        .build();
+
+    let collection = client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION);
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let user = match collection
+        .find_one_and_update_with_session(
+            doc! {
+                "id": &user_id,
+                "organizations.organization_id": &organization_id,
+                "version": version as i64,
+            },
+            doc! {
+                "$set": { "organizations.$.permissions": permissions },
+                "$inc": { "version": 1i64 },
+            },
+            options,
+            &mut session,
+        )
+        .await
+    {
+        Ok(optional_user) => optional_user,
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to update user permissions: {}", error),
+            ));
+        }
+    };
+
+    let user = match user {
+        Some(user) => {
+            if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await
+            {
+                return replier.handle_error(error);
+            }
+
+            user
+        }
+        None => {
+            transaction::abort(&mut session).await;
+
+            let error = match collection
+                .count_documents(
+                    doc! {
+                        "id": &user_id,
+                        "organizations.organization_id": &organization_id,
+                    },
+                    None,
+                )
+                .await
+            {
+                Ok(0) => Error::new(
+                    ErrorKind::UserNotFound,
+                    "user not found within the organization",
+                ),
+                Ok(_) => Error::new(
+                    ErrorKind::VersionConflict,
+                    format!("user has been modified since version {}", version),
+                )
+                .with_field("version"),
+                Err(error) => Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to find user: {}", error),
+                ),
+            };
+
+            return replier.handle_error(error);
+        }
+    };
+
+    match replier.send(Ok(user.into())) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn remove_membership(
+    user_id: String,
+    organization_id: String,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<(), Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let collection = client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION);
+
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let modified_count = match collection
+        .update_one_with_session(
+            doc! { "id": &user_id, "organizations.organization_id": &organization_id },
+            doc! {
+                "$pull": { "organizations": { "organization_id": &organization_id } },
+                "$inc": { "version": 1i64 },
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(result) => result.modified_count,
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to remove membership: {}", error),
+            ));
+        }
+    };
+
+    if modified_count == 0 {
+        transaction::abort(&mut session).await;
+
+        return replier.handle_error(Error::new(
+            ErrorKind::UserNotFound,
+            "user not found within the organization",
+        ));
+    }
+
+    // Users without organizations are equivalent to users which have never been stored.
+    if let Err(error) = collection
+        .delete_one_with_session(
+            doc! { "id": &user_id, "organizations": { "$size": 0 } },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        transaction::abort(&mut session).await;
+
+        return replier.handle_error(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to delete user without organizations: {}", error),
+        ));
+    }
+
+    if let Err(error) = outbox::commit(&events, &audit_records, client, &mut session).await {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn set_suspended(
+    user_id: String,
+    suspended: bool,
+    events: Vec<DomainEvent>,
+    audit_records: Vec<AuditRecord>,
+    replier: Sender<Result<(), Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let mut session = match transaction::start(client).await {
+        Ok(session) => session,
+        Err(error) => return replier.handle_error(error),
+    };
+
+    let matched_count = match client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION)
+        .update_one_with_session(
+            doc! { "id": &user_id },
+            doc! {
+                "$set": { "suspended": suspended },
+                "$inc": { "version": 1i64 },
+            },
+            None,
+            &mut session,
+        )
+        .await
+    {
+        Ok(result) => result.matched_count,
+        Err(error) => {
+            transaction::abort(&mut session).await;
+
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to update user suspension: {}", error),
+            ));
+        }
+    };
+
+    let result = if matched_count == 0 {
+        transaction::abort(&mut session).await;
+
+        Ok(())
+    } else {
+        outbox::commit(&events, &audit_records, client, &mut session).await
+    };
+
+    if let Err(error) = result {
+        return replier.handle_error(error);
+    }
+
+    match replier.send(Ok(())) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn count_administrators_by_organization_id(
+    organization_id: String,
+    replier: Sender<Result<u64, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let count = match client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION)
+        .count_documents(
+            doc! {
+                "organizations": {
+                    "$elemMatch": {
+                        "organization_id": organization_id,
+                        "permissions": OrganizationPermission::UpdateUser.to_string(),
+                    }
+                }
+            },
+            None,
+        )
+        .await
+    {
+        Ok(count) => count,
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to count administrators of organization: {}", error),
+            ));
+        }
+    };
+
+    match replier.send(Ok(count)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn count_by_organization_id(
+    organization_id: String,
+    replier: Sender<Result<u64, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let count = match client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION)
+        .count_documents(
+            doc! { "organizations.organization_id": organization_id },
+            None,
+        )
+        .await
+    {
+        Ok(count) => count,
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to count users of organization: {}", error),
+            ));
+        }
+    };
+
+    match replier.send(Ok(count)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
 async fn find_user_by_id(
     user_id: String,
     replier: Sender<Result<Option<logic::elements::user::User>, Error>>,
@@ -138,6 +550,103 @@ async fn find_user_by_id(
     Ok(())
 }
 
+async fn find_membership(
+    user_id: String,
+    organization_id: String,
+    replier: Sender<Result<Option<logic::elements::user::User>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let filter = doc! {
+        "id": user_id,
+        "organizations.organization_id": organization_id,
+    };
+
+    // Only the matching membership is read, however many the user has.
+    let options = FindOneOptions::builder()
+        .projection(doc! {
+            "_id": 1i32,
+            "id": 1i32,
+            "organizations.$": 1i32,
+            "version": 1i32,
+            "suspended": 1i32,
+        })
+        .build();
+
+    let result = match client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION)
+        .find_one(filter, options)
+        .await
+    {
+        Ok(result) => result.map(|user| user.into()),
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to find membership: {}", error),
+            ));
+        }
+    };
+
+    match replier.send(Ok(result)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+async fn find_users_by_ids(
+    user_ids: Vec<String>,
+    replier: Sender<Result<Vec<logic::elements::user::User>, Error>>,
+    client: &Client,
+) -> Result<(), Error> {
+    let filter = doc! {
+        "id": { "$in": user_ids },
+    };
+
+    let users: Vec<User> = match client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION)
+        .find(filter, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(users) => users,
+            Err(error) => {
+                return replier.handle_error(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read users: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return replier.handle_error(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to find users by ids: {}", error),
+            ));
+        }
+    };
+
+    let users = users.into_iter().map(|user| user.into()).collect();
+
+    match replier.send(Ok(users)) {
+        Ok(_) => (),
+        Err(_) => {
+            return Err(Error::new(
+                ErrorKind::InternalFailure,
+                "failed to send response to logic",
+            ))
+        }
+    }
+
+    Ok(())
+}
+
 async fn delete(
     id: String,
     replier: Sender<Result<(), Error>>,
@@ -179,6 +688,7 @@ async fn delete(
 use crate::logic::organization_permission::organization_creator_permissions;
 use crate::storage::elements::user;
 
+#[cfg(test)]
 async fn setup() -> Client {
     let uri = match std::env::var("MONGODB_URI") {
         Ok(uri) => uri,
@@ -209,7 +719,16 @@ async fn correctly_create_new_user_and_delete() {
     let (replier, receiver) =
         tokio::sync::oneshot::channel::<Result<logic::elements::user::User, Error>>();
 
-    create(TEST_ID.to_string(), user_organization, replier, &client).await;
+    create(
+        TEST_ID.to_string(),
+        user_organization,
+        Vec::new(),
+        Vec::new(),
+        replier,
+        &client,
+    )
+    .await
+    .unwrap();
     let create_result = receiver.await.unwrap();
 
     let (replier, receiver) = tokio::sync::oneshot::channel::<Result<(), Error>>();
@@ -1,52 +1,157 @@
+use crate::api::output::publisher::ConfirmedPublisher;
+use crate::dispatcher_pool::{DispatcherPool, Retirement};
 use crate::error::{Error, ErrorKind};
+use crate::health::heartbeat::{Heartbeat, HeartbeatRegistry};
+use crate::logic::permission_cache::PermissionCache;
 use crate::logic::storage_request::StorageRequest;
 use crate::storage;
+use crate::storage::migrations::migration::MigrationReport;
+use crate::supervisor;
+use crate::telemetry::metrics::MongoDbCommandMetrics;
+use crate::telemetry::traced_channel::Traced;
 use async_channel::Receiver;
 use cooplan_mongodb::config::mongodb_config;
-use cooplan_mongodb::connection_manager::MongoDbConnectionManager;
 use mongodb::Client;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 
+/// Answers the dispatchers along with the background tasks, which return once stopped.
+#[allow(clippy::too_many_arguments)]
 pub async fn initialize(
     concurrent_dispatchers: u16,
-    request_receiver: Receiver<StorageRequest>,
-    mongodb_uri: String
-) -> Result<(), Error> {
-    let config = match mongodb_config::try_generate_config(mongodb_uri).await {
-        Ok(config) => config,
-        Err(error) => return Err(Error::new(ErrorKind::AutoConfigFailure, error.message)),
-    };
+    request_receiver: Receiver<Traced<StorageRequest>>,
+    mongodb_uri: String,
+    organization_retention: Duration,
+    organization_purge_interval: Duration,
+    event_publisher: ConfirmedPublisher,
+    outbox_relay_interval: Duration,
+    permission_cache: PermissionCache,
+    stop: watch::Receiver<bool>,
+    heartbeat_registry: &HeartbeatRegistry,
+) -> Result<(DispatcherPool, Vec<JoinHandle<()>>), Error> {
+    let client = &connect(mongodb_uri).await?;
 
-    let mongodb_connection_manager = match MongoDbConnectionManager::try_new(config) {
-        Ok(mongodb_connection_manager) => mongodb_connection_manager,
-        Err(error) => {
-            return Err(Error::new(
-                ErrorKind::StorageFailure,
-                format!("failed to initialize mongodb connection manager: {}", error),
-            ))
-        }
-    };
+    storage::transaction::ensure_replica_set(client).await?;
 
-    let client = mongodb_connection_manager.client();
+    storage::migrations::runner::run(client).await?;
 
     initialize_elements(client).await?;
 
-    for _ in 0..concurrent_dispatchers {
-        let mongodb_request_dispatch =
-            storage::mongodb_request_dispatch::MongoDbRequestDispatch::new(
-                client.clone(),
-                request_receiver.clone(),
-            );
+    let dispatch_client = client.clone();
 
-        tokio::spawn(mongodb_request_dispatch.run());
-    }
+    let mut dispatchers = DispatcherPool::new(
+        "storage",
+        heartbeat_registry.clone(),
+        move |heartbeat, retirement| {
+            let mongodb_request_dispatch =
+                storage::mongodb_request_dispatch::MongoDbRequestDispatch::new(
+                    dispatch_client.clone(),
+                    request_receiver.clone(),
+                    organization_retention,
+                    heartbeat,
+                    retirement,
+                );
 
-    Ok(())
+            tokio::spawn(supervisor::supervise("storage", move || {
+                mongodb_request_dispatch.clone().run()
+            }))
+        },
+    );
+
+    dispatchers.scale(concurrent_dispatchers as usize);
+
+    let background_tasks = vec![
+        tokio::spawn(storage::organization_purge::run(
+            client.clone(),
+            organization_retention,
+            organization_purge_interval,
+            stop.clone(),
+        )),
+        tokio::spawn(event_publisher.clone().monitor(
+            heartbeat_registry.register("amqp_publisher".to_string()),
+            stop.clone(),
+        )),
+        tokio::spawn(storage::outbox_relay::run(
+            client.clone(),
+            event_publisher,
+            outbox_relay_interval,
+            stop.clone(),
+        )),
+        tokio::spawn(storage::user_changes::run(
+            client.clone(),
+            permission_cache,
+            stop,
+        )),
+    ];
+
+    tokio::spawn(storage::mongodb_probe::run(
+        client.clone(),
+        heartbeat_registry.register("mongodb".to_string()),
+    ));
+
+    Ok((dispatchers, background_tasks))
+}
+
+/// Runs a single dispatcher, without the background tasks of the service,
+/// for tools operating on the stored data.
+pub async fn initialize_standalone(
+    request_receiver: Receiver<Traced<StorageRequest>>,
+    mongodb_uri: String,
+    organization_retention: Duration,
+    heartbeat: Heartbeat,
+) -> Result<JoinHandle<()>, Error> {
+    let client = connect(mongodb_uri).await?;
+
+    storage::transaction::ensure_replica_set(&client).await?;
+
+    storage::migrations::runner::run(&client).await?;
+
+    initialize_elements(&client).await?;
+
+    let mongodb_request_dispatch = storage::mongodb_request_dispatch::MongoDbRequestDispatch::new(
+        client,
+        request_receiver,
+        organization_retention,
+        heartbeat,
+        Retirement::default(),
+    );
+
+    Ok(tokio::spawn(mongodb_request_dispatch.run()))
+}
+
+/// Reports the pending migrations without applying them.
+pub async fn report_migrations(mongodb_uri: String) -> Result<Vec<MigrationReport>, Error> {
+    let client = connect(mongodb_uri).await?;
+
+    storage::migrations::runner::dry_run(&client).await
+}
+
+async fn connect(mongodb_uri: String) -> Result<Client, Error> {
+    let config = match mongodb_config::try_generate_config(mongodb_uri).await {
+        Ok(config) => config,
+        Err(error) => return Err(Error::new(ErrorKind::AutoConfigFailure, error.message)),
+    };
+
+    let mut client_options = config.client_options();
+    client_options.command_event_handler = Some(Arc::new(MongoDbCommandMetrics));
+
+    match Client::with_options(client_options) {
+        Ok(client) => Ok(client),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to initialize mongodb client: {}", error),
+        )),
+    }
 }
 
 async fn initialize_elements(client: &Client) -> Result<(), Error> {
     storage::elements::organization::initialize(client).await?;
     storage::elements::user::initialize(client).await?;
     storage::elements::invitation::initialize(client).await?;
+    storage::elements::audit_record::initialize(client).await?;
+    storage::elements::outbox::initialize(client).await?;
 
     Ok(())
 }
@@ -0,0 +1,213 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::schema_migration::SchemaMigration;
+use crate::storage::elements::{audit_record, invitation, organization, schema_migration, user};
+use crate::storage::migrations;
+use crate::storage::migrations::migration::MigrationFuture;
+use crate::storage::migrations::LEGACY_DATABASE;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::Client;
+use std::time::SystemTime;
+
+pub const VERSION: u32 = 5;
+
+/// Left within the legacy `schema_migrations` once its data has been moved.
+const MOVED_MARKER_ID: &str = "moved";
+
+/// The `local` database is neither replicated nor writable within transactions,
+/// so its collections are copied into the dedicated database.
+const MOVED_COLLECTIONS: &[(&str, &str)] = &[
+    (organization::DATABASE, organization::COLLECTION),
+    (user::DATABASE, user::COLLECTION),
+    (invitation::DATABASE, invitation::COLLECTION),
+    (audit_record::DATABASE, audit_record::COLLECTION),
+];
+
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        if is_marked(client).await? {
+            return Ok(0);
+        }
+
+        let mut pending = 0u64;
+
+        for (database, collection) in MOVED_COLLECTIONS {
+            if count(client, database, collection).await? == 0 {
+                pending += count(client, LEGACY_DATABASE, collection).await?;
+            }
+        }
+
+        Ok(pending)
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        if is_marked(client).await? {
+            return Ok(0);
+        }
+
+        let legacy_versions = get_legacy_applied_versions(client).await?;
+
+        let mut has_legacy_data = false;
+        let mut moved = 0u64;
+
+        for (database, collection) in MOVED_COLLECTIONS {
+            let legacy = count(client, LEGACY_DATABASE, collection).await?;
+
+            if legacy == 0 {
+                continue;
+            }
+
+            has_legacy_data = true;
+
+            // Never overwrite data which has already been written to the dedicated database.
+            if count(client, database, collection).await? > 0 {
+                log::warn!(
+                    "skipping the move of '{}', since '{}' already contains it",
+                    collection,
+                    database
+                );
+                continue;
+            }
+
+            match client
+                .database(LEGACY_DATABASE)
+                .collection::<Document>(collection)
+                .aggregate(
+                    vec![doc! { "$out": { "db": *database, "coll": *collection } }],
+                    None,
+                )
+                .await
+            {
+                Ok(cursor) => match cursor.try_collect::<Vec<Document>>().await {
+                    Ok(_) => moved += legacy,
+                    Err(error) => {
+                        return Err(Error::new(
+                            ErrorKind::StorageFailure,
+                            format!("failed to move '{}': {}", collection, error),
+                        ));
+                    }
+                },
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to move '{}': {}", collection, error),
+                    ));
+                }
+            }
+        }
+
+        // The earlier migrations have already run against the dedicated database, which was empty,
+        // so the moved data only went through those recorded within the legacy one.
+        if has_legacy_data {
+            for migration in migrations::migrations() {
+                if migration.version >= VERSION || legacy_versions.contains(&migration.version) {
+                    continue;
+                }
+
+                if let Err(error) = (migration.apply)(client).await {
+                    return Err(Error::new(
+                        error.kind(),
+                        format!(
+                            "failed to apply migration {} '{}' to the moved data: {}",
+                            migration.version, migration.name, error
+                        ),
+                    ));
+                }
+            }
+        }
+
+        mark(client).await?;
+
+        Ok(moved)
+    })
+}
+
+async fn get_legacy_applied_versions(client: &Client) -> Result<Vec<u32>, Error> {
+    let applied: Vec<SchemaMigration> = match client
+        .database(LEGACY_DATABASE)
+        .collection::<SchemaMigration>(schema_migration::COLLECTION)
+        .find(doc! { "version": { "$exists": true } }, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(applied) => applied,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read legacy applied migrations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to read legacy applied migrations: {}", error),
+            ));
+        }
+    };
+
+    Ok(applied
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect())
+}
+
+async fn is_marked(client: &Client) -> Result<bool, Error> {
+    match client
+        .database(LEGACY_DATABASE)
+        .collection::<Document>(schema_migration::COLLECTION)
+        .count_documents(doc! { "_id": MOVED_MARKER_ID }, None)
+        .await
+    {
+        Ok(count) => Ok(count > 0),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to read the legacy move marker: {}", error),
+        )),
+    }
+}
+
+/// The legacy data is kept as a backup, yet never moved again.
+async fn mark(client: &Client) -> Result<(), Error> {
+    let moved_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    match client
+        .database(LEGACY_DATABASE)
+        .collection::<Document>(schema_migration::COLLECTION)
+        .insert_one(
+            doc! {
+                "_id": MOVED_MARKER_ID,
+                "database": organization::DATABASE,
+                "moved_at": moved_at as i64,
+            },
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to mark the legacy data as moved: {}", error),
+        )),
+    }
+}
+
+async fn count(client: &Client, database: &str, collection: &str) -> Result<u64, Error> {
+    match client
+        .database(database)
+        .collection::<Document>(collection)
+        .count_documents(None, None)
+        .await
+    {
+        Ok(count) => Ok(count),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to count documents of '{}': {}", collection, error),
+        )),
+    }
+}
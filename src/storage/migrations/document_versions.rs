@@ -0,0 +1,70 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::{organization, user};
+use crate::storage::migrations::migration::MigrationFuture;
+use mongodb::bson::{doc, Document};
+use mongodb::Client;
+
+/// Organizations and users created before optimistic concurrency control have no version,
+/// which would make every versioned update of them fail.
+const VERSIONED_COLLECTIONS: &[(&str, &str)] = &[
+    (organization::DATABASE, organization::COLLECTION),
+    (user::DATABASE, user::COLLECTION),
+];
+
+fn unversioned_filter() -> Document {
+    doc! { "version": { "$exists": false } }
+}
+
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let mut unversioned = 0u64;
+
+        for (database, collection) in VERSIONED_COLLECTIONS {
+            match client
+                .database(database)
+                .collection::<Document>(collection)
+                .count_documents(unversioned_filter(), None)
+                .await
+            {
+                Ok(count) => unversioned += count,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to count unversioned documents: {}", error),
+                    ));
+                }
+            }
+        }
+
+        Ok(unversioned)
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let mut versioned = 0u64;
+
+        for (database, collection) in VERSIONED_COLLECTIONS {
+            match client
+                .database(database)
+                .collection::<Document>(collection)
+                .update_many(
+                    unversioned_filter(),
+                    doc! { "$set": { "version": 0i64 } },
+                    None,
+                )
+                .await
+            {
+                Ok(result) => versioned += result.modified_count,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to version documents: {}", error),
+                    ));
+                }
+            }
+        }
+
+        Ok(versioned)
+    })
+}
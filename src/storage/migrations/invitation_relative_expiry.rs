@@ -0,0 +1,49 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::invitation;
+use crate::storage::migrations::migration::MigrationFuture;
+use mongodb::bson::{doc, Document};
+use mongodb::Client;
+
+/// `expires_after` is a duration in seconds, yet some invitations were stored with an
+/// absolute timestamp instead, which made them valid for decades.
+fn absolute_expiry_filter() -> Document {
+    doc! { "$expr": { "$gte": ["$expires_after", "$created_at"] } }
+}
+
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        match client
+            .database(invitation::DATABASE)
+            .collection::<Document>(invitation::COLLECTION)
+            .count_documents(absolute_expiry_filter(), None)
+            .await
+        {
+            Ok(count) => Ok(count),
+            Err(error) => Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to count invitations: {}", error),
+            )),
+        }
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let update = vec![doc! {
+            "$set": { "expires_after": { "$subtract": ["$expires_after", "$created_at"] } }
+        }];
+
+        match client
+            .database(invitation::DATABASE)
+            .collection::<Document>(invitation::COLLECTION)
+            .update_many(absolute_expiry_filter(), update, None)
+            .await
+        {
+            Ok(result) => Ok(result.modified_count),
+            Err(error) => Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to update invitations: {}", error),
+            )),
+        }
+    })
+}
@@ -0,0 +1,176 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::duplicate_key::is_duplicate_key_error;
+use crate::storage::elements::schema_migration::{COLLECTION, DATABASE};
+use mongodb::bson::oid::ObjectId;
+use mongodb::bson::{doc, Document};
+use mongodb::Client;
+use std::time::{Duration, SystemTime};
+use tokio::task::JoinHandle;
+
+const LOCK_ID: &str = "lock";
+const RETRY_INTERVAL: Duration = Duration::from_secs(1);
+/// Locks older than this are considered abandoned by a crashed instance.
+const STALE_AFTER: Duration = Duration::from_secs(600);
+/// Held locks are refreshed well within `STALE_AFTER`, so long migrations keep them.
+const RENEW_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Guarantees that only one instance of the service runs migrations at a time.
+pub struct MigrationLock {
+    client: Client,
+    owner: String,
+    renewal: JoinHandle<()>,
+}
+
+/// Waits until the lock is acquired or the timeout is exceeded.
+pub async fn acquire(client: &Client, timeout: Duration) -> Result<MigrationLock, Error> {
+    let owner = ObjectId::new().to_hex();
+    let started_at = SystemTime::now();
+
+    loop {
+        release_if_stale(client).await?;
+
+        match client
+            .database(DATABASE)
+            .collection::<Document>(COLLECTION)
+            .insert_one(
+                doc! {
+                    "_id": LOCK_ID,
+                    "owner": &owner,
+                    "locked_at": unix_timestamp() as i64,
+                },
+                None,
+            )
+            .await
+        {
+            Ok(_) => {
+                let renewal = tokio::spawn(renew_periodically(client.clone(), owner.clone()));
+
+                return Ok(MigrationLock {
+                    client: client.clone(),
+                    owner,
+                    renewal,
+                });
+            }
+            Err(error) => {
+                if !is_duplicate_key_error(&error) {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to acquire migration lock: {}", error),
+                    ));
+                }
+            }
+        }
+
+        if started_at.elapsed().unwrap_or_default() >= timeout {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                "timed out waiting for the migration lock",
+            ));
+        }
+
+        log::info!("waiting for another instance to finish the migrations");
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+impl MigrationLock {
+    /// Refreshes the lock, failing if it has been taken over by another instance,
+    /// so nothing else is migrated concurrently.
+    pub async fn ensure_held(&self) -> Result<(), Error> {
+        if renew(&self.client, &self.owner).await? {
+            Ok(())
+        } else {
+            Err(Error::new(
+                ErrorKind::StorageFailure,
+                "migration lock has been taken over by another instance",
+            ))
+        }
+    }
+
+    pub async fn release(self) -> Result<(), Error> {
+        self.renewal.abort();
+
+        match self
+            .client
+            .database(DATABASE)
+            .collection::<Document>(COLLECTION)
+            .delete_one(doc! { "_id": LOCK_ID, "owner": &self.owner }, None)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) => Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to release migration lock: {}", error),
+            )),
+        }
+    }
+}
+
+/// Returns whether the lock is still held by the owner.
+async fn renew(client: &Client, owner: &str) -> Result<bool, Error> {
+    match client
+        .database(DATABASE)
+        .collection::<Document>(COLLECTION)
+        .update_one(
+            doc! { "_id": LOCK_ID, "owner": owner },
+            doc! { "$set": { "locked_at": unix_timestamp() as i64 } },
+            None,
+        )
+        .await
+    {
+        Ok(result) => Ok(result.matched_count > 0),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to renew migration lock: {}", error),
+        )),
+    }
+}
+
+async fn renew_periodically(client: Client, owner: String) {
+    loop {
+        tokio::time::sleep(RENEW_INTERVAL).await;
+
+        match renew(&client, &owner).await {
+            Ok(true) => (),
+            Ok(false) => {
+                log::error!("migration lock has been taken over by another instance");
+                return;
+            }
+            // Retried on the next interval, which is still far from making the lock stale.
+            Err(error) => log::warn!("{}", error),
+        }
+    }
+}
+
+async fn release_if_stale(client: &Client) -> Result<(), Error> {
+    let stale_before = unix_timestamp().saturating_sub(STALE_AFTER.as_secs());
+
+    match client
+        .database(DATABASE)
+        .collection::<Document>(COLLECTION)
+        .delete_one(
+            doc! { "_id": LOCK_ID, "locked_at": { "$lt": stale_before as i64 } },
+            None,
+        )
+        .await
+    {
+        Ok(result) => {
+            if result.deleted_count > 0 {
+                log::warn!("released stale migration lock");
+            }
+
+            Ok(())
+        }
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to release stale migration lock: {}", error),
+        )),
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
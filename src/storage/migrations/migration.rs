@@ -0,0 +1,45 @@
+use crate::error::Error;
+use mongodb::Client;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+
+pub type MigrationFuture<'a> = Pin<Box<dyn Future<Output = Result<u64, Error>> + Send + 'a>>;
+
+/// Ordered change of the stored data.
+/// Both functions must be idempotent, so running them over migrated data is a no-op.
+pub struct Migration {
+    pub version: u32,
+    pub name: &'static str,
+    /// Counts the documents or indexes which would be changed by `apply`, without changing them.
+    pub inspect: fn(&Client) -> MigrationFuture<'_>,
+    /// Changes the stored data, returning the amount of changed documents or indexes.
+    pub apply: fn(&Client) -> MigrationFuture<'_>,
+}
+
+/// Outcome of a migration, either inspected through a dry-run or applied.
+#[derive(Debug)]
+pub struct MigrationReport {
+    pub version: u32,
+    pub name: String,
+    pub already_applied: bool,
+    pub affected: u64,
+}
+
+impl fmt::Display for MigrationReport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.already_applied {
+            write!(
+                f,
+                "migration {} '{}': already applied",
+                self.version, self.name
+            )
+        } else {
+            write!(
+                f,
+                "migration {} '{}': {} affected",
+                self.version, self.name, self.affected
+            )
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::storage::migrations::migration::Migration;
+
+mod dedicated_database;
+mod document_versions;
+mod invitation_relative_expiry;
+pub mod lock;
+pub mod migration;
+mod organization_lowercase_names;
+mod organization_telephone_normalization;
+pub mod runner;
+mod unique_ascending_indexes;
+
+/// Database which stored every collection, including `schema_migrations`, before the dedicated one.
+/// Only read by the migration moving its data, which applies the earlier migrations it lacks.
+pub const LEGACY_DATABASE: &str = "local";
+
+/// Every known migration, ordered by version.
+/// New migrations must be appended with a greater version than the latest one.
+pub fn migrations() -> Vec<Migration> {
+    vec![
+        Migration {
+            version: 1,
+            name: "organization_telephone_normalization",
+            inspect: organization_telephone_normalization::inspect,
+            apply: organization_telephone_normalization::apply,
+        },
+        Migration {
+            version: 2,
+            name: "unique_ascending_indexes",
+            inspect: unique_ascending_indexes::inspect,
+            apply: unique_ascending_indexes::apply,
+        },
+        Migration {
+            version: 3,
+            name: "invitation_relative_expiry",
+            inspect: invitation_relative_expiry::inspect,
+            apply: invitation_relative_expiry::apply,
+        },
+        Migration {
+            version: 4,
+            name: "document_versions",
+            inspect: document_versions::inspect,
+            apply: document_versions::apply,
+        },
+        Migration {
+            version: dedicated_database::VERSION,
+            name: "dedicated_database",
+            inspect: dedicated_database::inspect,
+            apply: dedicated_database::apply,
+        },
+        Migration {
+            version: 6,
+            name: "organization_lowercase_names",
+            inspect: organization_lowercase_names::inspect,
+            apply: organization_lowercase_names::apply,
+        },
+    ]
+}
+
+#[cfg(test)]
+#[test]
+fn migration_versions_are_strictly_increasing() {
+    let migrations = migrations();
+
+    for pair in migrations.windows(2) {
+        assert!(pair[0].version < pair[1].version);
+    }
+}
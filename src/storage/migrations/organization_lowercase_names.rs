@@ -0,0 +1,88 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::organization;
+use crate::storage::elements::organization::{lowercase_name, Organization};
+use crate::storage::migrations::migration::MigrationFuture;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, Document};
+use mongodb::Client;
+
+/// Listing by a name prefix matches the lowercase name, which organizations created
+/// before it was stored lack. It is computed here rather than with `$toLower`,
+/// which only lowercases ASCII letters.
+fn unlowercased_filter() -> Document {
+    doc! { "name_lowercase": { "$exists": false } }
+}
+
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        match client
+            .database(organization::DATABASE)
+            .collection::<Organization>(organization::COLLECTION)
+            .count_documents(unlowercased_filter(), None)
+            .await
+        {
+            Ok(count) => Ok(count),
+            Err(error) => Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!(
+                    "failed to count organizations without a lowercase name: {}",
+                    error
+                ),
+            )),
+        }
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let collection = client
+            .database(organization::DATABASE)
+            .collection::<Organization>(organization::COLLECTION);
+
+        let organizations: Vec<Organization> =
+            match collection.find(unlowercased_filter(), None).await {
+                Ok(cursor) => match cursor.try_collect().await {
+                    Ok(organizations) => organizations,
+                    Err(error) => {
+                        return Err(Error::new(
+                            ErrorKind::StorageFailure,
+                            format!("failed to read organizations: {}", error),
+                        ));
+                    }
+                },
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to read organizations: {}", error),
+                    ));
+                }
+            };
+
+        let mut lowercased = 0u64;
+
+        for organization in organizations {
+            // Matching the name as well leaves organizations renamed meanwhile, which already have it.
+            match collection
+                .update_one(
+                    doc! { "_id": organization._id, "name": &organization.name },
+                    doc! { "$set": { "name_lowercase": lowercase_name(&organization.name) } },
+                    None,
+                )
+                .await
+            {
+                Ok(result) => lowercased += result.modified_count,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!(
+                            "failed to store the lowercase name of organization '{}': {}",
+                            organization._id, error
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(lowercased)
+    })
+}
@@ -0,0 +1,164 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::validation::telephone::normalize_telephone;
+use crate::storage::elements::organization;
+use crate::storage::elements::organization::Organization;
+use crate::storage::migrations::migration::MigrationFuture;
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::Client;
+use std::collections::BTreeMap;
+
+/// Telephones were stored as typed by the user, therefore the same telephone written
+/// with different separators escaped the uniqueness check.
+/// Such duplicates are reported and the migration left unapplied until they are resolved.
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let pending = find_unnormalized_telephones(client).await?;
+
+        Ok(pending.len() as u64)
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let pending = find_unnormalized_telephones(client).await?;
+
+        for (organization_id, telephone) in &pending {
+            match client
+                .database(organization::DATABASE)
+                .collection::<Organization>(organization::COLLECTION)
+                .update_one(
+                    doc! { "_id": organization_id },
+                    doc! { "$set": { "telephone": telephone } },
+                    None,
+                )
+                .await
+            {
+                Ok(_) => (),
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!(
+                            "failed to normalize telephone of organization '{}': {}",
+                            organization_id, error
+                        ),
+                    ));
+                }
+            }
+        }
+
+        Ok(pending.len() as u64)
+    })
+}
+
+async fn find_unnormalized_telephones(client: &Client) -> Result<Vec<(ObjectId, String)>, Error> {
+    let organizations: Vec<Organization> = match client
+        .database(organization::DATABASE)
+        .collection::<Organization>(organization::COLLECTION)
+        .find(None, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(organizations) => organizations,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read organizations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to read organizations: {}", error),
+            ));
+        }
+    };
+
+    pending_telephones(organizations)
+}
+
+/// Fails if two organizations would share the same telephone once normalized.
+fn pending_telephones(organizations: Vec<Organization>) -> Result<Vec<(ObjectId, String)>, Error> {
+    let mut telephones: BTreeMap<String, Vec<ObjectId>> = BTreeMap::new();
+    let mut pending = Vec::new();
+
+    for organization in organizations {
+        let telephone = match normalize_telephone(&organization.telephone) {
+            Some(telephone) => telephone,
+            None => organization.telephone.clone(),
+        };
+
+        if telephone != organization.telephone {
+            pending.push((organization._id, telephone.clone()));
+        }
+
+        telephones
+            .entry(telephone)
+            .or_default()
+            .push(organization._id);
+    }
+
+    // Normalizing would break the unique index, so the conflicts are left to be resolved by hand.
+    let conflicts: Vec<String> = telephones
+        .into_iter()
+        .filter(|(_, organization_ids)| organization_ids.len() > 1)
+        .map(|(telephone, organization_ids)| {
+            let organization_ids: Vec<String> = organization_ids
+                .iter()
+                .map(|organization_id| organization_id.to_hex())
+                .collect();
+
+            format!("'{}' ({})", telephone, organization_ids.join(", "))
+        })
+        .collect();
+
+    if !conflicts.is_empty() {
+        return Err(Error::new(
+            ErrorKind::TelephoneAlreadyInUse,
+            format!(
+                "organizations share the same telephone once normalized, change all but one of them \
+                before migrating: {}",
+                conflicts.join("; ")
+            ),
+        ));
+    }
+
+    Ok(pending)
+}
+
+#[cfg(test)]
+fn organization(telephone: &str) -> Organization {
+    Organization {
+        _id: ObjectId::new(),
+        name: telephone.to_string(),
+        country: "RO".to_string(),
+        address: "ADDRESS".to_string(),
+        telephone: telephone.to_string(),
+        permissions: Vec::new(),
+        version: 0,
+    }
+}
+
+#[test]
+fn refuse_telephones_colliding_once_normalized() {
+    let pending = pending_telephones(vec![
+        organization("+40 753 313 640"),
+        organization("+40712345678"),
+    ])
+    .unwrap();
+
+    assert_eq!(1, pending.len());
+    assert_eq!("+40753313640", pending[0].1);
+
+    let error = pending_telephones(vec![
+        organization("+40 753 313 640"),
+        organization("+40-753-313-640"),
+        organization("+40712345678"),
+    ])
+    .unwrap_err();
+
+    assert_eq!(ErrorKind::TelephoneAlreadyInUse, error.kind);
+    assert!(error.message.contains("'+40753313640'"));
+}
@@ -0,0 +1,172 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::schema_migration;
+use crate::storage::elements::schema_migration::SchemaMigration;
+use crate::storage::migrations;
+use crate::storage::migrations::lock;
+use crate::storage::migrations::lock::MigrationLock;
+use crate::storage::migrations::migration::{Migration, MigrationReport};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::Client;
+use std::time::{Duration, SystemTime};
+
+const LOCK_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Applies every pending migration while holding the migration lock.
+pub async fn run(client: &Client) -> Result<Vec<MigrationReport>, Error> {
+    schema_migration::initialize(client).await?;
+
+    let lock = lock::acquire(client, LOCK_TIMEOUT).await?;
+
+    let result = apply_pending(client, &lock).await;
+
+    match lock.release().await {
+        Ok(_) => (),
+        Err(error) => {
+            if result.is_ok() {
+                return Err(error);
+            }
+
+            log::error!("{}", error);
+        }
+    }
+
+    result
+}
+
+/// Reports what `run` would change, without changing anything.
+pub async fn dry_run(client: &Client) -> Result<Vec<MigrationReport>, Error> {
+    let applied_versions = get_applied_versions(client).await?;
+    let mut reports = Vec::new();
+
+    for migration in migrations::migrations() {
+        if applied_versions.contains(&migration.version) {
+            reports.push(already_applied_report(&migration));
+            continue;
+        }
+
+        let affected = (migration.inspect)(client).await?;
+
+        reports.push(MigrationReport {
+            version: migration.version,
+            name: migration.name.to_string(),
+            already_applied: false,
+            affected,
+        });
+    }
+
+    Ok(reports)
+}
+
+async fn apply_pending(
+    client: &Client,
+    lock: &MigrationLock,
+) -> Result<Vec<MigrationReport>, Error> {
+    // Read after acquiring the lock, since another instance may have just finished.
+    let applied_versions = get_applied_versions(client).await?;
+    let mut reports = Vec::new();
+
+    for migration in migrations::migrations() {
+        if applied_versions.contains(&migration.version) {
+            reports.push(already_applied_report(&migration));
+            continue;
+        }
+
+        lock.ensure_held().await?;
+
+        let affected = match (migration.apply)(client).await {
+            Ok(affected) => affected,
+            Err(error) => {
+                return Err(Error::new(
+                    error.kind(),
+                    format!(
+                        "failed to apply migration {} '{}': {}",
+                        migration.version, migration.name, error
+                    ),
+                ));
+            }
+        };
+
+        record_applied(&migration, client).await?;
+
+        let report = MigrationReport {
+            version: migration.version,
+            name: migration.name.to_string(),
+            already_applied: false,
+            affected,
+        };
+
+        log::info!("{}", report);
+        reports.push(report);
+    }
+
+    Ok(reports)
+}
+
+fn already_applied_report(migration: &Migration) -> MigrationReport {
+    MigrationReport {
+        version: migration.version,
+        name: migration.name.to_string(),
+        already_applied: true,
+        affected: 0,
+    }
+}
+
+async fn get_applied_versions(client: &Client) -> Result<Vec<u32>, Error> {
+    let applied: Vec<SchemaMigration> = match client
+        .database(schema_migration::DATABASE)
+        .collection::<SchemaMigration>(schema_migration::COLLECTION)
+        .find(doc! { "version": { "$exists": true } }, None)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(applied) => applied,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read applied migrations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to read applied migrations: {}", error),
+            ));
+        }
+    };
+
+    Ok(applied
+        .into_iter()
+        .map(|migration| migration.version)
+        .collect())
+}
+
+async fn record_applied(migration: &Migration, client: &Client) -> Result<(), Error> {
+    let applied_at = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+
+    let record = SchemaMigration {
+        version: migration.version,
+        name: migration.name.to_string(),
+        applied_at,
+    };
+
+    match client
+        .database(schema_migration::DATABASE)
+        .collection::<SchemaMigration>(schema_migration::COLLECTION)
+        .insert_one(record, None)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!(
+                "failed to record migration {} '{}': {}",
+                migration.version, migration.name, error
+            ),
+        )),
+    }
+}
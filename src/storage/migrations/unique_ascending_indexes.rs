@@ -0,0 +1,93 @@
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::{invitation, organization};
+use crate::storage::migrations::migration::MigrationFuture;
+use mongodb::bson::Document;
+use mongodb::error::ErrorKind as MongoDbErrorKind;
+use mongodb::Client;
+
+const NAMESPACE_NOT_FOUND_ERROR_CODE: i32 = 26;
+
+/// Unique text indexes tokenize their values, so two organization names sharing a word
+/// collided. They are dropped here and replaced by ascending indexes on initialization.
+const TEXT_INDEXES: &[(&str, &str, &str)] = &[
+    (
+        organization::DATABASE,
+        organization::COLLECTION,
+        "name_text",
+    ),
+    (invitation::DATABASE, invitation::COLLECTION, "code_text"),
+];
+
+pub fn inspect(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let mut existing = 0u64;
+
+        for (database, collection, index) in TEXT_INDEXES {
+            if index_exists(client, database, collection, index).await? {
+                existing += 1;
+            }
+        }
+
+        Ok(existing)
+    })
+}
+
+pub fn apply(client: &Client) -> MigrationFuture<'_> {
+    Box::pin(async move {
+        let mut dropped = 0u64;
+
+        for (database, collection, index) in TEXT_INDEXES {
+            if !index_exists(client, database, collection, index).await? {
+                continue;
+            }
+
+            match client
+                .database(database)
+                .collection::<Document>(collection)
+                .drop_index(*index, None)
+                .await
+            {
+                Ok(_) => dropped += 1,
+                Err(error) => {
+                    return Err(Error::new(
+                        ErrorKind::StorageFailure,
+                        format!("failed to drop index '{}': {}", index, error),
+                    ));
+                }
+            }
+        }
+
+        Ok(dropped)
+    })
+}
+
+async fn index_exists(
+    client: &Client,
+    database: &str,
+    collection: &str,
+    index: &str,
+) -> Result<bool, Error> {
+    match client
+        .database(database)
+        .collection::<Document>(collection)
+        .list_index_names()
+        .await
+    {
+        Ok(names) => Ok(names.iter().any(|name| name == index)),
+        // Collections which do not exist yet have no indexes.
+        Err(error) if is_namespace_not_found_error(&error) => Ok(false),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to list indexes: {}", error),
+        )),
+    }
+}
+
+fn is_namespace_not_found_error(error: &mongodb::error::Error) -> bool {
+    match error.kind.as_ref() {
+        MongoDbErrorKind::Command(command_error) => {
+            command_error.code == NAMESPACE_NOT_FOUND_ERROR_CODE
+        }
+        _ => false,
+    }
+}
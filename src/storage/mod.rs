@@ -1,4 +1,12 @@
+mod duplicate_key;
 pub mod elements;
 mod executors;
-pub(crate) mod init;
+pub mod init;
+mod migrations;
+mod mongodb_probe;
 mod mongodb_request_dispatch;
+mod organization_purge;
+mod outbox;
+mod outbox_relay;
+mod transaction;
+mod user_changes;
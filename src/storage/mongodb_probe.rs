@@ -0,0 +1,20 @@
+use crate::health::heartbeat::Heartbeat;
+use mongodb::bson::doc;
+use mongodb::Client;
+
+/// Pings MongoDB once per heartbeat interval, beating only while it answers in time.
+pub async fn run(client: Client, heartbeat: Heartbeat) {
+    let database = client.database("admin");
+
+    loop {
+        let ping = database.run_command(doc! { "ping": 1i32 }, None);
+
+        match tokio::time::timeout(heartbeat.interval(), ping).await {
+            Ok(Ok(_)) => heartbeat.beat(),
+            Ok(Err(error)) => log::warn!("failed to ping mongodb: {}", error),
+            Err(_) => log::warn!("mongodb ping timed out"),
+        }
+
+        tokio::time::sleep(heartbeat.interval()).await;
+    }
+}
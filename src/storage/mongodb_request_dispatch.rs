@@ -1,59 +1,150 @@
 use async_channel::Receiver;
 use mongodb::Client;
+use std::time::{Duration, Instant};
+use tracing::{Instrument, Span};
 
+use crate::deadline;
+use crate::dispatcher_pool::Retirement;
+use crate::error::{Error, ErrorKind};
+use crate::health::heartbeat::Heartbeat;
 use crate::logic::storage_request::StorageRequest;
+use crate::telemetry::metrics;
+use crate::telemetry::traced_channel::Traced;
 
+#[derive(Clone)]
 pub struct MongoDbRequestDispatch {
     client: Client,
-    request_receiver: Receiver<StorageRequest>,
+    request_receiver: Receiver<Traced<StorageRequest>>,
+    organization_retention: Duration,
+    heartbeat: Heartbeat,
+    retirement: Retirement,
 }
 
 impl MongoDbRequestDispatch {
     pub fn new(
         client: Client,
-        request_receiver: Receiver<StorageRequest>,
+        request_receiver: Receiver<Traced<StorageRequest>>,
+        organization_retention: Duration,
+        heartbeat: Heartbeat,
+        retirement: Retirement,
     ) -> MongoDbRequestDispatch {
         MongoDbRequestDispatch {
             client,
             request_receiver,
+            organization_retention,
+            heartbeat,
+            retirement,
         }
     }
 
+    /// Runs until the requests channel is closed and every queued request has been executed,
+    /// or until the dispatcher is retired.
     pub async fn run(self) {
         loop {
-            match self.request_receiver.recv().await {
-                Ok(request) => {
-                    log::info!("received storage request");
-
-                    let result = match request {
-                        StorageRequest::Organization(action) => {
-                            crate::storage::executors::organization::execute(action, &self.client)
-                                .await
-                        }
-                        StorageRequest::OrganizationRoot(action) => {
-                            crate::storage::executors::organization_root::execute(
-                                action,
-                                &self.client,
-                            )
-                            .await
-                        }
-                        StorageRequest::User(action) => {
-                            crate::storage::executors::user::execute(action, &self.client).await
-                        }
-                        StorageRequest::Invitation(action) => {
-                            crate::storage::executors::invitation::execute(action, &self.client)
-                                .await
+            if self.retirement.is_retired() {
+                log::info!("storage dispatcher retired, stopping");
+                break;
+            }
+
+            let received =
+                match tokio::time::timeout(self.heartbeat.interval(), self.request_receiver.recv())
+                    .await
+                {
+                    Ok(received) => received,
+                    Err(_) => {
+                        self.heartbeat.beat();
+                        continue;
+                    }
+                };
+
+            self.heartbeat.beat();
+
+            match received {
+                Ok(Traced {
+                    span,
+                    deadline,
+                    request,
+                }) => {
+                    metrics::record_queued_requests("storage", self.request_receiver.len());
+
+                    span.in_scope(|| log::info!("received storage request"));
+
+                    if request.is_abandoned() {
+                        span.in_scope(|| {
+                            log::info!("skipping storage request whose requester stopped waiting")
+                        });
+                        continue;
+                    }
+
+                    let element = request.element();
+                    let action = request.action();
+                    let started = Instant::now();
+
+                    // Dropping an execution aborts its transaction, if any.
+                    let result = if deadline::has_expired(deadline) {
+                        Err(Error::new(
+                            ErrorKind::Timeout,
+                            "deadline reached before execution",
+                        ))
+                    } else {
+                        match deadline::within(deadline, self.execute(request, &span)).await {
+                            Some(result) => result,
+                            None => Err(Error::new(
+                                ErrorKind::Timeout,
+                                "deadline reached during execution",
+                            )),
                         }
                     };
 
+                    metrics::record_storage_request(element, action, started.elapsed(), &result);
+
                     if let Err(error) = result {
-                        log::info!("failed to execute storage request: {}", error);
+                        span.in_scope(|| {
+                            log::info!("failed to execute storage request: {}", error)
+                        });
                     }
                 }
-                Err(error) => {
-                    log::error!("failed to receive request: {}", error);
+                Err(_) => {
+                    log::info!("storage requests channel closed and drained, stopping dispatcher");
+                    break;
                 }
             }
         }
     }
+
+    async fn execute(&self, request: StorageRequest, span: &Span) -> Result<(), Error> {
+        let storage_span = tracing::info_span!(parent: span, "storage");
+
+        match request {
+            StorageRequest::Organization(action) => {
+                crate::storage::executors::organization::execute(
+                    action,
+                    &self.client,
+                    self.organization_retention,
+                )
+                .instrument(storage_span)
+                .await
+            }
+            StorageRequest::OrganizationRoot(action) => {
+                crate::storage::executors::organization_root::execute(action, &self.client)
+                    .instrument(storage_span)
+                    .await
+            }
+            StorageRequest::User(action) => {
+                crate::storage::executors::user::execute(action, &self.client)
+                    .instrument(storage_span)
+                    .await
+            }
+            StorageRequest::Invitation(action) => {
+                crate::storage::executors::invitation::execute(action, &self.client)
+                    .instrument(storage_span)
+                    .await
+            }
+            StorageRequest::Audit(action) => {
+                crate::storage::executors::audit::execute(action, &self.client)
+                    .instrument(storage_span)
+                    .await
+            }
+        }
+    }
 }
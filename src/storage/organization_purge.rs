@@ -0,0 +1,191 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::elements::domain_event::{DomainEvent, DomainEventPayload};
+use crate::storage::elements::invitation::Invitation;
+use crate::storage::elements::organization::Organization;
+use crate::storage::elements::user::User;
+use crate::storage::elements::{invitation, organization, user};
+use crate::storage::{outbox, transaction};
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use mongodb::bson::oid::ObjectId;
+use mongodb::{Client, ClientSession};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+/// Periodically hard-deletes the organizations whose retention period has expired,
+/// along with their invitations and memberships.
+/// Returns once stopped, after the purge in progress, if any.
+pub async fn run(
+    client: Client,
+    organization_retention: Duration,
+    interval: Duration,
+    mut stop: watch::Receiver<bool>,
+) {
+    while !*stop.borrow() {
+        match purge_expired(&client, organization_retention).await {
+            Ok(0) => (),
+            Ok(purged) => log::info!("purged {} deleted organizations", purged),
+            Err(error) => log::error!("failed to purge deleted organizations: {}", error),
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => (),
+            _ = stop.changed() => (),
+        }
+    }
+}
+
+async fn purge_expired(client: &Client, organization_retention: Duration) -> Result<u64, Error> {
+    let deleted_before = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+        .saturating_sub(organization_retention.as_secs());
+
+    let expired: Vec<Organization> = match client
+        .database(organization::DATABASE)
+        .collection::<Organization>(organization::COLLECTION)
+        .find(
+            doc! { "deleted_at": { "$lt": deleted_before as i64 } },
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(expired) => expired,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read deleted organizations: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to read deleted organizations: {}", error),
+            ));
+        }
+    };
+
+    for organization in &expired {
+        let mut session = transaction::start(client).await?;
+
+        let removed_users = match purge_organization(client, &organization._id, &mut session).await
+        {
+            Ok(removed_users) => removed_users,
+            Err(error) => {
+                transaction::abort(&mut session).await;
+
+                return Err(error);
+            }
+        };
+
+        let events: Vec<DomainEvent> = removed_users
+            .into_iter()
+            .map(|user_id| {
+                DomainEvent::new(DomainEventPayload::UserRemoved {
+                    organization_id: organization._id.to_string(),
+                    user_id,
+                })
+            })
+            .collect();
+
+        outbox::commit(&events, &[], client, &mut session).await?;
+    }
+
+    Ok(expired.len() as u64)
+}
+
+/// Every deletion belongs to the session's transaction, so an interrupted purge
+/// leaves no partially removed organization behind and is retried on the next run.
+/// Returns the users whose membership has been removed.
+async fn purge_organization(
+    client: &Client,
+    organization_id: &ObjectId,
+    session: &mut ClientSession,
+) -> Result<Vec<String>, Error> {
+    if let Err(error) = client
+        .database(invitation::DATABASE)
+        .collection::<Invitation>(invitation::COLLECTION)
+        .delete_many_with_session(doc! { "organization_id": organization_id }, None, session)
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to purge invitations: {}", error),
+        ));
+    }
+
+    let users = client
+        .database(user::DATABASE)
+        .collection::<User>(user::COLLECTION);
+
+    let members: Vec<User> = match users
+        .find_with_session(
+            doc! { "organizations.organization_id": organization_id.to_string() },
+            None,
+            session,
+        )
+        .await
+    {
+        Ok(mut cursor) => match cursor.stream(session).try_collect().await {
+            Ok(members) => members,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read members: {}", error),
+                ));
+            }
+        },
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to read members: {}", error),
+            ));
+        }
+    };
+
+    if let Err(error) = users
+        .update_many_with_session(
+            doc! { "organizations.organization_id": organization_id.to_string() },
+            doc! {
+                "$pull": { "organizations": { "organization_id": organization_id.to_string() } },
+                "$inc": { "version": 1i64 },
+            },
+            None,
+            session,
+        )
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to purge memberships: {}", error),
+        ));
+    }
+
+    // Users without organizations are equivalent to users which have never been stored.
+    if let Err(error) = users
+        .delete_many_with_session(doc! { "organizations": { "$size": 0 } }, None, session)
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to purge users without organizations: {}", error),
+        ));
+    }
+
+    if let Err(error) = client
+        .database(organization::DATABASE)
+        .collection::<Organization>(organization::COLLECTION)
+        .delete_one_with_session(doc! { "_id": organization_id }, None, session)
+        .await
+    {
+        return Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to purge organization: {}", error),
+        ));
+    }
+
+    Ok(members.into_iter().map(|member| member.id).collect())
+}
@@ -0,0 +1,78 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::elements::audit_record::AuditRecord;
+use crate::logic::elements::domain_event::{DomainEvent, OUTPUT_ELEMENT_ID};
+use crate::storage::elements::outbox;
+use crate::storage::executors::audit;
+use crate::storage::transaction;
+use mongodb::bson::{doc, to_document, Document};
+use mongodb::{Client, ClientSession};
+
+/// Writes the events along with the audit records of the mutation and commits
+/// the session's transaction, which is aborted on failure.
+pub async fn commit(
+    events: &[DomainEvent],
+    audit_records: &[AuditRecord],
+    client: &Client,
+    session: &mut ClientSession,
+) -> Result<(), Error> {
+    if let Err(error) = write(events, client, session).await {
+        transaction::abort(session).await;
+
+        return Err(error);
+    }
+
+    if let Err(error) = audit::write(audit_records, client, session).await {
+        transaction::abort(session).await;
+
+        return Err(error);
+    }
+
+    transaction::commit(session).await
+}
+
+/// Writes the events within the session's transaction, so they are only published
+/// if the mutation which caused them is committed.
+pub async fn write(
+    events: &[DomainEvent],
+    client: &Client,
+    session: &mut ClientSession,
+) -> Result<(), Error> {
+    if events.is_empty() {
+        return Ok(());
+    }
+
+    let mut entries = Vec::with_capacity(events.len());
+
+    for event in events {
+        let payload = match to_document(event) {
+            Ok(payload) => payload,
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::InternalFailure,
+                    format!("failed to serialize domain event: {}", error),
+                ));
+            }
+        };
+
+        entries.push(doc! {
+            "_id": &event.id,
+            "element": OUTPUT_ELEMENT_ID,
+            "payload": payload,
+            "attempts": 0i64,
+            "available_at": event.occurred_at as i64,
+        });
+    }
+
+    match client
+        .database(outbox::DATABASE)
+        .collection::<Document>(outbox::COLLECTION)
+        .insert_many_with_session(entries, None, session)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to write events to the outbox: {}", error),
+        )),
+    }
+}
@@ -0,0 +1,140 @@
+use crate::api::output::publisher::ConfirmedPublisher;
+use crate::error::{Error, ErrorKind};
+use crate::storage::elements::outbox;
+use crate::storage::elements::outbox::OutboxEntry;
+use mongodb::bson::{doc, Bson};
+use mongodb::options::{FindOneAndUpdateOptions, ReturnDocument};
+use mongodb::Client;
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+/// Seconds during which a claimed entry is not relayed again,
+/// after which it is reclaimed in case the relay stopped before removing it.
+const CLAIM_LEASE_IN_SECONDS: u64 = 60;
+const MAX_RETRY_DELAY_IN_SECONDS: u64 = 300;
+
+/// Periodically publishes the outbox entries, removing them once the broker has confirmed them.
+///
+/// Delivery is at-least-once: an entry whose publication is confirmed but whose removal fails,
+/// or whose publication is interrupted by a restart, is relayed again once its lease expires.
+/// Consumers discard duplicates through the event's `id`.
+///
+/// Once stopped, the entries still available are relayed a last time before returning.
+pub async fn run(
+    client: Client,
+    publisher: ConfirmedPublisher,
+    interval: Duration,
+    mut stop: watch::Receiver<bool>,
+) {
+    loop {
+        match relay_available(&client, &publisher).await {
+            Ok(0) => (),
+            Ok(relayed) => log::debug!("relayed {} outbox events", relayed),
+            Err(error) => log::error!("failed to relay outbox events: {}", error),
+        }
+
+        if *stop.borrow() {
+            return;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => (),
+            _ = stop.changed() => (),
+        }
+    }
+}
+
+async fn relay_available(client: &Client, publisher: &ConfirmedPublisher) -> Result<u64, Error> {
+    let mut relayed = 0u64;
+
+    while let Some(entry) = claim(client).await? {
+        let event = Bson::Document(entry.payload).into_relaxed_extjson();
+
+        if let Err(error) = publisher.publish(&event).await {
+            postpone(client, &entry._id, entry.attempts).await?;
+
+            return Err(error);
+        }
+
+        remove(client, &entry._id).await?;
+        relayed += 1;
+    }
+
+    Ok(relayed)
+}
+
+/// Leases the oldest available entry, so concurrent relays do not hand it over at the same time.
+async fn claim(client: &Client) -> Result<Option<OutboxEntry>, Error> {
+    let now = unix_timestamp();
+
+    let options = FindOneAndUpdateOptions::builder()
+        .sort(doc! { "available_at": 1i32 })
+        .return_document(ReturnDocument::After)
+        .build();
+
+    match client
+        .database(outbox::DATABASE)
+        .collection::<OutboxEntry>(outbox::COLLECTION)
+        .find_one_and_update(
+            doc! { "available_at": { "$lte": now as i64 } },
+            doc! {
+                "$set": { "available_at": (now + CLAIM_LEASE_IN_SECONDS) as i64 },
+                "$inc": { "attempts": 1i64 },
+            },
+            options,
+        )
+        .await
+    {
+        Ok(entry) => Ok(entry),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to claim outbox entry: {}", error),
+        )),
+    }
+}
+
+/// Delays the next attempt exponentially, up to `MAX_RETRY_DELAY_IN_SECONDS`.
+async fn postpone(client: &Client, id: &str, attempts: u32) -> Result<(), Error> {
+    let delay = 2u64
+        .saturating_pow(attempts)
+        .min(MAX_RETRY_DELAY_IN_SECONDS);
+
+    match client
+        .database(outbox::DATABASE)
+        .collection::<OutboxEntry>(outbox::COLLECTION)
+        .update_one(
+            doc! { "_id": id },
+            doc! { "$set": { "available_at": (unix_timestamp() + delay) as i64 } },
+            None,
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to postpone outbox entry: {}", error),
+        )),
+    }
+}
+
+async fn remove(client: &Client, id: &str) -> Result<(), Error> {
+    match client
+        .database(outbox::DATABASE)
+        .collection::<OutboxEntry>(outbox::COLLECTION)
+        .delete_one(doc! { "_id": id }, None)
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to remove relayed outbox entry: {}", error),
+        )),
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
@@ -0,0 +1,95 @@
+use crate::error::{Error, ErrorKind};
+use mongodb::bson::doc;
+use mongodb::error::UNKNOWN_TRANSACTION_COMMIT_RESULT;
+use mongodb::options::SessionOptions;
+use mongodb::{Client, ClientSession};
+
+const MAX_COMMIT_ATTEMPTS: u32 = 3;
+
+/// Fails unless MongoDB is deployed as a replica set, which transactions require,
+/// so a misconfigured deployment is noticed on startup rather than on the first write.
+pub async fn ensure_replica_set(client: &Client) -> Result<(), Error> {
+    match client
+        .database("admin")
+        .run_command(doc! { "hello": 1i32 }, None)
+        .await
+    {
+        Ok(reply) if reply.contains_key("setName") => Ok(()),
+        Ok(_) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            "mongodb is not deployed as a replica set, which transactions require",
+        )),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to query the mongodb deployment: {}", error),
+        )),
+    }
+}
+
+/// Starts a session with an open transaction.
+/// Transactions require MongoDB to be deployed as a replica set.
+pub async fn start(client: &Client) -> Result<ClientSession, Error> {
+    let mut session = match client.start_session(None).await {
+        Ok(session) => session,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to start session: {}", error),
+            ));
+        }
+    };
+
+    match session.start_transaction(None).await {
+        Ok(_) => Ok(session),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to start transaction: {}", error),
+        )),
+    }
+}
+
+/// Starts a session whose reads all share the same point in time, without a transaction.
+/// Snapshot reads require MongoDB 5.0 or later, deployed as a replica set.
+pub async fn snapshot(client: &Client) -> Result<ClientSession, Error> {
+    let options = SessionOptions::builder().snapshot(true).build();
+
+    match client.start_session(options).await {
+        Ok(session) => Ok(session),
+        Err(error) => Err(Error::new(
+            ErrorKind::StorageFailure,
+            format!("failed to start snapshot session: {}", error),
+        )),
+    }
+}
+
+/// Commits are idempotent, so they are retried while their result is unknown.
+pub async fn commit(session: &mut ClientSession) -> Result<(), Error> {
+    let mut attempts = 0u32;
+
+    loop {
+        attempts += 1;
+
+        match session.commit_transaction().await {
+            Ok(_) => return Ok(()),
+            Err(error)
+                if attempts < MAX_COMMIT_ATTEMPTS
+                    && error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) =>
+            {
+                log::warn!("retrying commit with unknown result: {}", error);
+            }
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to commit transaction: {}", error),
+                ));
+            }
+        }
+    }
+}
+
+/// Failures are only logged, since the transaction is aborted anyway once the session ends.
+pub async fn abort(session: &mut ClientSession) {
+    if let Err(error) = session.abort_transaction().await {
+        log::warn!("failed to abort transaction: {}", error);
+    }
+}
@@ -0,0 +1,83 @@
+use crate::error::{Error, ErrorKind};
+use crate::logic::permission_cache::PermissionCache;
+use crate::storage::elements::user;
+use futures::TryStreamExt;
+use mongodb::bson::Document;
+use mongodb::options::{ChangeStreamOptions, FullDocumentType};
+use mongodb::Client;
+use std::time::Duration;
+use tokio::sync::watch;
+
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Invalidates the cached permissions of every user changed by any process,
+/// such as other instances, the organization purge or the administrative tool.
+/// Changes of this instance are invalidated as they are applied, without waiting for the stream.
+pub async fn run(
+    client: Client,
+    permission_cache: PermissionCache,
+    mut stop: watch::Receiver<bool>,
+) {
+    while !*stop.borrow() {
+        // Changes may have been missed while the stream was closed.
+        permission_cache.clear();
+
+        tokio::select! {
+            result = watch_changes(&client, &permission_cache) => {
+                if let Err(error) = result {
+                    log::error!("failed to watch user changes: {}", error);
+                }
+            }
+            _ = stop.changed() => return,
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(RETRY_DELAY) => (),
+            _ = stop.changed() => (),
+        }
+    }
+}
+
+async fn watch_changes(client: &Client, permission_cache: &PermissionCache) -> Result<(), Error> {
+    let options = ChangeStreamOptions::builder()
+        .full_document(Some(FullDocumentType::UpdateLookup))
+        .build();
+
+    let mut changes = match client
+        .database(user::DATABASE)
+        .collection::<Document>(user::COLLECTION)
+        .watch(None, options)
+        .await
+    {
+        Ok(changes) => changes,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::StorageFailure,
+                format!("failed to open change stream: {}", error),
+            ))
+        }
+    };
+
+    loop {
+        let change = match changes.try_next().await {
+            Ok(Some(change)) => change,
+            Ok(None) => return Ok(()),
+            Err(error) => {
+                return Err(Error::new(
+                    ErrorKind::StorageFailure,
+                    format!("failed to read change stream: {}", error),
+                ))
+            }
+        };
+
+        // Deleted users are no longer found, so their identifier is unknown.
+        match change
+            .full_document
+            .as_ref()
+            .and_then(|user| user.get_str("id").ok())
+        {
+            Some(user_id) => permission_cache.invalidate_user(user_id),
+            None => permission_cache.clear(),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+use crate::telemetry::metrics;
+use futures::FutureExt;
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::time::{Duration, Instant};
+
+const INITIAL_RESTART_DELAY: Duration = Duration::from_millis(100);
+const MAX_RESTART_DELAY: Duration = Duration::from_secs(30);
+
+/// Runs a dispatcher of the layer, restarting it with an exponential backoff whenever it panics.
+/// Returns once the dispatcher stops on its own, such as when its channel is closed.
+pub async fn supervise<F, Fut>(layer: &'static str, dispatcher: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = ()>,
+{
+    let mut restart_delay = INITIAL_RESTART_DELAY;
+
+    loop {
+        let started = Instant::now();
+
+        let result = {
+            let _live = LiveDispatcher::new(layer);
+
+            AssertUnwindSafe(dispatcher()).catch_unwind().await
+        };
+
+        let panic = match result {
+            Ok(()) => return,
+            Err(panic) => panic,
+        };
+
+        // A dispatcher which had been running for a while is not failing repeatedly.
+        if started.elapsed() > MAX_RESTART_DELAY {
+            restart_delay = INITIAL_RESTART_DELAY;
+        }
+
+        log::error!(
+            "{} dispatcher panicked: {}, restarting in {} ms",
+            layer,
+            panic_message(panic.as_ref()),
+            restart_delay.as_millis()
+        );
+
+        metrics::record_dispatcher_restart(layer);
+
+        tokio::time::sleep(restart_delay).await;
+        restart_delay = (restart_delay * 2).min(MAX_RESTART_DELAY);
+    }
+}
+
+/// Counts a dispatcher as live until dropped, including when its task is aborted.
+struct LiveDispatcher {
+    layer: &'static str,
+}
+
+impl LiveDispatcher {
+    fn new(layer: &'static str) -> LiveDispatcher {
+        metrics::record_live_dispatchers(layer, 1.0);
+
+        LiveDispatcher { layer }
+    }
+}
+
+impl Drop for LiveDispatcher {
+    fn drop(&mut self) {
+        metrics::record_live_dispatchers(self.layer, -1.0);
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> &str {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        return message;
+    }
+
+    match panic.downcast_ref::<String>() {
+        Some(message) => message.as_str(),
+        None => "unknown cause",
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn restart_dispatcher_until_it_stops_on_its_own() {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+
+    let runs = Arc::new(AtomicU32::new(0));
+
+    supervise("TEST", || {
+        let runs = runs.clone();
+
+        async move {
+            if runs.fetch_add(1, Ordering::SeqCst) < 2 {
+                panic!("TEST_PANIC");
+            }
+        }
+    })
+    .await;
+
+    assert_eq!(runs.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,149 @@
+use crate::error::{Error, ErrorKind};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::TracerProvider;
+use opentelemetry_sdk::Resource;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::EnvFilter;
+
+const SERVICE_NAME: &str = "cooplan-organization";
+const DEFAULT_FILTER: &str = "info";
+
+/// Logs every event, including those of the `log` crate, along with the fields of its spans.
+/// Spans are exported over OTLP/gRPC as well if an endpoint is provided,
+/// in which case the returned provider must be kept alive while exporting.
+pub fn initialize(otlp_endpoint: Option<String>) -> Result<Option<TracerProvider>, Error> {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_FILTER));
+
+    let tracer_provider = match otlp_endpoint {
+        Some(otlp_endpoint) => Some(tracer_provider(otlp_endpoint)?),
+        None => None,
+    };
+
+    let otlp_layer = tracer_provider.as_ref().map(|tracer_provider| {
+        tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME))
+    });
+
+    match tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer())
+        .with(otlp_layer)
+        .try_init()
+    {
+        Ok(_) => Ok(tracer_provider),
+        Err(error) => Err(Error::new(
+            ErrorKind::AutoConfigFailure,
+            format!("failed to initialize tracing: {}", error),
+        )),
+    }
+}
+
+fn tracer_provider(otlp_endpoint: String) -> Result<TracerProvider, Error> {
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .with_endpoint(otlp_endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("failed to build otlp exporter: {}", error),
+            ));
+        }
+    };
+
+    Ok(TracerProvider::builder()
+        .with_batch_exporter(exporter, opentelemetry_sdk::runtime::Tokio)
+        .with_resource(Resource::new(vec![KeyValue::new(
+            "service.name",
+            SERVICE_NAME,
+        )]))
+        .build())
+}
+
+#[cfg(test)]
+mod collector {
+    use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+        TraceService, TraceServiceServer,
+    };
+    use opentelemetry_proto::tonic::collector::trace::v1::{
+        ExportTraceServiceRequest, ExportTraceServiceResponse,
+    };
+    use std::net::SocketAddr;
+    use tokio::sync::mpsc::{Receiver, Sender};
+
+    /// Stand-in of an OpenTelemetry collector, forwarding every export it receives.
+    struct Collector {
+        export_sender: Sender<ExportTraceServiceRequest>,
+    }
+
+    #[tonic::async_trait]
+    impl TraceService for Collector {
+        async fn export(
+            &self,
+            request: tonic::Request<ExportTraceServiceRequest>,
+        ) -> Result<tonic::Response<ExportTraceServiceResponse>, tonic::Status> {
+            let _ = self.export_sender.send(request.into_inner()).await;
+
+            Ok(tonic::Response::new(ExportTraceServiceResponse {
+                partial_success: None,
+            }))
+        }
+    }
+
+    pub async fn start() -> (SocketAddr, Receiver<ExportTraceServiceRequest>) {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+
+        let (export_sender, export_receiver) = tokio::sync::mpsc::channel(16);
+
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(TraceServiceServer::new(Collector { export_sender }))
+                .serve_with_incoming(tokio_stream::wrappers::TcpListenerStream::new(listener)),
+        );
+
+        (address, export_receiver)
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn export_spans_with_their_correlation_id() {
+    let (address, mut export_receiver) = collector::start().await;
+
+    let tracer_provider = tracer_provider(format!("http://{}", address)).unwrap();
+
+    let subscriber = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer(SERVICE_NAME)));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let request_span = tracing::info_span!("request", correlation_id = "CORRELATION_ID");
+        let _ = request_span.in_scope(|| tracing::info_span!("logic").entered());
+    });
+
+    for result in tracer_provider.force_flush() {
+        result.unwrap();
+    }
+
+    let export = export_receiver.recv().await.unwrap();
+
+    let spans: Vec<_> = export
+        .resource_spans
+        .iter()
+        .flat_map(|resource_spans| &resource_spans.scope_spans)
+        .flat_map(|scope_spans| &scope_spans.spans)
+        .collect();
+
+    let request_span = spans.iter().find(|span| span.name == "request").unwrap();
+    let logic_span = spans.iter().find(|span| span.name == "logic").unwrap();
+
+    assert_eq!(request_span.span_id, logic_span.parent_span_id);
+    assert!(request_span
+        .attributes
+        .iter()
+        .any(|attribute| attribute.key == "correlation_id"));
+}
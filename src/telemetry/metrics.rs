@@ -0,0 +1,169 @@
+use crate::error::{Error, ErrorKind};
+use metrics_exporter_prometheus::{Matcher, PrometheusBuilder};
+use mongodb::event::command::{CommandEventHandler, CommandFailedEvent, CommandSucceededEvent};
+use std::net::SocketAddr;
+use std::time::Duration;
+
+const LATENCY_BUCKETS_IN_SECONDS: &[f64] = &[
+    0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Serves the metrics in the Prometheus text format on the address, at any path.
+/// Metrics are not recorded if no address is provided.
+pub fn initialize(address: Option<String>) -> Result<(), Error> {
+    let address = match address {
+        Some(address) => address,
+        None => return Ok(()),
+    };
+
+    let address = match address.parse::<SocketAddr>() {
+        Ok(address) => address,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("invalid metrics address '{}': {}", address, error),
+            ));
+        }
+    };
+
+    let builder = match PrometheusBuilder::new()
+        .with_http_listener(address)
+        .set_buckets_for_metric(
+            Matcher::Suffix("duration_seconds".to_string()),
+            LATENCY_BUCKETS_IN_SECONDS,
+        ) {
+        Ok(builder) => builder,
+        Err(error) => {
+            return Err(Error::new(
+                ErrorKind::AutoConfigFailure,
+                format!("failed to configure metrics buckets: {}", error),
+            ));
+        }
+    };
+
+    match builder.install() {
+        Ok(_) => Ok(()),
+        Err(error) => Err(Error::new(
+            ErrorKind::AutoConfigFailure,
+            format!("failed to serve metrics: {}", error),
+        )),
+    }
+}
+
+pub fn record_logic_request(
+    element: &'static str,
+    action: &'static str,
+    elapsed: Duration,
+    result: &Result<(), Error>,
+) {
+    record_request("logic", element, action, elapsed, result);
+}
+
+pub fn record_storage_request(
+    element: &'static str,
+    action: &'static str,
+    elapsed: Duration,
+    result: &Result<(), Error>,
+) {
+    record_request("storage", element, action, elapsed, result);
+}
+
+/// Requests waiting in the channel which feeds the layer's dispatchers.
+pub fn record_queued_requests(layer: &'static str, queued: usize) {
+    metrics::gauge!(format!("{}_requests_queued", layer)).set(queued as f64);
+}
+
+/// Adds `change` to the number of running dispatchers of the layer.
+pub fn record_live_dispatchers(layer: &'static str, change: f64) {
+    metrics::gauge!(format!("{}_dispatchers_live", layer)).increment(change);
+}
+
+pub fn record_dispatcher_restart(layer: &'static str) {
+    metrics::counter!(format!("{}_dispatcher_restarts_total", layer)).increment(1);
+}
+
+pub fn record_rate_limited(element: &'static str, action: &str) {
+    metrics::counter!(
+        "rate_limited_requests_total",
+        "element" => element,
+        "action" => action.to_string()
+    )
+    .increment(1);
+}
+
+pub fn record_invitation_lockout() {
+    metrics::counter!("invitation_lockouts_total").increment(1);
+}
+
+fn record_request(
+    layer: &'static str,
+    element: &'static str,
+    action: &'static str,
+    elapsed: Duration,
+    result: &Result<(), Error>,
+) {
+    let labels = [("element", element), ("action", action)];
+
+    metrics::counter!(format!("{}_requests_total", layer), &labels).increment(1);
+    metrics::histogram!(format!("{}_request_duration_seconds", layer), &labels)
+        .record(elapsed.as_secs_f64());
+
+    if let Err(error) = result {
+        metrics::counter!(
+            format!("{}_request_errors_total", layer),
+            "element" => element,
+            "action" => action,
+            "kind" => format!("{:?}", error.kind)
+        )
+        .increment(1);
+    }
+}
+
+/// Times every command the executors send to MongoDB, by command name.
+pub struct MongoDbCommandMetrics;
+
+impl CommandEventHandler for MongoDbCommandMetrics {
+    fn handle_command_succeeded_event(&self, event: CommandSucceededEvent) {
+        metrics::histogram!("mongodb_command_duration_seconds", "command" => event.command_name)
+            .record(event.duration.as_secs_f64());
+    }
+
+    fn handle_command_failed_event(&self, event: CommandFailedEvent) {
+        metrics::histogram!(
+            "mongodb_command_duration_seconds",
+            "command" => event.command_name.clone()
+        )
+        .record(event.duration.as_secs_f64());
+        metrics::counter!("mongodb_command_failures_total", "command" => event.command_name)
+            .increment(1);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn render_request_counts_and_errors_by_kind() {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let handle = recorder.handle();
+
+    metrics::with_local_recorder(&recorder, || {
+        record_logic_request("organization", "create", Duration::from_millis(5), &Ok(()));
+        record_logic_request(
+            "organization",
+            "create",
+            Duration::from_millis(5),
+            &Err(Error::new(
+                ErrorKind::NameAlreadyTaken,
+                "name already taken",
+            )),
+        );
+        record_queued_requests("storage", 3);
+    });
+
+    let rendered = handle.render();
+
+    assert!(rendered.contains(r#"logic_requests_total{element="organization",action="create"} 2"#));
+    assert!(rendered.contains(
+        r#"logic_request_errors_total{element="organization",action="create",kind="NameAlreadyTaken"} 1"#
+    ));
+    assert!(rendered.contains("storage_requests_queued 3"));
+}
@@ -0,0 +1,57 @@
+use crate::deadline;
+use async_channel::{Receiver, SendError, Sender};
+use tokio::time::Instant;
+use tracing::Span;
+
+/// Request along with the span it has been sent from, so its execution is traced
+/// as part of the AMQP request which caused it, and the deadline by which it must be answered.
+pub struct Traced<T> {
+    pub span: Span,
+    pub deadline: Option<Instant>,
+    pub request: T,
+}
+
+/// Sender which attaches the current span and deadline to every request.
+pub struct TracedSender<T> {
+    sender: Sender<Traced<T>>,
+}
+
+impl<T> TracedSender<T> {
+    pub async fn send(&self, request: T) -> Result<(), SendError<T>> {
+        let traced = Traced {
+            span: Span::current(),
+            deadline: deadline::current(),
+            request,
+        };
+
+        match self.sender.send(traced).await {
+            Ok(_) => Ok(()),
+            Err(error) => Err(SendError(error.0.request)),
+        }
+    }
+
+    /// Refuses any further request, while the queued ones can still be received.
+    pub fn close(&self) {
+        self.sender.close();
+    }
+}
+
+impl<T> Clone for TracedSender<T> {
+    fn clone(&self) -> Self {
+        TracedSender {
+            sender: self.sender.clone(),
+        }
+    }
+}
+
+impl<T> From<Sender<Traced<T>>> for TracedSender<T> {
+    fn from(sender: Sender<Traced<T>>) -> Self {
+        TracedSender { sender }
+    }
+}
+
+pub fn bounded<T>(capacity: usize) -> (TracedSender<T>, Receiver<Traced<T>>) {
+    let (sender, receiver) = async_channel::bounded(capacity);
+
+    (TracedSender { sender }, receiver)
+}